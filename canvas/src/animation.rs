@@ -0,0 +1,291 @@
+use crate::draw::*;
+use crate::path::*;
+use crate::color::*;
+use crate::gradient::*;
+use crate::transform2d::*;
+
+use futures::prelude::*;
+use futures::stream;
+
+use std::sync::*;
+use std::time::{Duration};
+
+/// The fastest rate that an animation can be sampled at, in frames per second
+const MAX_FRAMES_PER_SECOND: f64 = 1_000_000.0;
+
+///
+/// An animation made up of keyframes, each of which is a drawing that is shown at a particular time
+///
+/// The frames between two keyframes are generated by interpolating the values (coordinates, colours, line widths, transforms
+/// and so on) of their drawing instructions. This works when both keyframes contain the same sequence of instructions with
+/// different values: where the instructions differ, the earlier keyframe is shown until the time of the later one is reached.
+///
+#[derive(Clone)]
+pub struct KeyframeAnimation {
+    /// The keyframes in this animation, ordered by time
+    keyframes: Vec<(Duration, Arc<Vec<Draw>>)>,
+
+    /// The easing function applied to the position between two keyframes (maps 0-1 to 0-1)
+    easing: Arc<dyn Send + Sync + Fn(f64) -> f64>,
+}
+
+impl KeyframeAnimation {
+    ///
+    /// Creates a new animation with no keyframes and linear easing
+    ///
+    pub fn new() -> KeyframeAnimation {
+        KeyframeAnimation {
+            keyframes:  vec![],
+            easing:     Arc::new(|t| t),
+        }
+    }
+
+    ///
+    /// Adds a keyframe that shows a drawing at a particular time (replacing any existing keyframe at that time)
+    ///
+    pub fn with_keyframe(mut self, time: Duration, drawing: Vec<Draw>) -> KeyframeAnimation {
+        match self.keyframes.binary_search_by(|(keyframe_time, _)| keyframe_time.cmp(&time)) {
+            Ok(existing_idx)    => { self.keyframes[existing_idx] = (time, Arc::new(drawing)); }
+            Err(insert_idx)     => { self.keyframes.insert(insert_idx, (time, Arc::new(drawing))); }
+        }
+
+        self
+    }
+
+    ///
+    /// Sets the easing function used when interpolating between keyframes
+    ///
+    /// The function is passed the position between two keyframes as a value from 0 to 1, and returns the proportion of the
+    /// way from the first keyframe to the second to use for the interpolated frame.
+    ///
+    pub fn with_easing(mut self, easing: impl 'static + Send + Sync + Fn(f64) -> f64) -> KeyframeAnimation {
+        self.easing = Arc::new(easing);
+
+        self
+    }
+
+    ///
+    /// The total length of this animation (the time of its last keyframe)
+    ///
+    pub fn duration(&self) -> Duration {
+        self.keyframes.last()
+            .map(|(time, _)| *time)
+            .unwrap_or(Duration::from_secs(0))
+    }
+
+    ///
+    /// Returns the drawing instructions for the frame at a particular time in the animation
+    ///
+    pub fn drawing_at(&self, time: Duration) -> Vec<Draw> {
+        // Find the first keyframe after the requested time
+        let next_idx = self.keyframes.iter()
+            .position(|(keyframe_time, _)| *keyframe_time > time)
+            .unwrap_or(self.keyframes.len());
+
+        if next_idx == 0 {
+            // Before the first keyframe (or there are no keyframes)
+            self.keyframes.first()
+                .map(|(_, drawing)| (**drawing).clone())
+                .unwrap_or_default()
+        } else if next_idx >= self.keyframes.len() {
+            // After the last keyframe
+            (*self.keyframes[next_idx-1].1).clone()
+        } else {
+            // Between two keyframes
+            let (start_time, start_drawing) = &self.keyframes[next_idx-1];
+            let (end_time, end_drawing)     = &self.keyframes[next_idx];
+
+            if start_drawing.len() != end_drawing.len() {
+                return (**start_drawing).clone();
+            }
+
+            let position    = (time - *start_time).as_secs_f64() / (*end_time - *start_time).as_secs_f64();
+            let amount      = ((self.easing)(position)) as f32;
+
+            start_drawing.iter().zip(end_drawing.iter())
+                .map(|(start, end)| interpolate_draw(start, end, amount))
+                .collect()
+        }
+    }
+
+    ///
+    /// Returns a stream of drawing instructions that samples this animation at a particular frame rate
+    ///
+    /// Frames are generated from the start of the animation up to and including the final keyframe, so an animation lasting 2
+    /// seconds sampled at 30 frames per second produces 61 frames. Each frame is surrounded by `StartFrame` and `ShowFrame`
+    /// instructions, and begins by clearing all of the layers. The frames are generated as fast as the stream is read: nothing
+    /// here waits between frames, so pacing them (eg, with `limit_frame_rate()` in flo_draw) is up to the caller.
+    ///
+    /// A frame rate that is not positive, or is not finite, produces no frames. Frame rates above 1,000,000 frames per second
+    /// are clamped to that rate.
+    ///
+    pub fn frames(&self, frames_per_second: f64) -> impl Send + Unpin + Stream<Item=Draw> {
+        let animation           = self.clone();
+        let is_valid            = frames_per_second.is_finite() && frames_per_second > 0.0;
+        let frames_per_second   = frames_per_second.min(MAX_FRAMES_PER_SECOND);
+        let num_frames          = if is_valid { (self.duration().as_secs_f64() * frames_per_second + 1e-9).floor() as usize + 1 } else { 0 };
+
+        stream::iter(0..num_frames)
+            .flat_map(move |frame_idx| {
+                let time    = Duration::from_secs_f64((frame_idx as f64) / frames_per_second);
+                let drawing = animation.drawing_at(time);
+
+                stream::iter(vec![Draw::StartFrame, Draw::ClearAllLayers].into_iter()
+                    .chain(drawing)
+                    .chain(Some(Draw::ShowFrame)))
+            })
+    }
+}
+
+impl Default for KeyframeAnimation {
+    fn default() -> KeyframeAnimation {
+        KeyframeAnimation::new()
+    }
+}
+
+///
+/// Interpolates between two numbers
+///
+#[inline]
+fn lerp(start: f32, end: f32, amount: f32) -> f32 {
+    start + (end - start) * amount
+}
+
+///
+/// Interpolates between two points
+///
+#[inline]
+fn lerp_point((x1, y1): (f32, f32), (x2, y2): (f32, f32), amount: f32) -> (f32, f32) {
+    (lerp(x1, x2, amount), lerp(y1, y2, amount))
+}
+
+///
+/// Interpolates between two colours (in RGBA space)
+///
+fn lerp_color(start: Color, end: Color, amount: f32) -> Color {
+    let (r1, g1, b1, a1) = start.to_rgba_components();
+    let (r2, g2, b2, a2) = end.to_rgba_components();
+
+    Color::Rgba(lerp(r1, r2, amount), lerp(g1, g2, amount), lerp(b1, b2, amount), lerp(a1, a2, amount))
+}
+
+///
+/// Interpolates between two transformation matrices
+///
+fn lerp_transform(Transform2D(start): Transform2D, Transform2D(end): Transform2D, amount: f32) -> Transform2D {
+    Transform2D([0, 1, 2].map(|row| [0, 1, 2].map(|col| lerp(start[row][col], end[row][col], amount))))
+}
+
+///
+/// Interpolates between two drawing instructions
+///
+/// If the instructions are different kinds of instruction, or don't have any values that can be interpolated, the start
+/// instruction is returned.
+///
+fn interpolate_draw(start: &Draw, end: &Draw, amount: f32) -> Draw {
+    use self::Draw::*;
+    use self::PathOp::*;
+
+    match (start, end) {
+        (Path(Move(x1, y1)), Path(Move(x2, y2)))                                    => { let (x, y) = lerp_point((*x1, *y1), (*x2, *y2), amount); Path(Move(x, y)) }
+        (Path(Line(x1, y1)), Path(Line(x2, y2)))                                    => { let (x, y) = lerp_point((*x1, *y1), (*x2, *y2), amount); Path(Line(x, y)) }
+        (Path(BezierCurve((cp1a, cp2a), pa)), Path(BezierCurve((cp1b, cp2b), pb)))  => Path(BezierCurve((lerp_point(*cp1a, *cp1b, amount), lerp_point(*cp2a, *cp2b, amount)), lerp_point(*pa, *pb, amount))),
+
+        (LineWidth(w1), LineWidth(w2))                                              => LineWidth(lerp(*w1, *w2, amount)),
+        (LineWidthPixels(w1), LineWidthPixels(w2))                                  => LineWidthPixels(lerp(*w1, *w2, amount)),
+        (DashLength(l1), DashLength(l2))                                            => DashLength(lerp(*l1, *l2, amount)),
+        (DashOffset(o1), DashOffset(o2))                                            => DashOffset(lerp(*o1, *o2, amount)),
+
+        (FillColor(c1), FillColor(c2))                                              => FillColor(lerp_color(*c1, *c2, amount)),
+        (StrokeColor(c1), StrokeColor(c2))                                          => StrokeColor(lerp_color(*c1, *c2, amount)),
+        (ClearCanvas(c1), ClearCanvas(c2))                                          => ClearCanvas(lerp_color(*c1, *c2, amount)),
+        (GlobalAlpha(a1), GlobalAlpha(a2))                                          => GlobalAlpha(lerp(*a1, *a2, amount)),
+
+        (FillTexture(t1, min1, max1), FillTexture(t2, min2, max2)) if t1 == t2      => FillTexture(*t1, lerp_point(*min1, *min2, amount), lerp_point(*max1, *max2, amount)),
+        (FillGradient(g1, min1, max1), FillGradient(g2, min2, max2)) if g1 == g2    => FillGradient(*g1, lerp_point(*min1, *min2, amount), lerp_point(*max1, *max2, amount)),
+        (FillTransform(t1), FillTransform(t2))                                      => FillTransform(lerp_transform(*t1, *t2, amount)),
+
+        (Gradient(g1, GradientOp::Create(c1)), Gradient(g2, GradientOp::Create(c2))) if g1 == g2                    => Gradient(*g1, GradientOp::Create(lerp_color(*c1, *c2, amount))),
+        (Gradient(g1, GradientOp::AddStop(p1, c1)), Gradient(g2, GradientOp::AddStop(p2, c2))) if g1 == g2          => Gradient(*g1, GradientOp::AddStop(lerp(*p1, *p2, amount), lerp_color(*c1, *c2, amount))),
+
+        (CanvasHeight(h1), CanvasHeight(h2))                                        => CanvasHeight(lerp(*h1, *h2, amount)),
+        (CenterRegion(min1, max1), CenterRegion(min2, max2))                        => CenterRegion(lerp_point(*min1, *min2, amount), lerp_point(*max1, *max2, amount)),
+        (MultiplyTransform(t1), MultiplyTransform(t2))                              => MultiplyTransform(lerp_transform(*t1, *t2, amount)),
+
+        (LayerAlpha(l1, a1), LayerAlpha(l2, a2)) if l1 == l2                        => LayerAlpha(*l1, lerp(*a1, *a2, amount)),
+
+        _                                                                           => start.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use futures::executor;
+
+    fn square_at(x: f32) -> Vec<Draw> {
+        vec![
+            Draw::Path(PathOp::NewPath),
+            Draw::Path(PathOp::Move(x, 0.0)),
+            Draw::Path(PathOp::Line(x + 10.0, 0.0)),
+            Draw::Path(PathOp::Line(x + 10.0, 10.0)),
+            Draw::Path(PathOp::Line(x, 10.0)),
+            Draw::Path(PathOp::ClosePath),
+            Draw::Fill,
+        ]
+    }
+
+    #[test]
+    fn frame_count_for_duration() {
+        let animation = KeyframeAnimation::new()
+            .with_keyframe(Duration::from_secs(0), square_at(0.0))
+            .with_keyframe(Duration::from_secs(2), square_at(100.0));
+
+        let frames          = executor::block_on(animation.frames(30.0).collect::<Vec<_>>());
+        let start_frames    = frames.iter().filter(|draw| **draw == Draw::StartFrame).count();
+        let show_frames     = frames.iter().filter(|draw| **draw == Draw::ShowFrame).count();
+
+        assert!(start_frames == 61, "{} frames", start_frames);
+        assert!(show_frames == 61, "{} frames", show_frames);
+
+        // Last frame shows the final keyframe
+        assert!(frames.iter().rev().any(|draw| *draw == Draw::Path(PathOp::Move(100.0, 0.0))));
+    }
+
+    #[test]
+    fn invalid_frame_rates_produce_no_frames() {
+        let animation = KeyframeAnimation::new()
+            .with_keyframe(Duration::from_secs(0), square_at(0.0))
+            .with_keyframe(Duration::from_secs(2), square_at(100.0));
+
+        for frames_per_second in [0.0, -30.0, f64::NAN, f64::INFINITY] {
+            let frames = executor::block_on(animation.frames(frames_per_second).collect::<Vec<_>>());
+
+            assert!(frames.is_empty(), "{} fps: {} instructions", frames_per_second, frames.len());
+        }
+    }
+
+    #[test]
+    fn interpolate_halfway() {
+        let animation = KeyframeAnimation::new()
+            .with_keyframe(Duration::from_secs(0), square_at(0.0))
+            .with_keyframe(Duration::from_secs(2), square_at(100.0));
+
+        let halfway = animation.drawing_at(Duration::from_secs(1));
+
+        assert!(halfway == square_at(50.0), "{:?}", halfway);
+    }
+
+    #[test]
+    fn easing_changes_interpolation() {
+        let animation = KeyframeAnimation::new()
+            .with_keyframe(Duration::from_secs(0), square_at(0.0))
+            .with_keyframe(Duration::from_secs(2), square_at(100.0))
+            .with_easing(|t| t*t);
+
+        let halfway = animation.drawing_at(Duration::from_secs(1));
+
+        assert!(halfway == square_at(25.0), "{:?}", halfway);
+    }
+}
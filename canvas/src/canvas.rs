@@ -223,6 +223,7 @@ mod test {
     use crate::font::*;
     use crate::sprite::*;
     use crate::gradient::*;
+    use crate::mesh::*;
     use crate::font_face::*;
     use crate::primitives::*;
     use crate::transform2d::*;
@@ -1034,6 +1035,54 @@ mod test {
         });
     }
 
+    #[test]
+    fn mesh_state_survives_clear_other_layer() {
+        let canvas  = Canvas::new();
+        let mesh    = vec![MeshVertex::new(0.0, 0.0, Color::Rgba(1.0, 0.0, 0.0, 1.0)), MeshVertex::new(1.0, 0.0, Color::Rgba(0.0, 1.0, 0.0, 1.0)), MeshVertex::new(0.0, 1.0, Color::Rgba(0.0, 0.0, 1.0, 1.0))];
+
+        canvas.draw(|gc| {
+            gc.layer(LayerId(0));
+
+            gc.identity_transform();
+            gc.transform(Transform2D::scale(2.0, 2.0));
+            gc.blend_mode(BlendMode::Multiply);
+            gc.global_alpha(0.5);
+
+            gc.draw_mesh(mesh.clone(), vec![0, 1, 2]);
+
+            gc.identity_transform();
+            gc.blend_mode(BlendMode::SourceOver);
+            gc.global_alpha(1.0);
+
+            gc.layer(LayerId(1));
+            gc.clear_layer();
+        });
+
+        let mut stream = canvas.stream();
+
+        executor::block_on(async {
+            assert!(stream.next().await == Some(Draw::ResetFrame));
+            assert!(stream.next().await == Some(Draw::ClearCanvas(Color::Rgba(0.0, 0.0, 0.0, 0.0))));
+
+            assert!(stream.next().await == Some(Draw::Layer(LayerId(0))));
+
+            // The state that the mesh is drawn with has to be kept
+            assert!(stream.next().await == Some(Draw::IdentityTransform));
+            assert!(stream.next().await == Some(Draw::MultiplyTransform(Transform2D::scale(2.0, 2.0))));
+            assert!(stream.next().await == Some(Draw::BlendMode(BlendMode::Multiply)));
+            assert!(stream.next().await == Some(Draw::GlobalAlpha(0.5)));
+
+            assert!(stream.next().await == Some(Draw::DrawMesh(mesh, vec![0, 1, 2])));
+
+            assert!(stream.next().await == Some(Draw::IdentityTransform));
+            assert!(stream.next().await == Some(Draw::BlendMode(BlendMode::SourceOver)));
+            assert!(stream.next().await == Some(Draw::GlobalAlpha(1.0)));
+
+            assert!(stream.next().await == Some(Draw::Layer(LayerId(1))));
+            assert!(stream.next().await == Some(Draw::ClearLayer));
+        });
+    }
+
     #[test]
     fn store_then_restore_and_free() {
         let canvas      = Canvas::new();
@@ -1,5 +1,6 @@
 use crate::draw::*;
 use crate::path::*;
+use crate::mesh::*;
 use crate::font::*;
 use crate::color::*;
 use crate::sprite::*;
@@ -54,6 +55,11 @@ pub trait GraphicsContext {
     /// Draws a line around the currently defined path
     fn stroke(&mut self)                                    { self.draw(Draw::Stroke); }
 
+    /// Draws a triangle mesh (every three indices define a triangle, with colours interpolated between the vertices)
+    fn draw_mesh(&mut self, vertices: Vec<MeshVertex>, indices: Vec<u16>) {
+        self.draw(Draw::DrawMesh(vertices, indices));
+    }
+
     /// Sets the line width for the next stroke() operation
     fn line_width(&mut self, width: f32)                    { self.draw(Draw::LineWidth(width)); }
 
@@ -95,8 +101,14 @@ pub trait GraphicsContext {
     }
 
     /// Applies a transformation to the fill texture or gradient
+    ///
+    /// The transform is applied in the same coordinates as the path, so it composes with the current drawing transform
+    /// (eg, a 45 degree rotation turns a horizontal gradient into a diagonal one without moving the shape being filled)
     fn fill_transform(&mut self, transform: Transform2D)    { self.draw(Draw::FillTransform(transform)); }
 
+    /// Sets the opacity of everything that is filled or stroked from now on (eg, for fading in or out an entire scene)
+    fn global_alpha(&mut self, alpha: f32)                  { self.draw(Draw::GlobalAlpha(alpha)); }
+
     /// Sets the colour to use for the next stroke() operation
     fn stroke_color(&mut self, col: Color)                  { self.draw(Draw::StrokeColor(col)); }
 
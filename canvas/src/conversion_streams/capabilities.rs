@@ -0,0 +1,240 @@
+use crate::draw::*;
+use crate::color::*;
+use crate::gradient::*;
+use crate::namespace::*;
+use crate::texture::*;
+
+use flo_stream::*;
+use futures::prelude::*;
+
+use std::collections::{HashMap};
+
+///
+/// Describes the optional drawing features that a rendering backend supports
+///
+/// `flo_render_canvas::drawing_capabilities()` works these out for a renderer that implements `RenderCapabilities`.
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct DrawingCapabilities {
+    /// True if the backend can fill shapes with gradients
+    pub gradients: bool,
+
+    /// True if the backend can apply filters to textures and sprites
+    pub filters: bool,
+}
+
+impl DrawingCapabilities {
+    ///
+    /// Capabilities for a backend that supports every drawing feature
+    ///
+    pub fn all() -> DrawingCapabilities {
+        DrawingCapabilities {
+            gradients:  true,
+            filters:    true,
+        }
+    }
+
+    ///
+    /// Capabilities for a backend that can only draw the basic shapes and colours
+    ///
+    pub fn minimal() -> DrawingCapabilities {
+        DrawingCapabilities {
+            gradients:  false,
+            filters:    false,
+        }
+    }
+}
+
+///
+/// Returns the average colour of a gradient
+///
+/// The colour components are weighted by their alpha, so transparent parts of the gradient don't change the colour of the result
+/// (only how transparent it is).
+///
+fn average_gradient_color(gradient: &Vec<GradientOp>) -> Color {
+    let scale           = gradient_scale::<_, 64>(gradient.iter().cloned());
+    let (r, g, b, a)    = scale.iter()
+        .map(|texel| texel.map(|component| (component as f32) / 255.0))
+        .fold((0.0, 0.0, 0.0, 0.0), |(r, g, b, a), [tr, tg, tb, ta]| (r + tr*ta, g + tg*ta, b + tb*ta, a + ta));
+
+    if a <= 0.0 {
+        Color::Rgba(0.0, 0.0, 0.0, 0.0)
+    } else {
+        Color::Rgba(r / a, g / a, b / a, a / (scale.len() as f32))
+    }
+}
+
+///
+/// Rewrites the drawing instructions that a backend does not support into ones that it does
+///
+/// If the backend doesn't support gradients, gradient fills are replaced with a solid fill using the average colour of the gradient.
+/// If it doesn't support filters, texture filters are removed and sprites drawn with filters are drawn without them. This makes it
+/// possible to degrade gracefully when rendering to a limited backend.
+///
+pub fn drawing_for_capabilities<InStream: 'static+Send+Unpin+Stream<Item=Draw>>(draw_stream: InStream, capabilities: DrawingCapabilities) -> impl Send+Unpin+Stream<Item=Draw> {
+    generator_stream(move |yield_value| async move {
+        let mut draw_stream = draw_stream;
+
+        // The gradients that have been defined, indexed by namespace
+        let mut gradients   = HashMap::<(usize, GradientId), Vec<GradientOp>>::new();
+        let mut namespace   = NamespaceId::default().local_id();
+
+        while let Some(drawing) = draw_stream.next().await {
+            use self::Draw::*;
+
+            match drawing {
+                ClearCanvas(color) => {
+                    gradients.clear();
+                    namespace = NamespaceId::default().local_id();
+
+                    yield_value(ClearCanvas(color)).await;
+                }
+
+                Namespace(new_namespace) => {
+                    namespace = new_namespace.local_id();
+
+                    yield_value(Namespace(new_namespace)).await;
+                }
+
+                Gradient(gradient_id, op) => {
+                    // Track the gradient definition so it can be replaced with a colour later on
+                    match &op {
+                        GradientOp::Create(_)       => { gradients.insert((namespace, gradient_id), vec![op.clone()]); }
+                        GradientOp::AddStop(_, _)   => { gradients.entry((namespace, gradient_id)).or_insert_with(|| vec![]).push(op.clone()); }
+                    }
+
+                    if capabilities.gradients {
+                        yield_value(Gradient(gradient_id, op)).await;
+                    }
+                }
+
+                FillGradient(gradient_id, start, end) => {
+                    if capabilities.gradients {
+                        yield_value(FillGradient(gradient_id, start, end)).await;
+                    } else {
+                        let color = gradients.get(&(namespace, gradient_id))
+                            .map(|gradient| average_gradient_color(gradient))
+                            .unwrap_or(Color::Rgba(0.0, 0.0, 0.0, 0.0));
+
+                        yield_value(FillColor(color)).await;
+                    }
+                }
+
+                DrawSpriteWithFilters(sprite_id, filters) => {
+                    if capabilities.filters {
+                        yield_value(DrawSpriteWithFilters(sprite_id, filters)).await;
+                    } else {
+                        yield_value(DrawSprite(sprite_id)).await;
+                    }
+                }
+
+                Texture(texture_id, TextureOp::Filter(filter)) => {
+                    if capabilities.filters {
+                        yield_value(Texture(texture_id, TextureOp::Filter(filter))).await;
+                    }
+                }
+
+                other => {
+                    yield_value(other).await;
+                }
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::sprite::*;
+
+    use futures::stream;
+    use futures::executor;
+
+    #[test]
+    fn gradient_fill_becomes_average_color() {
+        let input_drawing = vec![
+            Draw::Gradient(GradientId(0), GradientOp::Create(Color::Rgba(0.0, 0.0, 0.0, 1.0))),
+            Draw::Gradient(GradientId(0), GradientOp::AddStop(1.0, Color::Rgba(1.0, 1.0, 1.0, 1.0))),
+            Draw::FillGradient(GradientId(0), (0.0, 0.0), (100.0, 0.0)),
+        ];
+
+        executor::block_on(async move {
+            let supported       = drawing_for_capabilities(stream::iter(input_drawing.into_iter()), DrawingCapabilities::minimal());
+            let output_drawing  = supported.collect::<Vec<_>>().await;
+
+            assert!(output_drawing.len() == 1, "{:?}", output_drawing);
+
+            match output_drawing[0] {
+                Draw::FillColor(Color::Rgba(r, g, b, a)) => {
+                    assert!((r-0.5).abs() < 0.01 && (g-0.5).abs() < 0.01 && (b-0.5).abs() < 0.01, "{:?}", output_drawing);
+                    assert!((a-1.0).abs() < 0.01, "{:?}", output_drawing);
+                }
+
+                _ => { assert!(false, "{:?}", output_drawing); }
+            }
+        });
+    }
+
+    #[test]
+    fn average_color_is_weighted_by_alpha() {
+        // The transparent end of the gradient is black, but that shouldn't make the average colour any darker
+        let input_drawing = vec![
+            Draw::Gradient(GradientId(0), GradientOp::Create(Color::Rgba(1.0, 0.0, 0.0, 1.0))),
+            Draw::Gradient(GradientId(0), GradientOp::AddStop(0.5, Color::Rgba(1.0, 0.0, 0.0, 1.0))),
+            Draw::Gradient(GradientId(0), GradientOp::AddStop(0.5, Color::Rgba(0.0, 0.0, 0.0, 0.0))),
+            Draw::Gradient(GradientId(0), GradientOp::AddStop(1.0, Color::Rgba(0.0, 0.0, 0.0, 0.0))),
+            Draw::FillGradient(GradientId(0), (0.0, 0.0), (100.0, 0.0)),
+        ];
+
+        executor::block_on(async move {
+            let supported       = drawing_for_capabilities(stream::iter(input_drawing.into_iter()), DrawingCapabilities::minimal());
+            let output_drawing  = supported.collect::<Vec<_>>().await;
+
+            assert!(output_drawing.len() == 1, "{:?}", output_drawing);
+
+            match output_drawing[0] {
+                Draw::FillColor(Color::Rgba(r, g, b, a)) => {
+                    assert!((r-1.0).abs() < 0.02 && g.abs() < 0.02 && b.abs() < 0.02, "{:?}", output_drawing);
+                    assert!((a-0.5).abs() < 0.05, "{:?}", output_drawing);
+                }
+
+                _ => { assert!(false, "{:?}", output_drawing); }
+            }
+        });
+    }
+
+    #[test]
+    fn filters_are_removed() {
+        let input_drawing = vec![
+            Draw::DrawSpriteWithFilters(SpriteId(0), vec![TextureFilter::GaussianBlur(4.0)]),
+            Draw::Texture(TextureId(0), TextureOp::Filter(TextureFilter::AlphaBlend(0.5))),
+            Draw::Texture(TextureId(0), TextureOp::FillTransparency(0.5)),
+        ];
+
+        executor::block_on(async move {
+            let supported       = drawing_for_capabilities(stream::iter(input_drawing.into_iter()), DrawingCapabilities::minimal());
+            let output_drawing  = supported.collect::<Vec<_>>().await;
+
+            assert!(output_drawing == vec![
+                Draw::DrawSprite(SpriteId(0)),
+                Draw::Texture(TextureId(0), TextureOp::FillTransparency(0.5)),
+            ], "{:?}", output_drawing);
+        });
+    }
+
+    #[test]
+    fn supported_operations_are_unchanged() {
+        let input_drawing = vec![
+            Draw::Gradient(GradientId(0), GradientOp::Create(Color::Rgba(0.0, 0.0, 0.0, 1.0))),
+            Draw::FillGradient(GradientId(0), (0.0, 0.0), (100.0, 0.0)),
+            Draw::DrawSpriteWithFilters(SpriteId(0), vec![TextureFilter::GaussianBlur(4.0)]),
+        ];
+
+        executor::block_on(async move {
+            let supported       = drawing_for_capabilities(stream::iter(input_drawing.clone().into_iter()), DrawingCapabilities::all());
+            let output_drawing  = supported.collect::<Vec<_>>().await;
+
+            assert!(output_drawing == input_drawing, "{:?}", output_drawing);
+        });
+    }
+}
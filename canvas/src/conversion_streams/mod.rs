@@ -9,5 +9,9 @@ pub use self::path_stream::*;
 #[cfg(feature = "outline-fonts")] pub use self::outline_fonts::*;
 
 mod dashed_lines;
+mod valid_colors;
+mod capabilities;
 
 pub use self::dashed_lines::*;
+pub use self::valid_colors::*;
+pub use self::capabilities::*;
@@ -0,0 +1,148 @@
+use crate::draw::*;
+use crate::color::*;
+use crate::gradient::*;
+
+use futures::prelude::*;
+use futures::future;
+
+///
+/// Clamps a value to a range, returning None if it's not finite
+///
+#[inline]
+fn clamp_component(component: f32, min: f32, max: f32) -> Option<f32> {
+    if component.is_finite() {
+        Some(component.max(min).min(max))
+    } else {
+        None
+    }
+}
+
+///
+/// Wraps a hue into the range 0-360, returning None if it's not finite
+///
+#[inline]
+fn wrap_hue(hue: f32) -> Option<f32> {
+    if hue.is_finite() {
+        Some(hue.rem_euclid(360.0))
+    } else {
+        None
+    }
+}
+
+///
+/// Returns a version of a colour with its components clamped to their valid ranges, or None if any of its components are not finite
+///
+fn clamp_color(color: Color) -> Option<Color> {
+    match color {
+        Color::Rgba(r, g, b, a)     => Some(Color::Rgba(clamp_component(r, 0.0, 1.0)?, clamp_component(g, 0.0, 1.0)?, clamp_component(b, 0.0, 1.0)?, clamp_component(a, 0.0, 1.0)?)),
+        Color::Hsluv(h, s, l, a)    => Some(Color::Hsluv(wrap_hue(h)?, clamp_component(s, 0.0, 100.0)?, clamp_component(l, 0.0, 100.0)?, clamp_component(a, 0.0, 1.0)?)),
+    }
+}
+
+///
+/// Returns a valid version of a colour: colours with components out of range are clamped and colours with NaN or infinite
+/// components are replaced with the fallback colour
+///
+#[inline]
+fn valid_color(color: Color, fallback: Color) -> Color {
+    clamp_color(color)
+        .or_else(|| clamp_color(fallback))
+        .unwrap_or(Color::Rgba(0.0, 0.0, 0.0, 1.0))
+}
+
+///
+/// Makes sure that all of the colours in a drawing are valid
+///
+/// Colour components outside of their valid range (0-1 for RGBA and alpha, 0-100 for HSLUV saturation and luminance) are
+/// clamped into it, and hues are wrapped around into the range 0-360. Colours with components that are NaN or infinite are
+/// replaced with the fallback colour, and gradient stops at a NaN or infinite position are removed. This is useful when colours
+/// come from user input or interpolation, where they might otherwise produce rendering artifacts.
+///
+pub fn drawing_with_valid_colors<InStream: 'static+Send+Unpin+Stream<Item=Draw>>(draw_stream: InStream, fallback: Color) -> impl Send+Unpin+Stream<Item=Draw> {
+    draw_stream.filter_map(move |drawing| {
+        use self::Draw::*;
+
+        future::ready(match drawing {
+            FillColor(color)                                                    => Some(FillColor(valid_color(color, fallback))),
+            StrokeColor(color)                                                  => Some(StrokeColor(valid_color(color, fallback))),
+            ClearCanvas(color)                                                  => Some(ClearCanvas(valid_color(color, fallback))),
+            Gradient(id, GradientOp::Create(color))                             => Some(Gradient(id, GradientOp::Create(valid_color(color, fallback)))),
+            Gradient(_, GradientOp::AddStop(pos, _)) if !pos.is_finite()        => None,
+            Gradient(id, GradientOp::AddStop(pos, color))                       => Some(Gradient(id, GradientOp::AddStop(pos, valid_color(color, fallback)))),
+
+            other                                                               => Some(other)
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use futures::stream;
+    use futures::executor;
+
+    #[test]
+    fn clamp_out_of_range_and_nan_colors() {
+        let input_drawing = vec![
+            Draw::FillColor(Color::Rgba(1.5, -0.5, 0.5, 2.0)),
+            Draw::StrokeColor(Color::Rgba(f32::NAN, 0.5, 0.5, 1.0)),
+            Draw::Gradient(GradientId(0), GradientOp::AddStop(0.5, Color::Hsluv(400.0, 50.0, f32::INFINITY, 1.0))),
+            Draw::ClearCanvas(Color::Rgba(0.25, 0.5, 0.75, 1.0)),
+        ];
+
+        executor::block_on(async move {
+            let fallback        = Color::Rgba(0.0, 0.0, 1.0, 1.0);
+            let valid_colors    = drawing_with_valid_colors(stream::iter(input_drawing.into_iter()), fallback);
+            let output_drawing  = valid_colors.collect::<Vec<_>>().await;
+
+            assert!(output_drawing == vec![
+                Draw::FillColor(Color::Rgba(1.0, 0.0, 0.5, 1.0)),
+                Draw::StrokeColor(fallback),
+                Draw::Gradient(GradientId(0), GradientOp::AddStop(0.5, fallback)),
+                Draw::ClearCanvas(Color::Rgba(0.25, 0.5, 0.75, 1.0)),
+            ], "{:?}", output_drawing);
+        });
+    }
+
+    #[test]
+    fn wrap_hues() {
+        let input_drawing = vec![
+            Draw::FillColor(Color::Hsluv(400.0, 50.0, 50.0, 1.0)),
+            Draw::StrokeColor(Color::Hsluv(-90.0, 50.0, 50.0, 1.0)),
+            Draw::ClearCanvas(Color::Hsluv(360.0, 50.0, 50.0, 1.0)),
+        ];
+
+        executor::block_on(async move {
+            let valid_colors    = drawing_with_valid_colors(stream::iter(input_drawing.into_iter()), Color::Rgba(0.0, 0.0, 1.0, 1.0));
+            let output_drawing  = valid_colors.collect::<Vec<_>>().await;
+
+            assert!(output_drawing == vec![
+                Draw::FillColor(Color::Hsluv(40.0, 50.0, 50.0, 1.0)),
+                Draw::StrokeColor(Color::Hsluv(270.0, 50.0, 50.0, 1.0)),
+                Draw::ClearCanvas(Color::Hsluv(0.0, 50.0, 50.0, 1.0)),
+            ], "{:?}", output_drawing);
+        });
+    }
+
+    #[test]
+    fn remove_stops_at_invalid_positions() {
+        let input_drawing = vec![
+            Draw::Gradient(GradientId(0), GradientOp::Create(Color::Rgba(1.0, 0.0, 0.0, 1.0))),
+            Draw::Gradient(GradientId(0), GradientOp::AddStop(f32::NAN, Color::Rgba(0.0, 1.0, 0.0, 1.0))),
+            Draw::Gradient(GradientId(0), GradientOp::AddStop(f32::INFINITY, Color::Rgba(0.0, 1.0, 0.0, 1.0))),
+            Draw::Gradient(GradientId(0), GradientOp::AddStop(f32::NEG_INFINITY, Color::Rgba(0.0, 1.0, 0.0, 1.0))),
+            Draw::Gradient(GradientId(0), GradientOp::AddStop(1.0, Color::Rgba(0.0, 0.0, 1.0, 1.0))),
+        ];
+
+        executor::block_on(async move {
+            let valid_colors    = drawing_with_valid_colors(stream::iter(input_drawing.into_iter()), Color::Rgba(0.0, 0.0, 0.0, 1.0));
+            let output_drawing  = valid_colors.collect::<Vec<_>>().await;
+
+            assert!(output_drawing == vec![
+                Draw::Gradient(GradientId(0), GradientOp::Create(Color::Rgba(1.0, 0.0, 0.0, 1.0))),
+                Draw::Gradient(GradientId(0), GradientOp::AddStop(1.0, Color::Rgba(0.0, 0.0, 1.0, 1.0))),
+            ], "{:?}", output_drawing);
+        });
+    }
+}
@@ -1,5 +1,6 @@
 use crate::draw::*;
 use crate::path::*;
+use crate::mesh::*;
 use crate::font::*;
 use crate::color::*;
 use crate::sprite::*;
@@ -74,6 +75,17 @@ struct DecodeBytes {
     byte_encoding:  PartialResult<Vec<u8>>
 }
 
+///
+/// Represents the state of an operation decoding a triangle mesh
+///
+struct DecodeMesh {
+    num_vertices:   Option<u64>,
+    vertices:       Vec<MeshVertex>,
+    num_indices:    Option<u64>,
+    indices:        Vec<u16>,
+    value_so_far:   String,
+}
+
 type DecodeLayerId      = PartialResult<LayerId>;
 type DecodeFontId       = PartialResult<FontId>;
 type DecodeSpriteId     = PartialResult<SpriteId>;
@@ -351,6 +363,88 @@ impl DecodeGlyphPositions {
     }
 }
 
+impl DecodeMesh {
+    ///
+    /// Creates a new mesh decoder that has matched 0 characters
+    ///
+    fn new() -> DecodeMesh {
+        DecodeMesh {
+            num_vertices:   None,
+            vertices:       vec![],
+            num_indices:    None,
+            indices:        vec![],
+            value_so_far:   String::new(),
+        }
+    }
+
+    ///
+    /// Indicates if this mesh decoder has read all of the vertices and indices
+    ///
+    #[inline] fn ready(&self) -> bool {
+        match self.num_indices {
+            Some(num_indices)   => self.indices.len() as u64 >= num_indices,
+            None                => false
+        }
+    }
+
+    ///
+    /// Returns the vertices and indices matched by this decoder (once it's ready)
+    ///
+    #[inline] fn to_mesh(self) -> Result<(Vec<MeshVertex>, Vec<u16>), DecoderError> {
+        if self.ready() {
+            Ok((self.vertices, self.indices))
+        } else {
+            Err(DecoderError::NotReady)
+        }
+    }
+
+    ///
+    /// Decodes a single character and returns the new state of the decoder
+    ///
+    /// Only the characters for the value that's currently being decoded are kept, so each character takes a constant amount of time
+    ///
+    fn decode(mut self, chr: char) -> Result<DecodeMesh, DecoderError> {
+        self.value_so_far.push(chr);
+        let mut chars = self.value_so_far.chars();
+
+        match (self.num_vertices, self.num_indices) {
+            (None, _) => {
+                // Vertex count
+                if let Some(num_vertices) = CanvasDecoder::try_decode_compact_u64(&mut chars)? {
+                    self.num_vertices = Some(num_vertices);
+                    self.value_so_far.clear();
+                }
+            }
+
+            (Some(num_vertices), None) if (self.vertices.len() as u64) < num_vertices => {
+                // Next vertex
+                if let Some(vertex) = CanvasDecoder::try_decode_mesh_vertex(&mut chars)? {
+                    self.vertices.push(vertex);
+                    self.value_so_far.clear();
+                }
+            }
+
+            (Some(_), None) => {
+                // Index count
+                if let Some(num_indices) = CanvasDecoder::try_decode_compact_u64(&mut chars)? {
+                    self.num_indices = Some(num_indices);
+                    self.value_so_far.clear();
+                }
+            }
+
+            (Some(_), Some(_)) => {
+                // Next index
+                if let Some(index) = CanvasDecoder::try_decode_compact_u64(&mut chars)? {
+                    self.indices.push(u16::try_from(index).map_err(|_| DecoderError::BadNumber)?);
+                    self.value_so_far.clear();
+                }
+            }
+        }
+
+        Ok(self)
+    }
+}
+
 ///
 /// The possible states for a decoder to be in after accepting some characters from the source
 ///
@@ -372,6 +466,8 @@ enum DecoderState {
     Line(String),                               // l (x, y)
     BezierCurve(String),                        // c (x, y, x, y, x, y)
 
+    DrawMesh(DecodeMesh),                       // V (num_vertices, vertices, num_indices, indices)
+
     LineStyleWidth(String),                     // 'Lw' (w)
     LineStyleWidthPixels(String),               // 'Lp' (w)
     LineStyleJoin(String),                      // 'Lj' (j)
@@ -386,6 +482,7 @@ enum DecoderState {
     ColorTexture(DecodeTextureId, String),      // 'Ct' (texture_id, x1, y1, x2, y2)
     ColorGradient(DecodeGradientId, String),    // 'Cg' (gradient_id, x1, y1, x2, y2)
     ColorTransform(String),                     // 'CT' (transform)
+    ColorGlobalAlpha(String),                   // 'Ca' (alpha)
 
     BlendMode(String),                          // 'M' (mode)
 
@@ -508,6 +605,8 @@ impl CanvasDecoder {
             Line(param)                     => Self::decode_line(next_chr, param)?,
             BezierCurve(param)              => Self::decode_bezier_curve(next_chr, param)?,
 
+            DrawMesh(mesh)                  => Self::decode_draw_mesh(next_chr, mesh)?,
+
             LineStyleWidth(param)           => Self::decode_line_width(next_chr, param)?,
             LineStyleWidthPixels(param)     => Self::decode_line_width_pixels(next_chr, param)?,
             LineStyleJoin(param)            => Self::decode_line_style_join(next_chr, param)?,
@@ -524,6 +623,7 @@ impl CanvasDecoder {
             ColorTexture(id, param)         => Self::decode_color_texture(next_chr, id, param)?,
             ColorGradient(id, param)        => Self::decode_color_gradient(next_chr, id, param)?,
             ColorTransform(param)           => Self::decode_color_transform(next_chr, param)?,
+            ColorGlobalAlpha(param)         => Self::decode_color_global_alpha(next_chr, param)?,
 
             BlendMode(param)                => Self::decode_blend_mode(next_chr, param)?,
 
@@ -609,6 +709,7 @@ impl CanvasDecoder {
             'm' => Ok((DecoderState::Move(String::new()), None)),
             'l' => Ok((DecoderState::Line(String::new()), None)),
             'c' => Ok((DecoderState::BezierCurve(String::new()), None)),
+            'V' => Ok((DecoderState::DrawMesh(DecodeMesh::new()), None)),
             'M' => Ok((DecoderState::BlendMode(String::new()), None)),
 
             't' => Ok((DecoderState::FontDrawing, None)),
@@ -680,6 +781,7 @@ impl CanvasDecoder {
             't'     => Ok((DecoderState::ColorTexture(DecodeTextureId::new(), String::new()), None)),
             'g'     => Ok((DecoderState::ColorGradient(DecodeGradientId::new(), String::new()), None)),
             'T'     => Ok((DecoderState::ColorTransform(String::new()), None)),
+            'a'     => Ok((DecoderState::ColorGlobalAlpha(String::new()), None)),
 
             _       => Err(DecoderError::InvalidCharacter(next_chr))
         }
@@ -786,6 +888,46 @@ impl CanvasDecoder {
         }
     }
 
+    fn decode_draw_mesh(next_chr: char, mesh: DecodeMesh) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        let mesh = mesh.decode(next_chr)?;
+
+        if mesh.ready() {
+            let (vertices, indices) = mesh.to_mesh()?;
+            Ok((DecoderState::None, Some(Draw::DrawMesh(vertices, indices))))
+        } else {
+            Ok((DecoderState::DrawMesh(mesh), None))
+        }
+    }
+
+    ///
+    /// Decodes a mesh vertex (position followed by an RGBA colour)
+    ///
+    /// Returns the vertex if the parameter matches one, 'None' if more characters are required, or an error if there's a problem
+    ///
+    fn try_decode_mesh_vertex(chars: &mut Chars) -> Result<Option<MeshVertex>, DecoderError> {
+        let x           = Self::try_decode_f32(chars)?;
+        let x           = if let Some(x) = x { x } else { return Ok(None); };
+        let y           = Self::try_decode_f32(chars)?;
+        let y           = if let Some(y) = y { y } else { return Ok(None); };
+
+        match chars.next() {
+            Some('R')   => { }
+            Some(_)     => { return Err(DecoderError::UnknownColorType); }
+            None        => { return Ok(None); }
+        }
+
+        let r           = Self::try_decode_f32(chars)?;
+        let r           = if let Some(r) = r { r } else { return Ok(None); };
+        let g           = Self::try_decode_f32(chars)?;
+        let g           = if let Some(g) = g { g } else { return Ok(None); };
+        let b           = Self::try_decode_f32(chars)?;
+        let b           = if let Some(b) = b { b } else { return Ok(None); };
+        let a           = Self::try_decode_f32(chars)?;
+        let a           = if let Some(a) = a { a } else { return Ok(None); };
+
+        Ok(Some(MeshVertex::new(x, y, Color::Rgba(r, g, b, a))))
+    }
+
     #[inline] fn decode_line_width(next_chr: char, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
         if param.len() < 5 {
             param.push(next_chr);
@@ -989,6 +1131,20 @@ impl CanvasDecoder {
         }
     }
 
+    #[inline] fn decode_color_global_alpha(next_chr: char, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
+        if param.len() < 5 {
+            param.push(next_chr);
+            Ok((DecoderState::ColorGlobalAlpha(param), None))
+        } else {
+            param.push(next_chr);
+
+            let mut param   = param.chars();
+            let alpha       = Self::decode_f32(&mut param)?;
+
+            Ok((DecoderState::None, Some(Draw::GlobalAlpha(alpha))))
+        }
+    }
+
     #[inline] fn decode_blend_mode(next_chr: char, mut param: String) -> Result<(DecoderState, Option<Draw>), DecoderError> {
         if param.len() < 1 {
             param.push(next_chr);
@@ -2184,6 +2340,51 @@ mod test {
         check_round_trip_single(Draw::Stroke);
     }
 
+    #[test]
+    fn decode_draw_mesh() {
+        check_round_trip_single(Draw::DrawMesh(vec![
+            MeshVertex::new(0.0, 0.0, Color::Rgba(1.0, 0.0, 0.0, 1.0)),
+            MeshVertex::new(100.0, 0.0, Color::Rgba(0.0, 1.0, 0.0, 1.0)),
+            MeshVertex::new(100.0, 100.0, Color::Rgba(0.0, 0.0, 1.0, 1.0)),
+            MeshVertex::new(0.0, 100.0, Color::Rgba(1.0, 1.0, 1.0, 0.5)),
+        ], vec![0, 1, 2, 0, 2, 3]));
+    }
+
+    #[test]
+    fn decode_large_mesh() {
+        // Large meshes are decoded a value at a time rather than re-reading everything that's been received for each character
+        let vertices    = (0..20000).map(|idx| MeshVertex::new(idx as f32, 0.0, Color::Rgba(1.0, 0.0, 0.0, 1.0))).collect::<Vec<_>>();
+        let indices     = (0..60000).map(|idx| (idx % 20000) as u16).collect::<Vec<_>>();
+        let mesh        = Draw::DrawMesh(vertices, indices);
+
+        let mut encoded = String::new();
+        mesh.encode_canvas(&mut encoded);
+
+        let decoded     = decode_drawing(encoded.chars()).collect::<Vec<_>>();
+
+        assert!(decoded.len() == 1);
+        assert!(decoded[0].as_ref() == Ok(&mesh));
+    }
+
+    #[test]
+    fn decode_empty_mesh() {
+        check_round_trip_single(Draw::DrawMesh(vec![], vec![]));
+    }
+
+    #[test]
+    fn error_on_mesh_index_out_of_range() {
+        // Encode a mesh with a single index, then replace the index with one that's too large for a u16
+        let mut encoded = String::new();
+        Draw::DrawMesh(vec![], vec![0]).encode_canvas(&mut encoded);
+        encoded.pop();
+        (&LayerId(0x10000)).encode_canvas(&mut encoded);
+
+        let mut decoder = CanvasDecoder::new();
+        let result      = encoded.chars().map(|chr| decoder.decode(chr)).find(|result| result != &Ok(None));
+
+        assert!(result == Some(Err(DecoderError::BadNumber)), "{:?}", result);
+    }
+
     #[test]
     fn decode_line_width() {
         check_round_trip_single(Draw::LineWidth(23.0));
@@ -2194,6 +2395,11 @@ mod test {
         check_round_trip_single(Draw::LineWidthPixels(43.0));
     }
 
+    #[test]
+    fn decode_global_alpha() {
+        check_round_trip_single(Draw::GlobalAlpha(0.5));
+    }
+
     #[test]
     fn decode_line_join() {
         check_round_trip_single(Draw::LineJoin(LineJoin::Bevel));
@@ -2617,6 +2823,7 @@ mod test {
             Draw::Fill,
             Draw::FillTexture(TextureId(42), (1.0, 2.0), (3.0, 4.0)),
             Draw::Stroke,
+            Draw::DrawMesh(vec![MeshVertex::new(1.0, 2.0, Color::Rgba(0.1, 0.2, 0.3, 0.4)), MeshVertex::new(3.0, 4.0, Color::Rgba(0.5, 0.6, 0.7, 0.8)), MeshVertex::new(5.0, 6.0, Color::Rgba(0.9, 1.0, 0.0, 1.0))], vec![0, 1, 2]),
             Draw::LineWidth(23.0),
             Draw::LineWidthPixels(43.0),
             Draw::LineJoin(LineJoin::Bevel),
@@ -10,6 +10,7 @@ use crate::sprite::*;
 use crate::color::*;
 use crate::font::*;
 use crate::path::*;
+use crate::mesh::*;
 
 ///
 /// Possible way to join lines
@@ -138,6 +139,12 @@ pub enum Draw {
     /// Draw a line around the current path
     Stroke,
 
+    /// Draws a triangle mesh directly, without tessellating a path
+    ///
+    /// Every three indices in the second list describe a triangle made from the vertices in the first list. The colour
+    /// of each vertex is interpolated across its triangles: the current fill colour, texture or gradient is not used.
+    DrawMesh(Vec<MeshVertex>, Vec<u16>),
+
     /// Set the line width
     LineWidth(f32),
 
@@ -171,6 +178,11 @@ pub enum Draw {
     /// For a gradient or texture fill, apply a transformation matrix
     FillTransform(Transform2D),
 
+    /// Sets an opacity (0.0-1.0) that multiplies the alpha of everything filled or stroked after this instruction
+    ///
+    /// This is stored with the rest of the drawing state, so it's reset by `PopState`
+    GlobalAlpha(f32),
+
     /// Set the line color
     StrokeColor(Color),
 
@@ -33,6 +33,8 @@ pub (crate) enum DrawResource {
     FillBlend,
     FillColor,
 
+    GlobalAlpha,
+
     StateStack
 }
 
@@ -50,6 +52,7 @@ impl Draw {
 
             // The fill and stroke operations depend on multiple resources, so their resource is 'special'
             Fill                                    => match resource { DrawResource::CanvasTransform | DrawResource::FillWindingRule | DrawResource::FillBlend | DrawResource::FillColor => true, _ => false },
+            DrawMesh(_, _)                          => match resource { DrawResource::CanvasTransform | DrawResource::FillBlend | DrawResource::GlobalAlpha => true, _ => false },
             Stroke                                  => match resource { DrawResource::CanvasTransform | DrawResource::StrokeLineWidth | DrawResource::StrokeLineCap | DrawResource::StrokeLineJoin | DrawResource::StrokeDash | DrawResource::StrokeColor | DrawResource::FillBlend => true, _ => false },
 
             // Texture and font operations generally alter the existing resource so they have a dependency
@@ -125,7 +128,8 @@ impl Draw {
 
             WindingRule(_)                          |
             BlendMode(_)                            |
-            FillColor(_)                            => smallvec![],
+            FillColor(_)                            |
+            GlobalAlpha(_)                          => smallvec![],
 
             LayerBlend(layer_id, _)                 => smallvec![DrawResource::Layer(*layer_id)],
             LayerAlpha(layer_id, _)                 => smallvec![DrawResource::Layer(*layer_id)],
@@ -135,15 +139,16 @@ impl Draw {
             DashOffset(_)                           => smallvec![DrawResource::StrokeDash],
 
             // The fill and stroke operations depend on multiple resources, so their resource is 'special'
-            Fill                                    => smallvec![*active_resource, DrawResource::CanvasTransform, DrawResource::FillWindingRule, DrawResource::FillBlend, DrawResource::FillColor],
-            Stroke                                  => smallvec![*active_resource, DrawResource::CanvasTransform, DrawResource::StrokeLineWidth, DrawResource::StrokeLineCap, DrawResource::StrokeLineJoin, DrawResource::StrokeDash, DrawResource::StrokeColor, DrawResource::FillBlend],
+            Fill                                    => smallvec![*active_resource, DrawResource::CanvasTransform, DrawResource::FillWindingRule, DrawResource::FillBlend, DrawResource::FillColor, DrawResource::GlobalAlpha],
+            DrawMesh(_, _)                          => smallvec![*active_resource, DrawResource::CanvasTransform, DrawResource::FillBlend, DrawResource::GlobalAlpha],
+            Stroke                                  => smallvec![*active_resource, DrawResource::CanvasTransform, DrawResource::StrokeLineWidth, DrawResource::StrokeLineCap, DrawResource::StrokeLineJoin, DrawResource::StrokeDash, DrawResource::StrokeColor, DrawResource::FillBlend, DrawResource::GlobalAlpha],
 
             // Texture and font operations generally alter the existing resource so they have a dependency
             Texture(texture_id, TextureOp::CreateDynamicSprite(sprite_id, _, _)) => smallvec![DrawResource::Texture(*texture_id), DrawResource::Sprite(*sprite_id), DrawResource::CanvasTransform],
 
             Texture(texture_id, _)                  => smallvec![DrawResource::Texture(*texture_id)],
             Font(font_id, FontOp::LayoutText(_))    |
-            Font(font_id, FontOp::DrawGlyphs(_))    => smallvec![*active_resource, DrawResource::Font(*font_id), DrawResource::FontSize(*font_id), DrawResource::CanvasTransform, DrawResource::FillWindingRule, DrawResource::FillBlend, DrawResource::FillColor],
+            Font(font_id, FontOp::DrawGlyphs(_))    => smallvec![*active_resource, DrawResource::Font(*font_id), DrawResource::FontSize(*font_id), DrawResource::CanvasTransform, DrawResource::FillWindingRule, DrawResource::FillBlend, DrawResource::FillColor, DrawResource::GlobalAlpha],
 
             DrawSprite(sprite_id)                   => smallvec![DrawResource::CanvasTransform, DrawResource::Sprite(*sprite_id)],
 
@@ -208,6 +213,7 @@ impl Draw {
             FillGradient(_, _, _)               |
             FillTexture(_, _, _)                |
             FillTransform(_)                    => DrawResource::FillColor,
+            GlobalAlpha(_)                      => DrawResource::GlobalAlpha,
 
             SwapLayers(layer1, _layer2)         => DrawResource::Layer(*layer1),
             LayerBlend(layer_id, _)             => DrawResource::Layer(*layer_id),
@@ -253,7 +259,8 @@ impl Draw {
             FillColor(_)                        |
             FillGradient(_, _, _)               |
             FillTexture(_, _, _)                |
-            FillTransform(_)                    => true,
+            FillTransform(_)                    |
+            GlobalAlpha(_)                      => true,
 
             _                                   => false
         }
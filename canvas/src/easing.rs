@@ -0,0 +1,184 @@
+//!
+//! Standard easing functions for animations
+//!
+//! Each function maps a position from 0 to 1 (eg, the proportion of the time between two keyframes that has elapsed) to the
+//! proportion of the way between the start and end values to use. All of these return 0 for 0 and 1 for 1. The elastic
+//! functions overshoot the 0-1 range, and the elastic and bounce functions do not always increase.
+//!
+//! These can be passed directly to `KeyframeAnimation::with_easing()`, or used to adjust the position of a value when
+//! interpolating anything else, such as a gradient stop.
+//!
+
+use std::f64::consts::{PI};
+
+///
+/// No easing: moves at a constant rate
+///
+pub fn linear(t: f64) -> f64 {
+    t
+}
+
+///
+/// Starts slowly and accelerates (quadratic)
+///
+pub fn quad_in(t: f64) -> f64 {
+    t * t
+}
+
+///
+/// Starts quickly and decelerates (quadratic)
+///
+pub fn quad_out(t: f64) -> f64 {
+    1.0 - quad_in(1.0 - t)
+}
+
+///
+/// Accelerates until halfway and then decelerates (quadratic)
+///
+pub fn quad_in_out(t: f64) -> f64 {
+    in_out(quad_in, t)
+}
+
+///
+/// Starts slowly and accelerates (cubic)
+///
+pub fn cubic_in(t: f64) -> f64 {
+    t * t * t
+}
+
+///
+/// Starts quickly and decelerates (cubic)
+///
+pub fn cubic_out(t: f64) -> f64 {
+    1.0 - cubic_in(1.0 - t)
+}
+
+///
+/// Accelerates until halfway and then decelerates (cubic)
+///
+pub fn cubic_in_out(t: f64) -> f64 {
+    in_out(cubic_in, t)
+}
+
+///
+/// Winds up with an increasing oscillation before moving to the end value
+///
+pub fn elastic_in(t: f64) -> f64 {
+    if t <= 0.0 {
+        0.0
+    } else if t >= 1.0 {
+        1.0
+    } else {
+        -f64::powf(2.0, 10.0 * t - 10.0) * f64::sin((t * 10.0 - 10.75) * (2.0 * PI / 3.0))
+    }
+}
+
+///
+/// Overshoots the end value and then settles on it with a decreasing oscillation
+///
+pub fn elastic_out(t: f64) -> f64 {
+    1.0 - elastic_in(1.0 - t)
+}
+
+///
+/// Oscillates at the start and the end
+///
+pub fn elastic_in_out(t: f64) -> f64 {
+    in_out(elastic_in, t)
+}
+
+///
+/// Bounces off the start value with increasing height before moving to the end value
+///
+pub fn bounce_in(t: f64) -> f64 {
+    1.0 - bounce_out(1.0 - t)
+}
+
+///
+/// Bounces against the end value with decreasing height, like a ball being dropped
+///
+pub fn bounce_out(t: f64) -> f64 {
+    const N: f64 = 7.5625;
+    const D: f64 = 2.75;
+
+    if t < 1.0 / D {
+        N * t * t
+    } else if t < 2.0 / D {
+        let t = t - 1.5 / D;
+        N * t * t + 0.75
+    } else if t < 2.5 / D {
+        let t = t - 2.25 / D;
+        N * t * t + 0.9375
+    } else {
+        let t = t - 2.625 / D;
+        N * t * t + 0.984375
+    }
+}
+
+///
+/// Bounces at the start and the end
+///
+pub fn bounce_in_out(t: f64) -> f64 {
+    in_out(bounce_in, t)
+}
+
+///
+/// Creates an 'in-out' easing function from an 'in' easing function, by using the first half for the first half of the time
+/// and a mirrored version for the second half
+///
+#[inline]
+fn in_out(ease_in: impl Fn(f64) -> f64, t: f64) -> f64 {
+    if t < 0.5 {
+        ease_in(t * 2.0) / 2.0
+    } else {
+        1.0 - ease_in((1.0 - t) * 2.0) / 2.0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn all_easing_functions() -> Vec<(&'static str, fn(f64) -> f64)> {
+        vec![
+            ("linear", linear),
+            ("quad_in", quad_in), ("quad_out", quad_out), ("quad_in_out", quad_in_out),
+            ("cubic_in", cubic_in), ("cubic_out", cubic_out), ("cubic_in_out", cubic_in_out),
+            ("elastic_in", elastic_in), ("elastic_out", elastic_out), ("elastic_in_out", elastic_in_out),
+            ("bounce_in", bounce_in), ("bounce_out", bounce_out), ("bounce_in_out", bounce_in_out),
+        ]
+    }
+
+    #[test]
+    fn boundary_values() {
+        for (name, easing) in all_easing_functions() {
+            assert!(easing(0.0).abs() < 1e-9, "{}(0) = {}", name, easing(0.0));
+            assert!((easing(1.0) - 1.0).abs() < 1e-9, "{}(1) = {}", name, easing(1.0));
+        }
+    }
+
+    #[test]
+    fn in_out_is_halfway_at_midpoint() {
+        for (name, easing) in all_easing_functions().into_iter().filter(|(name, _)| name.ends_with("in_out")) {
+            assert!((easing(0.5) - 0.5).abs() < 1e-9, "{}(0.5) = {}", name, easing(0.5));
+        }
+    }
+
+    #[test]
+    fn polynomial_easing_is_monotonic() {
+        let monotonic = vec![linear as fn(f64) -> f64, quad_in, quad_out, quad_in_out, cubic_in, cubic_out, cubic_in_out];
+
+        for easing in monotonic {
+            let values = (0..=100).map(|t| easing((t as f64) / 100.0)).collect::<Vec<_>>();
+
+            assert!(values.windows(2).all(|pair| pair[1] >= pair[0]), "{:?}", values);
+        }
+    }
+
+    #[test]
+    fn bounce_stays_in_range() {
+        for easing in vec![bounce_in as fn(f64) -> f64, bounce_out, bounce_in_out] {
+            assert!((0..=100).map(|t| easing((t as f64) / 100.0)).all(|value| value >= -1e-9 && value <= 1.0 + 1e-9));
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use crate::draw::*;
 use crate::path::*;
+use crate::mesh::*;
 use crate::font::*;
 use crate::color::*;
 use crate::sprite::*;
@@ -492,6 +493,26 @@ impl<'a> CanvasEncoding<String> for &'a Vec<GlyphPosition> {
     }
 }
 
+impl CanvasEncoding<String> for MeshVertex {
+    fn encode_canvas(&self, append_to: &mut String) {
+        (self.pos, &self.color).encode_canvas(append_to);
+    }
+}
+
+impl<'a> CanvasEncoding<String> for &'a Vec<MeshVertex> {
+    fn encode_canvas(&self, append_to: &mut String) {
+        encode_compact_u64(&(self.len() as u64), append_to);
+        self.iter().for_each(|vertex| vertex.encode_canvas(append_to));
+    }
+}
+
+impl<'a> CanvasEncoding<String> for &'a Vec<u16> {
+    fn encode_canvas(&self, append_to: &mut String) {
+        encode_compact_u64(&(self.len() as u64), append_to);
+        self.iter().for_each(|index| encode_compact_u64(&(*index as u64), append_to));
+    }
+}
+
 impl<'a> CanvasEncoding<String> for &NamespaceId {
     fn encode_canvas(&self, append_to: &mut String) {
         let global_id               = self.global_id();
@@ -517,6 +538,7 @@ impl CanvasEncoding<String> for Draw {
             Path(ClosePath)                             => ('.').encode_canvas(append_to),
             Fill                                        => 'F'.encode_canvas(append_to),
             Stroke                                      => 'S'.encode_canvas(append_to),
+            DrawMesh(vertices, indices)                 => ('V', vertices, indices).encode_canvas(append_to),
             LineWidth(width)                            => ('L', 'w', width).encode_canvas(append_to),
             LineWidthPixels(width)                      => ('L', 'p', width).encode_canvas(append_to),
             LineJoin(join)                              => ('L', 'j', join).encode_canvas(append_to),
@@ -530,6 +552,7 @@ impl CanvasEncoding<String> for Draw {
             FillTexture(texture, (x1, y1), (x2, y2))    => ('C', 't', texture, (x1, y1), (x2, y2)).encode_canvas(append_to),
             FillGradient(gradient, (x1, y1), (x2, y2))  => ('C', 'g', gradient, (x1, y1), (x2, y2)).encode_canvas(append_to),
             FillTransform(transform)                    => ('C', 'T', transform).encode_canvas(append_to),
+            GlobalAlpha(alpha)                          => ('C', 'a', alpha).encode_canvas(append_to),
             BlendMode(mode)                             => ('M', mode).encode_canvas(append_to),
             IdentityTransform                           => ('T', 'i').encode_canvas(append_to),
             CanvasHeight(height)                        => ('T', 'h', height).encode_canvas(append_to),
@@ -621,6 +644,8 @@ mod test {
     #[test]
     fn encode_linewidthpixels() { assert!(&encode_draw(Draw::LineWidthPixels(20.0)) == "LpAAAoBB") }
     #[test]
+    fn encode_globalalpha() { assert!(&encode_draw(Draw::GlobalAlpha(20.0)) == "CaAAAoBB") }
+    #[test]
     fn encode_linejoin() { assert!(&encode_draw(Draw::LineJoin(LineJoin::Bevel)) == "LjB") }
     #[test]
     fn encode_linecap() { assert!(&encode_draw(Draw::LineCap(LineCap::Butt)) == "LcB") }
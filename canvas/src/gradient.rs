@@ -124,6 +124,32 @@ pub fn gradient_scale<GradientIter: IntoIterator<Item=GradientOp>, const N: usiz
     }
 }
 
+///
+/// Returns a colour with its colour components multiplied by its alpha component
+///
+#[inline]
+fn premultiply_color(color: Color) -> Color {
+    let (r, g, b, a) = color.to_rgba_components();
+
+    Color::Rgba(r*a, g*a, b*a, a)
+}
+
+///
+/// Creates a gradient scale with premultiplied alpha, as 8-bit RGBA quads from a set of gradient operations
+///
+/// The stops are premultiplied before they're interpolated, so a stop that's fully transparent doesn't darken the colours
+/// that are blended with it (which happens when blending with a transparent black stop without premultiplying)
+///
+pub fn gradient_scale_premultiplied<GradientIter: IntoIterator<Item=GradientOp>, const N: usize>(description: GradientIter) -> [[u8; 4]; N] {
+    let premultiplied = description.into_iter()
+        .map(|op| match op {
+            GradientOp::Create(col)         => GradientOp::Create(premultiply_color(col)),
+            GradientOp::AddStop(pos, col)   => GradientOp::AddStop(pos, premultiply_color(col))
+        });
+
+    gradient_scale(premultiplied)
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -154,6 +180,21 @@ mod test {
         assert!(scale[14] == [238, 238, 238, 238]);
     }
 
+    #[test]
+    fn premultiplied_transparent_to_opaque() {
+        let scale = gradient_scale_premultiplied::<_, 16>(vec![
+            GradientOp::Create(Color::Rgba(1.0, 0.0, 0.0, 0.0)), 
+            GradientOp::AddStop(1.0, Color::Rgba(1.0, 0.0, 0.0, 1.0))
+        ]);
+
+        assert!(scale[0]  == [0, 0, 0, 0]);
+        assert!(scale[15] == [255, 0, 0, 255]);
+
+        // Colour components should never be greater than the alpha component
+        assert!(scale.iter().all(|texel| texel[0] <= texel[3]));
+        assert!(scale[8]  == [136, 0, 0, 136]);
+    }
+
     #[test]
     fn generate_basic_gradient_scale_1024() {
         let scale = gradient_scale::<_, 1024>(vec![
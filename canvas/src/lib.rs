@@ -43,6 +43,7 @@ extern crate hsluv;
 
 mod draw;
 mod path;
+mod mesh;
 mod font;
 mod color;
 mod sprite;
@@ -55,17 +56,23 @@ mod gradient;
 mod namespace;
 mod font_face;
 mod primitives;
+mod path_measure;
+mod path_offset;
+mod animation;
 mod transform2d;
 mod draw_stream;
 mod draw_resource;
 mod drawing_target;
 mod conversion_streams;
 
+pub mod easing;
+
 #[cfg(feature = "outline-fonts")] mod font_line_layout;
 #[cfg(feature = "scenery")] pub mod scenery;
 
 pub use self::draw::*;
 pub use self::path::*;
+pub use self::mesh::*;
 pub use self::font::*;
 pub use self::color::*;
 pub use self::sprite::*;
@@ -78,6 +85,9 @@ pub use self::gradient::*;
 pub use self::namespace::*;
 pub use self::font_face::*;
 pub use self::primitives::*;
+pub use self::path_measure::*;
+pub use self::path_offset::*;
+pub use self::animation::*;
 pub use self::transform2d::*;
 pub use self::draw_stream::*;
 pub use self::drawing_target::*;
@@ -0,0 +1,30 @@
+use crate::color::*;
+
+///
+/// A vertex in a triangle mesh
+///
+/// Meshes are drawn without tessellation, so they're a good way to render content that is naturally made up
+/// of triangles (mesh gradients, for example). The colour of each vertex is interpolated across the triangles
+/// that use it.
+///
+#[derive(Clone, Copy, PartialEq, Debug, Serialize, Deserialize)]
+pub struct MeshVertex {
+    /// The position of this vertex in canvas coordinates
+    pub pos: (f32, f32),
+
+    /// The colour of this vertex
+    pub color: Color,
+}
+
+impl MeshVertex {
+    ///
+    /// Creates a new mesh vertex at the specified position
+    ///
+    #[inline]
+    pub fn new(x: f32, y: f32, color: Color) -> MeshVertex {
+        MeshVertex {
+            pos:    (x, y),
+            color:  color
+        }
+    }
+}
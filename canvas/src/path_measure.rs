@@ -0,0 +1,188 @@
+use flo_curves::geo::*;
+use flo_curves::bezier::*;
+use flo_curves::bezier::path::*;
+
+/// The maximum error to allow when measuring the length of a curve
+const MAX_LENGTH_ERROR: f64 = 0.01;
+
+/// The number of iterations used when searching a curve for the position at a particular distance
+const DISTANCE_SEARCH_ITERATIONS: usize = 32;
+
+/// Tangents shorter than this are treated as zero-length (where the control points overlap the end points, or at a cusp)
+const MIN_TANGENT_LENGTH: f64 = 1e-9;
+
+/// The distance in t values to the nearby points used to find the direction of a curve where its derivative is zero
+const TANGENT_SAMPLE_OFFSET: f64 = 1e-3;
+
+///
+/// Returns the curves that make up a bezier path
+///
+fn path_curves<Path: BezierPath>(path: &Path) -> Vec<Curve<Path::Point>> {
+    let mut start_point = path.start_point();
+
+    path.points()
+        .map(|(cp1, cp2, end_point)| {
+            let curve   = Curve::from_points(start_point, (cp1, cp2), end_point);
+            start_point = end_point;
+
+            curve
+        })
+        .collect()
+}
+
+///
+/// Returns the unit tangent of a curve at a particular t value
+///
+/// The derivative of a curve is zero where a control point is on top of an end point, or at a cusp. The direction between the
+/// points on either side of the t value is used instead there, or the direction from the start to the end of the curve if that's
+/// zero too. A curve that's just a single point has no direction, so the result is a zero vector.
+///
+fn unit_tangent<Point: Coordinate>(curve: &Curve<Point>, t: f64) -> Point {
+    let p0          = curve.start_point();
+    let (p1, p2)    = curve.control_points();
+    let p3          = curve.end_point();
+    let mt          = 1.0 - t;
+
+    let tangent     = (p1 - p0) * (3.0*mt*mt) + (p2 - p1) * (6.0*mt*t) + (p3 - p2) * (3.0*t*t);
+    if tangent.magnitude() > MIN_TANGENT_LENGTH {
+        return tangent.to_unit_vector();
+    }
+
+    let before      = curve.point_at_pos((t - TANGENT_SAMPLE_OFFSET).max(0.0));
+    let after       = curve.point_at_pos((t + TANGENT_SAMPLE_OFFSET).min(1.0));
+    let nearby      = after - before;
+    if nearby.magnitude() > MIN_TANGENT_LENGTH {
+        return nearby.to_unit_vector();
+    }
+
+    let chord       = p3 - p0;
+    if chord.magnitude() > MIN_TANGENT_LENGTH {
+        return chord.to_unit_vector();
+    }
+
+    Point::origin()
+}
+
+///
+/// Finds the t value of the point at a particular distance along a curve with a known length
+///
+fn t_for_distance<Point: Coordinate>(curve: &Curve<Point>, distance: f64, total_length: f64) -> f64 {
+    if total_length <= 0.0 || distance <= 0.0 {
+        return 0.0;
+    } else if distance >= total_length {
+        return 1.0;
+    }
+
+    // Binary search for the t value where the length of the curve section matches the distance
+    let mut min_t = 0.0;
+    let mut max_t = 1.0;
+
+    for _ in 0..DISTANCE_SEARCH_ITERATIONS {
+        let mid_t           = (min_t + max_t) / 2.0;
+        let section_length  = curve_length(&curve.section(0.0, mid_t), MAX_LENGTH_ERROR);
+
+        if section_length < distance {
+            min_t = mid_t;
+        } else {
+            max_t = mid_t;
+        }
+    }
+
+    (min_t + max_t) / 2.0
+}
+
+///
+/// Returns the total arc length of a bezier path
+///
+pub fn path_length<Path: BezierPath>(path: &Path) -> f64 {
+    path_curves(path).iter()
+        .map(|curve| curve_length(curve, MAX_LENGTH_ERROR))
+        .sum()
+}
+
+///
+/// Returns the point and unit tangent at a particular distance along a bezier path
+///
+/// This is measured using the arc length of the path, so it can be used to move objects along a path at a constant speed (for
+/// example, for a 'follow path' animation). Returns `None` if the distance is negative or beyond the end of the path.
+///
+pub fn path_point_at_distance<Path: BezierPath>(path: &Path, distance: f64) -> Option<(Path::Point, Path::Point)> {
+    if distance < 0.0 {
+        return None;
+    }
+
+    let mut remaining = distance;
+
+    for curve in path_curves(path) {
+        let length = curve_length(&curve, MAX_LENGTH_ERROR);
+
+        if remaining <= length {
+            let t = t_for_distance(&curve, remaining, length);
+
+            return Some((curve.point_at_pos(t), unit_tangent(&curve, t)));
+        }
+
+        remaining -= length;
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn length_of_straight_line() {
+        let line = (Coord2(0.0, 0.0), vec![(Coord2(10.0, 0.0), Coord2(20.0, 0.0), Coord2(100.0, 0.0))]);
+
+        assert!((path_length(&line) - 100.0).abs() < 0.1, "{}", path_length(&line));
+    }
+
+    #[test]
+    fn midpoint_of_straight_line() {
+        // Control points are unevenly spaced, so the midpoint by distance is not the point at t=0.5
+        let line                = (Coord2(0.0, 0.0), vec![(Coord2(10.0, 10.0), Coord2(20.0, 20.0), Coord2(100.0, 100.0))]);
+        let length              = path_length(&line);
+        let (point, tangent)    = path_point_at_distance(&line, length / 2.0).unwrap();
+
+        assert!(point.distance_to(&Coord2(50.0, 50.0)) < 0.1, "{:?}", point);
+
+        let direction = f64::sqrt(0.5);
+        assert!(tangent.distance_to(&Coord2(direction, direction)) < 0.001, "{:?}", tangent);
+    }
+
+    #[test]
+    fn point_on_second_curve() {
+        let path                = (Coord2(0.0, 0.0), vec![
+            (Coord2(0.0, 10.0), Coord2(0.0, 20.0), Coord2(0.0, 30.0)),
+            (Coord2(10.0, 30.0), Coord2(20.0, 30.0), Coord2(30.0, 30.0)),
+        ]);
+        let (point, tangent)    = path_point_at_distance(&path, 45.0).unwrap();
+
+        assert!(point.distance_to(&Coord2(15.0, 30.0)) < 0.1, "{:?}", point);
+        assert!(tangent.distance_to(&Coord2(1.0, 0.0)) < 0.001, "{:?}", tangent);
+
+        // Nothing beyond the end of the path
+        assert!(path_point_at_distance(&path, 61.0).is_none());
+    }
+
+    #[test]
+    fn tangent_where_control_point_overlaps_start() {
+        // The derivative at the start of this curve is zero, as the first control point is on top of the start point
+        let line                = (Coord2(0.0, 0.0), vec![(Coord2(0.0, 0.0), Coord2(50.0, 0.0), Coord2(100.0, 0.0))]);
+        let (point, tangent)    = path_point_at_distance(&line, 0.0).unwrap();
+
+        assert!(point.distance_to(&Coord2(0.0, 0.0)) < 0.001, "{:?}", point);
+        assert!(tangent.distance_to(&Coord2(1.0, 0.0)) < 0.001, "{:?}", tangent);
+    }
+
+    #[test]
+    fn tangent_of_single_point_is_zero() {
+        let point_path          = (Coord2(10.0, 10.0), vec![(Coord2(10.0, 10.0), Coord2(10.0, 10.0), Coord2(10.0, 10.0))]);
+        let (point, tangent)    = path_point_at_distance(&point_path, 0.0).unwrap();
+
+        assert!(point.distance_to(&Coord2(10.0, 10.0)) < 0.001, "{:?}", point);
+        assert!(tangent == Coord2(0.0, 0.0), "{:?}", tangent);
+    }
+}
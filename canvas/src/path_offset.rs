@@ -0,0 +1,358 @@
+use flo_curves::geo::*;
+use flo_curves::bezier::*;
+use flo_curves::bezier::path::*;
+
+/// Number of points sampled along each curve when fitting its offset
+const OFFSET_SAMPLES: u32 = 32;
+
+/// The maximum distance between the fitted offset curve and the sampled offset points
+const OFFSET_MAX_ERROR: f64 = 0.01;
+
+/// Distance below which two points are considered to be the same
+const CLOSE_DISTANCE: f64 = 1e-6;
+
+/// The furthest a convex corner can extend from the original corner (as a multiple of the offset distance) before it is bevelled instead of mitred
+const MITER_LIMIT: f64 = 4.0;
+
+///
+/// A section of the offset path (start point, control points, end point)
+///
+#[derive(Clone, Copy, Debug)]
+struct OffsetSection {
+    start:  Coord2,
+    cp1:    Coord2,
+    cp2:    Coord2,
+    end:    Coord2,
+
+    /// The point on the original path that the end of this section was offset from
+    corner: Coord2,
+}
+
+impl OffsetSection {
+    ///
+    /// The tangent at the start of this section
+    ///
+    fn start_tangent(&self) -> Option<Coord2> {
+        first_direction(&[self.cp1 - self.start, self.cp2 - self.start, self.end - self.start])
+    }
+
+    ///
+    /// The tangent at the end of this section
+    ///
+    fn end_tangent(&self) -> Option<Coord2> {
+        first_direction(&[self.end - self.cp2, self.end - self.cp1, self.end - self.start])
+    }
+
+    ///
+    /// Moves the start point of this section, keeping the direction of the start tangent
+    ///
+    fn move_start(&mut self, new_start: Coord2) {
+        let delta   = new_start - self.start;
+        self.start  = new_start;
+        self.cp1    = self.cp1 + delta;
+    }
+
+    ///
+    /// Moves the end point of this section, keeping the direction of the end tangent
+    ///
+    fn move_end(&mut self, new_end: Coord2) {
+        let delta   = new_end - self.end;
+        self.end    = new_end;
+        self.cp2    = self.cp2 + delta;
+    }
+}
+
+///
+/// Returns the first of a set of vectors that has a non-zero length, as a unit vector
+///
+fn first_direction(candidates: &[Coord2]) -> Option<Coord2> {
+    candidates.iter()
+        .find(|vector| vector.x().abs() > CLOSE_DISTANCE || vector.y().abs() > CLOSE_DISTANCE)
+        .map(|vector| vector.to_unit_vector())
+}
+
+///
+/// Returns the control points of a straight line between two points
+///
+fn line_between(start: Coord2, end: Coord2) -> (Coord2, Coord2, Coord2) {
+    let delta = end - start;
+
+    (start + delta * (1.0/3.0), start + delta * (2.0/3.0), end)
+}
+
+///
+/// Finds the point where two lines (each described as a point and a direction) meet
+///
+fn line_intersection(p1: Coord2, direction1: Coord2, p2: Coord2, direction2: Coord2) -> Option<Coord2> {
+    let cross = direction1.x()*direction2.y() - direction1.y()*direction2.x();
+
+    if cross.abs() < CLOSE_DISTANCE {
+        // Lines are parallel
+        None
+    } else {
+        let offset  = p2 - p1;
+        let s       = (offset.x()*direction2.y() - offset.y()*direction2.x()) / cross;
+
+        Some(p1 + direction1 * s)
+    }
+}
+
+///
+/// Returns the signed area of the control polygon of a path (positive for anti-clockwise paths)
+///
+fn signed_area(start_point: Coord2, points: &[(Coord2, Coord2, Coord2)]) -> f64 {
+    let polygon     = points.iter().flat_map(|(cp1, cp2, end)| vec![*cp1, *cp2, *end]);
+    let mut last    = start_point;
+    let mut area    = 0.0;
+
+    for point in polygon.chain(Some(start_point)) {
+        area += last.x()*point.y() - point.x()*last.y();
+        last = point;
+    }
+
+    area / 2.0
+}
+
+///
+/// Offsets a single curve to its right-hand side, returning the sections that make up the offset curve
+///
+fn offset_curve(curve: &Curve<Coord2>, distance: f64) -> Vec<OffsetSection> {
+    // Curves with no length have no direction to offset along
+    let start       = curve.start_point();
+    let (cp1, cp2)  = curve.control_points();
+    let end         = curve.end_point();
+
+    if first_direction(&[cp1 - start, cp2 - start, end - start]).is_none() {
+        return vec![];
+    }
+
+    // flo_curves offsets along the left-hand normal. Fitting to sampled points is accurate even on tight curves, but it can fail to
+    // find a fit, so the scaling algorithm is used as a fallback
+    let offset_curves = offset_lms_sampling(curve, |_t| -distance, |_t| 0.0, OFFSET_SAMPLES, OFFSET_MAX_ERROR)
+        .unwrap_or_else(|| offset(curve, -distance, -distance));
+
+    offset_curves.into_iter()
+        .map(|offset_curve| {
+            let start       = offset_curve.start_point();
+            let (cp1, cp2)  = offset_curve.control_points();
+            let end         = offset_curve.end_point();
+
+            // The corner is the point on the original curve that the end of this section is offset from
+            let corner      = match first_direction(&[end - cp2, end - cp1, end - start]) {
+                Some(end_tangent)   => end - Coord2(end_tangent.y(), -end_tangent.x()) * distance,
+                None                => end,
+            };
+
+            OffsetSection {
+                start:  start,
+                cp1:    cp1,
+                cp2:    cp2,
+                end:    end,
+                corner: corner,
+            }
+        })
+        .collect()
+}
+
+///
+/// Joins two offset sections, returning the extra points that need to be added between them
+///
+/// Convex corners (where the sections move apart) are mitred, and concave corners (where they overlap) are trimmed back to the
+/// point where they cross so the result does not contain a loop.
+///
+fn join_sections(first: &mut OffsetSection, second: &mut OffsetSection, distance: f64) -> Vec<Coord2> {
+    if first.end.distance_to(&second.start) < CLOSE_DISTANCE {
+        return vec![];
+    }
+
+    let (end_tangent, start_tangent) = match (first.end_tangent(), second.start_tangent()) {
+        (Some(end_tangent), Some(start_tangent))    => (end_tangent, start_tangent),
+        _                                           => { return vec![second.start]; }
+    };
+
+    match line_intersection(first.end, end_tangent, second.start, start_tangent) {
+        None            => vec![second.start],
+        Some(meeting)   => {
+            let towards_meeting = meeting - first.end;
+
+            if towards_meeting.x()*end_tangent.x() + towards_meeting.y()*end_tangent.y() > 0.0 {
+                // Convex corner: extend the two sections until they meet (or bevel if the point is too far away)
+                if meeting.distance_to(&first.corner) > MITER_LIMIT * distance.abs() {
+                    vec![second.start]
+                } else {
+                    vec![meeting, second.start]
+                }
+            } else {
+                // Concave corner: trim both sections back to the point where they cross
+                first.move_end(meeting);
+                second.move_start(meeting);
+
+                vec![]
+            }
+        }
+    }
+}
+
+///
+/// Creates a new path that is offset from an existing one by a fixed distance
+///
+/// For closed paths, positive distances grow the path outwards and negative distances shrink it inwards. Open paths are offset
+/// to the right-hand side of their direction of travel for positive distances. Unlike stroking a path, this produces a single
+/// contour, so it's suitable for generating outlines or glows around a shape.
+///
+/// Each curve is offset using `flo_curves::bezier::offset_lms_sampling()`, and the results are joined at the corners of the path.
+///
+/// Concave corners are trimmed where the offset edges cross. Insetting a path by more than the width of one of its features can
+/// still produce a self-intersecting result.
+///
+pub fn path_offset<Path: BezierPathFactory<Point=Coord2>>(path: &Path, distance: f64) -> Path {
+    let start_point = path.start_point();
+    let points      = path.points().collect::<Vec<_>>();
+    let end_point   = points.last().map(|(_, _, end)| *end).unwrap_or(start_point);
+    let is_closed   = points.len() > 1 && end_point.distance_to(&start_point) < CLOSE_DISTANCE;
+
+    // Offset to the outside for closed paths, or to the right for open paths
+    let distance    = if is_closed && signed_area(start_point, &points) < 0.0 { -distance } else { distance };
+
+    // Offset each curve in the path
+    let mut last_point  = start_point;
+    let mut sections    = vec![];
+
+    for (cp1, cp2, end) in points {
+        let curve   = Curve::from_points(last_point, (cp1, cp2), end);
+        last_point  = end;
+
+        sections.extend(offset_curve(&curve, distance));
+    }
+
+    if sections.is_empty() {
+        return Path::from_points(start_point, vec![]);
+    }
+
+    // Join the sections (including the join between the last and first sections for closed paths)
+    let num_sections    = sections.len();
+    let num_joins       = if is_closed { num_sections } else { num_sections - 1 };
+    let mut joins       = vec![vec![]; num_sections];
+
+    for idx in 0..num_joins {
+        let next_idx        = (idx + 1) % num_sections;
+        let mut first       = sections[idx];
+        let mut second      = sections[next_idx];
+
+        joins[idx]          = join_sections(&mut first, &mut second, distance);
+        sections[idx]       = first;
+        sections[next_idx]  = second;
+    }
+
+    // Generate the offset path
+    let mut offset_points   = vec![];
+    let mut last_point      = sections[0].start;
+
+    for (section, join) in sections.iter().zip(joins) {
+        offset_points.push((section.cp1, section.cp2, section.end));
+        last_point = section.end;
+
+        for join_point in join {
+            offset_points.push(line_between(last_point, join_point));
+            last_point = join_point;
+        }
+    }
+
+    Path::from_points(sections[0].start, offset_points)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn polygon(corners: Vec<Coord2>) -> SimpleBezierPath {
+        let lines = corners.windows(2).map(|points| line_between(points[0], points[1])).collect::<Vec<_>>();
+
+        SimpleBezierPath::from_points(corners[0], lines)
+    }
+
+    fn square(min: f64, max: f64) -> SimpleBezierPath {
+        polygon(vec![Coord2(min, min), Coord2(max, min), Coord2(max, max), Coord2(min, max), Coord2(min, min)])
+    }
+
+    fn bounds(path: &SimpleBezierPath) -> (Coord2, Coord2) {
+        let points = path.points().flat_map(|(cp1, cp2, end)| vec![cp1, cp2, end]).chain(Some(path.start_point()));
+
+        points.fold((Coord2(f64::MAX, f64::MAX), Coord2(f64::MIN, f64::MIN)), |(min, max), point| {
+            (Coord2(min.x().min(point.x()), min.y().min(point.y())), Coord2(max.x().max(point.x()), max.y().max(point.y())))
+        })
+    }
+
+    #[test]
+    fn outset_square() {
+        let offset      = path_offset(&square(10.0, 110.0), 5.0);
+        let (min, max)  = bounds(&offset);
+
+        assert!(min.distance_to(&Coord2(5.0, 5.0)) < 0.001, "{:?}", min);
+        assert!(max.distance_to(&Coord2(115.0, 115.0)) < 0.001, "{:?}", max);
+        assert!(((max.x() - min.x()) - 110.0).abs() < 0.001);
+        assert!(((max.y() - min.y()) - 110.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn outset_clockwise_square() {
+        let clockwise   = polygon(vec![Coord2(10.0, 10.0), Coord2(10.0, 110.0), Coord2(110.0, 110.0), Coord2(110.0, 10.0), Coord2(10.0, 10.0)]);
+        let offset      = path_offset(&clockwise, 5.0);
+        let (min, max)  = bounds(&offset);
+
+        assert!(min.distance_to(&Coord2(5.0, 5.0)) < 0.001, "{:?}", min);
+        assert!(max.distance_to(&Coord2(115.0, 115.0)) < 0.001, "{:?}", max);
+    }
+
+    fn circle(radius: f64) -> SimpleBezierPath {
+        let curves: Vec<Curve<Coord2>>  = flo_curves::arc::Circle::new(Coord2(0.0, 0.0), radius).to_curves();
+        let points                      = curves.iter().map(|curve| { let (cp1, cp2) = curve.control_points(); (cp1, cp2, curve.end_point()) }).collect();
+
+        SimpleBezierPath::from_points(curves[0].start_point(), points)
+    }
+
+    ///
+    /// The range of distances from the origin of the points along a path
+    ///
+    fn distance_range(path: &SimpleBezierPath) -> (f64, f64) {
+        let mut last_point  = path.start_point();
+        let mut range       = (f64::MAX, f64::MIN);
+
+        for (cp1, cp2, end) in path.points() {
+            let curve   = Curve::from_points(last_point, (cp1, cp2), end);
+            last_point  = end;
+
+            for sample in 0..=100 {
+                let distance    = curve.point_at_pos((sample as f64) / 100.0).distance_to(&Coord2(0.0, 0.0));
+                range           = (range.0.min(distance), range.1.max(distance));
+            }
+        }
+
+        range
+    }
+
+    #[test]
+    fn outset_circle_is_accurate() {
+        // Offsetting a circle should produce another circle: a tight curve shows up any inaccuracy in the offset
+        let (min, max) = distance_range(&path_offset(&circle(10.0), 5.0));
+
+        assert!((min - 15.0).abs() < 0.05 && (max - 15.0).abs() < 0.05, "{} {}", min, max);
+    }
+
+    #[test]
+    fn inset_circle_is_accurate() {
+        let (min, max) = distance_range(&path_offset(&circle(10.0), -5.0));
+
+        assert!((min - 5.0).abs() < 0.05 && (max - 5.0).abs() < 0.05, "{} {}", min, max);
+    }
+
+    #[test]
+    fn inset_square() {
+        let offset      = path_offset(&square(10.0, 110.0), -5.0);
+        let (min, max)  = bounds(&offset);
+
+        // Concave corners are trimmed, so nothing should extend outside of the inset square
+        assert!(min.distance_to(&Coord2(15.0, 15.0)) < 0.001, "{:?}", min);
+        assert!(max.distance_to(&Coord2(105.0, 105.0)) < 0.001, "{:?}", max);
+    }
+}
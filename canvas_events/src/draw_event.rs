@@ -26,8 +26,9 @@ pub enum DrawEvent {
     /// A pointer device has changed its state
     Pointer(PointerAction, PointerId, PointerState),
 
-    /// The user has pressed a key (parameters are scancode and the name of the key that was pressed, if known)
-    KeyDown(u64, Option<Key>),
+    /// The user has pressed a key (parameters are scancode, the name of the key that was pressed, if known, and whether or not this
+    /// is a repeat generated by the key being held down)
+    KeyDown(u64, Option<Key>, bool),
 
     /// The user has released a key (parameters are scancode and the name of the key that was pressed, if known)
     KeyUp(u64, Option<Key>),
@@ -38,6 +38,18 @@ pub enum EventWindowRequest {
 
     /// Sets the mouse pointer to display for the window
     SetMousePointer(MousePointer),
+
+    /// Sets whether or not the window should be displayed above all other windows
+    SetAlwaysOnTop(bool),
+
+    /// Sets whether or not the window should be hidden from the taskbar
+    SetSkipTaskbar(bool),
+
+    /// Starts moving the window with the mouse pointer
+    ///
+    /// This should be sent while a mouse button is held down, usually in response to a `ButtonDown` event over an area the
+    /// application is drawing as a title bar (useful when the window has no decorations)
+    DragWindow,
 }
 
 
@@ -66,6 +78,18 @@ pub enum DrawingWindowRequest {
 
     /// Sets the mouse pointer to display for the window
     SetMousePointer(MousePointer),
+
+    /// Sets whether or not the window should be displayed above all other windows
+    SetAlwaysOnTop(bool),
+
+    /// Sets whether or not the window should be hidden from the taskbar
+    SetSkipTaskbar(bool),
+
+    /// Starts moving the window with the mouse pointer
+    ///
+    /// This should be sent while a mouse button is held down, usually in response to a `ButtonDown` event over an area the
+    /// application is drawing as a title bar (useful when the window has no decorations)
+    DragWindow,
 }
 
 ///
@@ -92,6 +116,18 @@ pub enum RenderWindowRequest {
 
     /// Sets the mouse pointer to display for the window
     SetMousePointer(MousePointer),
+
+    /// Sets whether or not the window should be displayed above all other windows
+    SetAlwaysOnTop(bool),
+
+    /// Sets whether or not the window should be hidden from the taskbar
+    SetSkipTaskbar(bool),
+
+    /// Starts moving the window with the mouse pointer
+    ///
+    /// This should be sent while a mouse button is held down, usually in response to a `ButtonDown` event over an area the
+    /// application is drawing as a title bar (useful when the window has no decorations)
+    DragWindow,
 }
 
 impl From<RenderRequest> for RenderWindowRequest {
@@ -115,6 +151,9 @@ impl From<EventWindowRequest> for RenderWindowRequest {
             EventWindowRequest::SetFullScreen(fullscreen)       => RenderWindowRequest::SetFullScreen(fullscreen),
             EventWindowRequest::SetHasDecorations(decorations)  => RenderWindowRequest::SetHasDecorations(decorations),
             EventWindowRequest::SetMousePointer(mouse_pointer)  => RenderWindowRequest::SetMousePointer(mouse_pointer),
+            EventWindowRequest::SetAlwaysOnTop(always_on_top)   => RenderWindowRequest::SetAlwaysOnTop(always_on_top),
+            EventWindowRequest::SetSkipTaskbar(skip_taskbar)    => RenderWindowRequest::SetSkipTaskbar(skip_taskbar),
+            EventWindowRequest::DragWindow                      => RenderWindowRequest::DragWindow,
         }
     }
 }
@@ -128,6 +167,9 @@ impl From<EventWindowRequest> for DrawingWindowRequest {
             EventWindowRequest::SetFullScreen(fullscreen)       => DrawingWindowRequest::SetFullScreen(fullscreen),
             EventWindowRequest::SetHasDecorations(decorations)  => DrawingWindowRequest::SetHasDecorations(decorations),
             EventWindowRequest::SetMousePointer(mouse_pointer)  => DrawingWindowRequest::SetMousePointer(mouse_pointer),
+            EventWindowRequest::SetAlwaysOnTop(always_on_top)   => DrawingWindowRequest::SetAlwaysOnTop(always_on_top),
+            EventWindowRequest::SetSkipTaskbar(skip_taskbar)    => DrawingWindowRequest::SetSkipTaskbar(skip_taskbar),
+            EventWindowRequest::DragWindow                      => DrawingWindowRequest::DragWindow,
         }
     }
 }
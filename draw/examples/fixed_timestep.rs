@@ -0,0 +1,124 @@
+use flo_draw::*;
+use flo_canvas::*;
+
+use rand::*;
+use futures::prelude::*;
+use futures::executor;
+
+use std::time::{Instant};
+
+#[derive(Clone)]
+struct Ball {
+    col: Color,
+    radius: f64,
+    x: f64,
+    y: f64,
+
+    dx: f64,
+    dy: f64
+}
+
+impl Ball {
+    ///
+    /// Generates a new ball
+    ///
+    pub fn random() -> Ball {
+        Ball {
+            col:    Color::Hsluv(random::<f32>()*360.0, random::<f32>()*100.0, random::<f32>()*75.0 + 25.0, 1.0),
+            radius: random::<f64>() * 16.0 + 16.0,
+            x:      random::<f64>() * 1000.0,
+            y:      random::<f64>() * 1000.0 + 64.0,
+            dx:     random::<f64>() * 8.0 - 4.0,
+            dy:     random::<f64>() * 8.0 - 4.0
+        }
+    }
+
+    ///
+    /// Moves this ball on by one simulation step
+    ///
+    pub fn update(&mut self) {
+        // Collide with the edges of the screen
+        if self.x+self.dx+self.radius > 1000.0 && self.dx > 0.0     { self.dx = -self.dx; }
+        if self.y+self.dy+self.radius > 1000.0 && self.dy > 0.0     { self.dy = -self.dy; }
+        if self.x+self.dx-self.radius < 0.0 && self.dx < 0.0        { self.dx = -self.dx; }
+        if self.y+self.dy-self.radius < 0.0 && self.dy < 0.0        { self.dy = -self.dy; }
+
+        // Gravity
+        if self.y >= self.radius {
+            self.dy -= 0.2;
+        }
+
+        // Move this ball in whatever direction it's going
+        self.x += self.dx;
+        self.y += self.dy;
+    }
+}
+
+///
+/// Draws the balls at a position between their previous and current states
+///
+fn draw_balls(gc: &mut impl GraphicsContext, previous: &[Ball], current: &[Ball], alpha: f64) {
+    gc.clear_canvas(Color::Rgba(0.6, 0.7, 0.8, 1.0));
+    gc.canvas_height(1000.0);
+    gc.center_region(0.0, 0.0, 1000.0, 1000.0);
+
+    for (previous, current) in previous.iter().zip(current.iter()) {
+        let x = previous.x + (current.x - previous.x) * alpha;
+        let y = previous.y + (current.y - previous.y) * alpha;
+
+        gc.circle(x as f32, y as f32, current.radius as f32);
+        gc.fill_color(current.col);
+        gc.fill();
+    }
+}
+
+///
+/// Bouncing balls, simulated at a fixed 60Hz but rendered whenever the display is ready for a new frame
+///
+/// `FixedTimestep` works out how many simulation steps to run each time a `NewFrame` event arrives from the window, and how
+/// far between the last two simulation states the frame should be drawn. Interpolating between the states keeps the motion
+/// smooth on displays that refresh faster or slower than the simulation rate.
+///
+pub fn main() {
+    // 'with_2d_graphics' is used to support operating systems that can't run event loops anywhere other than the main thread
+    with_2d_graphics(|| {
+        // Create a window and an event queue
+        let (canvas, events) = create_drawing_window_with_events("Fixed timestep");
+
+        // Generate some random balls (we keep the state from before the last simulation step for interpolation)
+        let mut current     = (0..256).map(|_| Ball::random()).collect::<Vec<_>>();
+        let mut previous    = current.clone();
+
+        // The simulation runs at 60 steps per second
+        let mut timestep    = FixedTimestep::new(60.0);
+
+        // Draw the first frame: the window will send a 'NewFrame' event once it's been displayed
+        timestep.advance_to(Instant::now());
+        canvas.draw(|gc| draw_balls(gc, &previous, &current, 1.0));
+
+        executor::block_on(async move {
+            let mut events = events;
+
+            while let Some(event) = events.next().await {
+                match event {
+                    DrawEvent::NewFrame => {
+                        // Run the simulation steps that have elapsed since the last frame
+                        let update = timestep.advance_to(Instant::now());
+
+                        for _ in 0..update.steps {
+                            previous = current.clone();
+                            current.iter_mut().for_each(|ball| ball.update());
+                        }
+
+                        // Render the next frame, interpolated between the last two simulation states
+                        canvas.draw(|gc| draw_balls(gc, &previous, &current, update.alpha));
+                    }
+
+                    DrawEvent::Closed => { break; }
+
+                    _ => { }
+                }
+            }
+        });
+    });
+}
@@ -0,0 +1,96 @@
+use flo_draw::*;
+use flo_canvas::*;
+
+use futures::prelude::*;
+use futures::executor;
+
+///
+/// Draws the scene that's displayed in the viewports
+///
+fn draw_scene(gc: &mut impl GraphicsContext) {
+    // Background
+    gc.new_path();
+    gc.rect(0.0, 0.0, 1000.0, 1000.0);
+    gc.fill_color(Color::Rgba(1.0, 1.0, 0.8, 1.0));
+    gc.fill();
+
+    // Triangle
+    gc.new_path();
+    gc.move_to(200.0, 200.0);
+    gc.line_to(800.0, 200.0);
+    gc.line_to(500.0, 800.0);
+    gc.line_to(200.0, 200.0);
+
+    gc.fill_color(Color::Rgba(0.0, 0.0, 0.8, 1.0));
+    gc.fill();
+}
+
+///
+/// Draws the scene (and the cursor, if there is one) into a sprite, then shows that sprite in each viewport
+///
+fn draw_viewports(gc: &mut impl GraphicsContext, viewports: &[CanvasViewport], cursor: Option<(f32, f32)>) {
+    gc.sprite(SpriteId(0));
+    gc.clear_sprite();
+    draw_scene(gc);
+
+    if let Some((x, y)) = cursor {
+        gc.new_path();
+        gc.circle(x, y, 20.0);
+        gc.stroke_color(Color::Rgba(0.1, 0.1, 0.1, 0.8));
+        gc.line_width(4.0);
+        gc.stroke();
+    }
+
+    gc.layer(LayerId(0));
+    gc.clear_layer();
+
+    for viewport in viewports.iter() {
+        viewport.draw_sprite(gc, SpriteId(0));
+    }
+}
+
+///
+/// Demonstrates rendering the same scene into two side-by-side viewports
+///
+/// The scene is drawn into a sprite, which each `CanvasViewport` then draws clipped to its own region of the canvas. The
+/// left viewport shows the whole scene and the right viewport shows a zoomed-in view of the middle of it. Pointer events
+/// are mapped back to scene coordinates by the viewport they fall in, and a circle is drawn at that position in both views.
+///
+pub fn main() {
+    with_2d_graphics(|| {
+        let (canvas, events) = create_drawing_window_with_events("Split viewports");
+
+        // One viewport showing the whole scene, and one showing the middle of the scene
+        let viewports = vec![
+            CanvasViewport::new(0.0, 0.0, 1000.0, 1000.0).show_region(0.0, 0.0, 1000.0, 1000.0),
+            CanvasViewport::new(1000.0, 0.0, 2000.0, 1000.0).show_region(300.0, 300.0, 700.0, 700.0),
+        ];
+
+        canvas.draw(|gc| {
+            gc.clear_canvas(Color::Rgba(0.3, 0.2, 0.0, 1.0));
+            gc.canvas_height(1000.0);
+            gc.center_region(0.0, 0.0, 2000.0, 1000.0);
+
+            draw_viewports(gc, &viewports, None);
+        });
+
+        executor::block_on(async move {
+            let mut events = events;
+
+            while let Some(event) = events.next().await {
+                match event {
+                    DrawEvent::Pointer(_action, _id, state) => {
+                        // Map the pointer into scene coordinates using whichever viewport it's over
+                        let cursor = state.location_in_canvas
+                            .and_then(|(x, y)| viewport_at_point(&viewports, x as _, y as _))
+                            .map(|(_viewport_idx, scene_pos)| scene_pos);
+
+                        canvas.draw(|gc| draw_viewports(gc, &viewports, cursor));
+                    }
+
+                    _ => {}
+                }
+            }
+        })
+    });
+}
@@ -160,7 +160,7 @@ pub fn main() {
                     let next_event = events.next().await;
                     if next_event.is_none() { return; }
 
-                    if let Some(DrawEvent::KeyDown(_, Some(key))) = next_event {
+                    if let Some(DrawEvent::KeyDown(_, Some(key), _)) = next_event {
                         if key == Key::KeySpace {
                             break;
                         }
@@ -0,0 +1,40 @@
+use flo_draw::*;
+use flo_canvas::*;
+use flo_binding::*;
+
+///
+/// Displays a translucent circle on a transparent window
+///
+/// Transparent windows are not supported on every platform: where they aren't, the window will just have a black background.
+///
+pub fn main() {
+    with_2d_graphics(|| {
+        // Create some window properties for a transparent window
+        let mut window_properties               = WindowProperties::from(&"Transparent window");
+
+        window_properties.transparent           = BindRef::from(bind(true));
+        window_properties.has_decorations       = BindRef::from(bind(false));
+        window_properties.initial_clear_color   = BindRef::from(bind(Color::Rgba(0.0, 0.0, 0.0, 0.0)));
+
+        // Create a window with these properties
+        let canvas = create_drawing_window(window_properties);
+
+        canvas.draw(|gc| {
+            // Clear the canvas to a transparent colour so the desktop shows through
+            gc.clear_canvas(Color::Rgba(0.0, 0.0, 0.0, 0.0));
+            gc.canvas_height(1000.0);
+            gc.center_region(0.0, 0.0, 1000.0, 1000.0);
+
+            // Draw a translucent circle
+            gc.new_path();
+            gc.circle(500.0, 500.0, 400.0);
+
+            gc.fill_color(Color::Rgba(0.3, 0.6, 0.8, 0.5));
+            gc.fill();
+
+            gc.line_width(6.0);
+            gc.stroke_color(Color::Rgba(0.0, 0.0, 0.0, 0.8));
+            gc.stroke();
+        });
+    });
+}
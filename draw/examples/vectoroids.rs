@@ -117,10 +117,10 @@ pub fn main() {
                         });
                     }
 
-                    VectorEvent::DrawEvent(DrawEvent::KeyDown(_, Some(Key::KeyLeft))) => {
+                    VectorEvent::DrawEvent(DrawEvent::KeyDown(_, Some(Key::KeyLeft), _)) => {
                         game_state.ship.rotation = (360.0) / 60.0;
                     }
-                    VectorEvent::DrawEvent(DrawEvent::KeyDown(_, Some(Key::KeyRight))) => {
+                    VectorEvent::DrawEvent(DrawEvent::KeyDown(_, Some(Key::KeyRight), _)) => {
                         game_state.ship.rotation = -(360.0) / 60.0;
                     }
                     VectorEvent::DrawEvent(DrawEvent::KeyUp(_, Some(Key::KeyLeft))) |
@@ -128,14 +128,14 @@ pub fn main() {
                         game_state.ship.rotation = 0.0;
                     }
 
-                    VectorEvent::DrawEvent(DrawEvent::KeyDown(_, Some(Key::KeyUp))) => {
+                    VectorEvent::DrawEvent(DrawEvent::KeyDown(_, Some(Key::KeyUp), _)) => {
                         game_state.ship.thrust = 0.3;
                     }
                     VectorEvent::DrawEvent(DrawEvent::KeyUp(_, Some(Key::KeyUp))) => {
                         game_state.ship.thrust = 0.0;
                     }
 
-                    VectorEvent::DrawEvent(DrawEvent::KeyDown(_, Some(Key::KeySpace))) => {
+                    VectorEvent::DrawEvent(DrawEvent::KeyDown(_, Some(Key::KeySpace), _)) => {
                         game_state.bullets.push(Bullet::new(bullet_sprite, game_state.ship.x, game_state.ship.y, game_state.ship.vel_x, game_state.ship.vel_y, game_state.ship.angle));
                     }
 
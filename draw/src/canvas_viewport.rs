@@ -0,0 +1,142 @@
+use flo_canvas::*;
+
+///
+/// A viewport displays a scene in a sub-rectangle of a canvas, using its own transform
+///
+/// The scene is drawn once into a sprite, and each viewport draws that sprite clipped to its bounds. This makes it
+/// possible to show the same drawing several times in one window, eg for split-screen or comparison views. Pointer
+/// positions in canvas coordinates can be mapped back into scene coordinates with `canvas_to_scene()`, which
+/// also determines which viewport (if any) a pointer event belongs to.
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct CanvasViewport {
+    /// The bounds of this viewport in canvas coordinates, as (x1, y1, x2, y2)
+    bounds: (f32, f32, f32, f32),
+
+    /// Transform from scene coordinates to canvas coordinates
+    transform: Transform2D,
+}
+
+impl CanvasViewport {
+    ///
+    /// Creates a viewport covering the specified region of the canvas, with scene coordinates matching canvas coordinates
+    ///
+    pub fn new(x1: f32, y1: f32, x2: f32, y2: f32) -> CanvasViewport {
+        CanvasViewport {
+            bounds:     (f32::min(x1, x2), f32::min(y1, y2), f32::max(x1, x2), f32::max(y1, y2)),
+            transform:  Transform2D::identity(),
+        }
+    }
+
+    ///
+    /// Sets the transform used to map scene coordinates into canvas coordinates for this viewport
+    ///
+    pub fn with_transform(self, transform: Transform2D) -> CanvasViewport {
+        CanvasViewport {
+            bounds:     self.bounds,
+            transform:  transform,
+        }
+    }
+
+    ///
+    /// Sets the transform so that the specified region of the scene is centered in this viewport, preserving the aspect ratio
+    ///
+    pub fn show_region(self, x1: f32, y1: f32, x2: f32, y2: f32) -> CanvasViewport {
+        let (vx1, vy1, vx2, vy2)    = self.bounds;
+        let (sx1, sy1, sx2, sy2)    = (f32::min(x1, x2), f32::min(y1, y2), f32::max(x1, x2), f32::max(y1, y2));
+
+        let scene_width             = sx2 - sx1;
+        let scene_height            = sy2 - sy1;
+        let scale                   = if scene_width > 0.0 && scene_height > 0.0 {
+            f32::min((vx2 - vx1) / scene_width, (vy2 - vy1) / scene_height)
+        } else {
+            1.0
+        };
+
+        let center_scene            = ((sx1 + sx2) / 2.0, (sy1 + sy2) / 2.0);
+        let center_viewport         = ((vx1 + vx2) / 2.0, (vy1 + vy2) / 2.0);
+
+        let transform               = Transform2D::translate(center_viewport.0, center_viewport.1)
+            * Transform2D::scale(scale, scale)
+            * Transform2D::translate(-center_scene.0, -center_scene.1);
+
+        self.with_transform(transform)
+    }
+
+    ///
+    /// Returns the bounds of this viewport in canvas coordinates, as (x1, y1, x2, y2)
+    ///
+    pub fn bounds(&self) -> (f32, f32, f32, f32) {
+        self.bounds
+    }
+
+    ///
+    /// Returns the transform from scene coordinates to canvas coordinates for this viewport
+    ///
+    pub fn transform(&self) -> Transform2D {
+        self.transform
+    }
+
+    ///
+    /// True if the specified point (in canvas coordinates) is within this viewport
+    ///
+    pub fn contains(&self, x: f32, y: f32) -> bool {
+        let (x1, y1, x2, y2) = self.bounds;
+
+        x >= x1 && x <= x2 && y >= y1 && y <= y2
+    }
+
+    ///
+    /// Maps a point in scene coordinates to a point in canvas coordinates
+    ///
+    pub fn scene_to_canvas(&self, x: f32, y: f32) -> (f32, f32) {
+        self.transform.transform_point(x, y)
+    }
+
+    ///
+    /// Maps a point in canvas coordinates (eg, `PointerState::location_in_canvas`) to scene coordinates
+    ///
+    /// Returns `None` if the point is outside of this viewport or the viewport's transform can't be inverted
+    ///
+    pub fn canvas_to_scene(&self, x: f32, y: f32) -> Option<(f32, f32)> {
+        if !self.contains(x, y) {
+            return None;
+        }
+
+        self.transform.invert().map(|inverse| inverse.transform_point(x, y))
+    }
+
+    ///
+    /// Draws a sprite containing the scene into this viewport, clipped to the viewport bounds
+    ///
+    /// This should be called while the layer the viewport should appear on is selected
+    ///
+    pub fn draw_sprite<TContext: GraphicsContext>(&self, gc: &mut TContext, sprite_id: SpriteId) {
+        let (x1, y1, x2, y2) = self.bounds;
+
+        gc.push_state();
+
+        // Restrict drawing to the viewport
+        gc.new_path();
+        gc.rect(x1, y1, x2, y2);
+        gc.clip();
+
+        // Draw the scene with the viewport's transform
+        gc.sprite_transform(SpriteTransform::Identity);
+        gc.sprite_transform(SpriteTransform::Transform2D(self.transform));
+        gc.draw_sprite(sprite_id);
+
+        gc.unclip();
+        gc.pop_state();
+    }
+}
+
+///
+/// Finds the first viewport containing the specified point in canvas coordinates, returning its index and the point in scene coordinates
+///
+pub fn viewport_at_point(viewports: &[CanvasViewport], x: f32, y: f32) -> Option<(usize, (f32, f32))> {
+    viewports.iter()
+        .enumerate()
+        .filter_map(|(idx, viewport)| viewport.canvas_to_scene(x, y).map(|point| (idx, point)))
+        .next()
+}
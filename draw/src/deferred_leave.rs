@@ -0,0 +1,151 @@
+use crate::events::*;
+
+use std::collections::{HashMap};
+
+///
+/// Holds back the 'leave' events for pointers that leave a window while a button is held down
+///
+/// This doesn't capture the pointer itself: the windowing system already keeps sending move and release events to the window
+/// where a drag started (with coordinates outside the window bounds) until the last button is released. What it does do is
+/// stop the window from seeing a 'leave' event part way through the drag: any 'leave' event is delivered after the last button
+/// is released instead, and leaving and re-entering the window during the drag produces no events at all.
+///
+pub (crate) struct DeferredLeave {
+    /// For each pointer with a button held down, the 'leave' event that was received while it was held (if the pointer has left the window)
+    captured: HashMap<PointerId, Option<PointerState>>,
+}
+
+impl DeferredLeave {
+    ///
+    /// Creates a new tracker with no pointers held down
+    ///
+    pub fn new() -> DeferredLeave {
+        DeferredLeave {
+            captured: HashMap::new()
+        }
+    }
+
+    ///
+    /// Updates the held pointers from a set of events for a window, returning the events that should be delivered to it
+    ///
+    pub fn filter_events(&mut self, events: Vec<DrawEvent>) -> Vec<DrawEvent> {
+        let mut filtered = vec![];
+
+        for event in events {
+            match event {
+                DrawEvent::Pointer(action, pointer_id, pointer_state) => {
+                    filtered.extend(self.filter_pointer_event(action, pointer_id, pointer_state));
+                }
+
+                other_event => { filtered.push(other_event); }
+            }
+        }
+
+        filtered
+    }
+
+    ///
+    /// Updates the held pointers for a single pointer event
+    ///
+    fn filter_pointer_event(&mut self, action: PointerAction, pointer_id: PointerId, pointer_state: PointerState) -> Vec<DrawEvent> {
+        match action {
+            PointerAction::ButtonDown => {
+                // Pressing a button holds back any 'leave' event for the pointer
+                self.captured.entry(pointer_id).or_insert(None);
+                vec![DrawEvent::Pointer(action, pointer_id, pointer_state)]
+            }
+
+            PointerAction::Leave => {
+                if let Some(leave_state) = self.captured.get_mut(&pointer_id) {
+                    // Held pointers don't leave the window until they're released
+                    *leave_state = Some(pointer_state);
+                    vec![]
+                } else {
+                    vec![DrawEvent::Pointer(action, pointer_id, pointer_state)]
+                }
+            }
+
+            PointerAction::Enter => {
+                if let Some(leave_state) = self.captured.get_mut(&pointer_id).filter(|leave_state| leave_state.is_some()) {
+                    // The pointer is returning to the window before it was released, so the app never saw it leave
+                    *leave_state = None;
+                    vec![]
+                } else {
+                    vec![DrawEvent::Pointer(action, pointer_id, pointer_state)]
+                }
+            }
+
+            PointerAction::ButtonUp | PointerAction::Cancel => {
+                let mut events = vec![DrawEvent::Pointer(action, pointer_id, pointer_state.clone())];
+
+                if pointer_state.buttons.len() == 0 {
+                    // Releasing the last button means the pointer can now leave the window
+                    if let Some(Some(leave_state)) = self.captured.remove(&pointer_id) {
+                        events.push(DrawEvent::Pointer(PointerAction::Leave, pointer_id, PointerState { buttons: vec![], ..leave_state }));
+                    }
+                }
+
+                events
+            }
+
+            PointerAction::Move | PointerAction::Drag => {
+                vec![DrawEvent::Pointer(action, pointer_id, pointer_state)]
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn pointer_event(action: PointerAction, location: (f64, f64), buttons: Vec<Button>) -> DrawEvent {
+        let mut state               = PointerState::new();
+        state.location_in_window    = location;
+        state.buttons               = buttons;
+
+        DrawEvent::Pointer(action, PointerId(0), state)
+    }
+
+    #[test]
+    fn drag_outside_window() {
+        let mut deferred = DeferredLeave::new();
+
+        let press       = deferred.filter_events(vec![pointer_event(PointerAction::ButtonDown, (10.0, 10.0), vec![Button::Left])]);
+        let leave       = deferred.filter_events(vec![pointer_event(PointerAction::Leave, (-1.0, 10.0), vec![Button::Left])]);
+        let drag        = deferred.filter_events(vec![pointer_event(PointerAction::Drag, (-50.0, 10.0), vec![Button::Left])]);
+        let release     = deferred.filter_events(vec![pointer_event(PointerAction::ButtonUp, (-60.0, 10.0), vec![])]);
+
+        // Press, out-of-bounds drag and release are all delivered, and the pointer leaves the window after it's released
+        assert!(press == vec![pointer_event(PointerAction::ButtonDown, (10.0, 10.0), vec![Button::Left])]);
+        assert!(leave == vec![]);
+        assert!(drag == vec![pointer_event(PointerAction::Drag, (-50.0, 10.0), vec![Button::Left])]);
+        assert!(release == vec![
+            pointer_event(PointerAction::ButtonUp, (-60.0, 10.0), vec![]),
+            pointer_event(PointerAction::Leave, (-1.0, 10.0), vec![]),
+        ]);
+    }
+
+    #[test]
+    fn drag_out_and_back_in() {
+        let mut deferred = DeferredLeave::new();
+
+        deferred.filter_events(vec![pointer_event(PointerAction::ButtonDown, (10.0, 10.0), vec![Button::Left])]);
+        let leave       = deferred.filter_events(vec![pointer_event(PointerAction::Leave, (-1.0, 10.0), vec![Button::Left])]);
+        let enter       = deferred.filter_events(vec![pointer_event(PointerAction::Enter, (1.0, 10.0), vec![Button::Left])]);
+        let release     = deferred.filter_events(vec![pointer_event(PointerAction::ButtonUp, (5.0, 10.0), vec![])]);
+
+        // Leaving and re-entering while a button is held is invisible
+        assert!(leave == vec![]);
+        assert!(enter == vec![]);
+        assert!(release == vec![pointer_event(PointerAction::ButtonUp, (5.0, 10.0), vec![])]);
+    }
+
+    #[test]
+    fn leave_without_held_button() {
+        let mut deferred = DeferredLeave::new();
+
+        let leave       = deferred.filter_events(vec![pointer_event(PointerAction::Leave, (-1.0, 10.0), vec![])]);
+        assert!(leave == vec![pointer_event(PointerAction::Leave, (-1.0, 10.0), vec![])]);
+    }
+}
@@ -0,0 +1,156 @@
+use crate::events::*;
+
+use futures::prelude::*;
+use futures::stream::{BoxStream};
+use futures::task::{Context, Poll, Waker, ArcWake, waker};
+
+use std::pin::*;
+use std::sync::*;
+use std::collections::{HashMap, VecDeque};
+
+///
+/// The shared state of a multiplexed event stream
+///
+struct MultiplexerCore {
+    /// The stream that the events are being read from (None once it has finished)
+    source: Option<BoxStream<'static, DrawEvent>>,
+
+    /// The events waiting to be read by each subscriber
+    queues: HashMap<usize, VecDeque<DrawEvent>>,
+
+    /// The ID to assign to the next subscriber
+    next_subscriber_id: usize,
+}
+
+///
+/// The wakers for the subscribers that are waiting for the source stream to produce an event
+///
+struct SubscriberWakers {
+    wakers: Mutex<HashMap<usize, Waker>>
+}
+
+impl ArcWake for SubscriberWakers {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        let wakers = arc_self.wakers.lock().unwrap().drain().collect::<Vec<_>>();
+
+        for (_id, waker) in wakers {
+            waker.wake();
+        }
+    }
+}
+
+///
+/// Shares a stream of `DrawEvent`s from a window between any number of subscribers
+///
+/// The window functions return a single stream of events: this can be used when several independent parts of an
+/// application (for example an input handler and a recorder) each need to see every event. Each subscriber receives
+/// every event that is read from the window after it subscribed.
+///
+/// Events are read from the window as the subscribers are polled, and are queued for any subscriber that is not currently
+/// reading, so every subscriber should either be read from or dropped.
+///
+pub struct DrawEventMultiplexer {
+    core:   Arc<Mutex<MultiplexerCore>>,
+    wakers: Arc<SubscriberWakers>,
+}
+
+///
+/// A stream of the events received by a `DrawEventMultiplexer`
+///
+pub struct DrawEventSubscriber {
+    id:     usize,
+    core:   Arc<Mutex<MultiplexerCore>>,
+    wakers: Arc<SubscriberWakers>,
+}
+
+impl DrawEventMultiplexer {
+    ///
+    /// Creates a multiplexer that distributes the events from a window event stream
+    ///
+    pub fn new(events: impl 'static + Send + Stream<Item=DrawEvent>) -> DrawEventMultiplexer {
+        let core = MultiplexerCore {
+            source:             Some(events.boxed()),
+            queues:             HashMap::new(),
+            next_subscriber_id: 0,
+        };
+
+        DrawEventMultiplexer {
+            core:   Arc::new(Mutex::new(core)),
+            wakers: Arc::new(SubscriberWakers { wakers: Mutex::new(HashMap::new()) }),
+        }
+    }
+
+    ///
+    /// Creates a new stream that will receive all of the events read from the window from now on
+    ///
+    pub fn subscribe(&self) -> DrawEventSubscriber {
+        let mut core    = self.core.lock().unwrap();
+        let id          = core.next_subscriber_id;
+
+        core.next_subscriber_id += 1;
+        core.queues.insert(id, VecDeque::new());
+
+        DrawEventSubscriber {
+            id:     id,
+            core:   Arc::clone(&self.core),
+            wakers: Arc::clone(&self.wakers),
+        }
+    }
+}
+
+impl Stream for DrawEventSubscriber {
+    type Item = DrawEvent;
+
+    fn poll_next(self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Option<DrawEvent>> {
+        let mut core    = self.core.lock().unwrap();
+        let core        = &mut *core;
+
+        loop {
+            // Return any event that's already waiting for this subscriber
+            if let Some(event) = core.queues.get_mut(&self.id).and_then(|queue| queue.pop_front()) {
+                return Poll::Ready(Some(event));
+            }
+
+            // Read the next event from the source stream (stopping if it's finished)
+            let source = if let Some(source) = core.source.as_mut() { source } else { return Poll::Ready(None); };
+
+            // Register this subscriber before polling, so it's woken up along with any other waiting subscribers
+            self.wakers.wakers.lock().unwrap().insert(self.id, context.waker().clone());
+            let source_waker        = waker(Arc::clone(&self.wakers));
+            let mut source_context  = Context::from_waker(&source_waker);
+
+            match source.poll_next_unpin(&mut source_context) {
+                Poll::Pending       => { return Poll::Pending; }
+
+                Poll::Ready(None)   => {
+                    // Stream has finished: wake up the other subscribers so they can finish too
+                    core.source = None;
+                    self.wakers.wakers.lock().unwrap().remove(&self.id);
+                    source_waker.wake();
+
+                    return Poll::Ready(None);
+                }
+
+                Poll::Ready(Some(event)) => {
+                    // Every subscriber receives a copy of the event
+                    self.wakers.wakers.lock().unwrap().remove(&self.id);
+
+                    for queue in core.queues.values_mut() {
+                        queue.push_back(event.clone());
+                    }
+
+                    // Wake up any other subscriber that is waiting for an event
+                    source_waker.wake();
+                }
+            }
+        }
+    }
+}
+
+impl Drop for DrawEventSubscriber {
+    fn drop(&mut self) {
+        // Stop queuing events for this subscriber
+        self.core.lock().unwrap().queues.remove(&self.id);
+        self.wakers.wakers.lock().unwrap().remove(&self.id);
+    }
+}
@@ -151,23 +151,32 @@ where
             DrawEvent::Closed                   => { vec![] }
             DrawEvent::CanvasTransform(_)       => { vec![] }
             DrawEvent::Pointer(_, _, _)         => { vec![] }
-            DrawEvent::KeyDown(_, _)            => { vec![] }
+            DrawEvent::KeyDown(_, _, _)         => { vec![] }
             DrawEvent::KeyUp(_, _)              => { vec![] }
         }
     }
 }
 
+///
+/// Returns the transform that maps pointer coordinates in a window to coordinates on the canvas being displayed by a renderer
+///
+/// This is used to set the `location_in_canvas` for pointer events. Window coordinates have their origin at the top-left corner,
+/// with y increasing downwards, so the window height is needed to flip them into the canvas coordinate scheme.
+///
+pub (crate) fn window_to_canvas_transform(renderer: &CanvasRenderer, window_height: f64) -> Transform2D {
+    // Fetch the window tranform from the canvas, and invert it to get the transform from window coordinates to canvas coordinates
+    let window_transform    = renderer.get_window_transform().invert().unwrap();
+
+    // Window coordinates are inverted compared to canvas coordinates, so flip them before converting them
+    window_transform * Transform2D::translate(0.0, window_height as _) * Transform2D::scale(1.0, -1.0)
+}
+
 impl RendererState {
     ///
     /// Updates the window transform for this state
     ///
     fn update_window_transform(&mut self) -> Transform2D {
-        // Fetch the window tranform from the canvas, and invert it to get the transform from window coordinates to canvas coordinates
-        let window_transform    = self.renderer.get_window_transform().invert().unwrap();
-
-        // Window coordinates are inverted compared to canvas coordinates
-        let window_transform    = Transform2D::scale(1.0, -1.0) * window_transform;
-        let window_transform    = window_transform * Transform2D::translate(0.0, -self.height as _);
+        let window_transform    = window_to_canvas_transform(&self.renderer, self.height);
 
         // Update the value of the transform in the state
         self.window_transform   = Some(window_transform);
@@ -280,6 +289,9 @@ pub fn create_drawing_window_entity(context: &Arc<SceneContext>, entity_id: Enti
                             DrawingWindowRequest::SetFullScreen(fullscreen)         => { render_target.send(RenderWindowRequest::SetFullScreen(fullscreen)).await.ok(); },
                             DrawingWindowRequest::SetHasDecorations(decorations)    => { render_target.send(RenderWindowRequest::SetHasDecorations(decorations)).await.ok(); },
                             DrawingWindowRequest::SetMousePointer(mouse_pointer)    => { render_target.send(RenderWindowRequest::SetMousePointer(mouse_pointer)).await.ok(); },
+                            DrawingWindowRequest::SetAlwaysOnTop(always_on_top)     => { render_target.send(RenderWindowRequest::SetAlwaysOnTop(always_on_top)).await.ok(); },
+                            DrawingWindowRequest::SetSkipTaskbar(skip_taskbar)      => { render_target.send(RenderWindowRequest::SetSkipTaskbar(skip_taskbar)).await.ok(); },
+                            DrawingWindowRequest::DragWindow                        => { render_target.send(RenderWindowRequest::DragWindow).await.ok(); },
                         }
                     }
 
@@ -384,3 +396,33 @@ pub fn create_drawing_window_entity(context: &Arc<SceneContext>, entity_id: Enti
         when_closed.await;
     })
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use futures::executor;
+
+    #[test]
+    fn window_centre_maps_to_canvas_centre() {
+        let mut renderer = CanvasRenderer::new();
+
+        executor::block_on(async {
+            // 800x600 window showing a canvas with a height of 1000 (0,0 is at the centre of the canvas)
+            renderer.set_viewport(0.0..800.0, 0.0..600.0, 800.0, 600.0, 1.0);
+            renderer.draw(vec![Draw::ClearCanvas(Color::Rgba(0.0, 0.0, 0.0, 0.0)), Draw::CanvasHeight(1000.0)].into_iter()).collect::<Vec<_>>().await;
+        });
+
+        let window_to_canvas = window_to_canvas_transform(&renderer, 600.0);
+
+        // The centre of the window should be the centre of the canvas
+        let (x, y) = window_to_canvas.transform_point(400.0, 300.0);
+        assert!(x.abs() < 0.01, "{:?}", (x, y));
+        assert!(y.abs() < 0.01, "{:?}", (x, y));
+
+        // The top of the window is at the top of the canvas (window y coordinates are flipped compared to the canvas)
+        let (x, y) = window_to_canvas.transform_point(400.0, 0.0);
+        assert!(x.abs() < 0.01, "{:?}", (x, y));
+        assert!((y-500.0).abs() < 0.01, "{:?}", (x, y));
+    }
+}
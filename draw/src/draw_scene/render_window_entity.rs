@@ -4,7 +4,10 @@ use super::glutin_render_window_entity::*;
 #[cfg(feature="render-wgpu")]
 use super::wgpu_render_window_entity::*;
 
+use crate::window_properties::*;
+
 use flo_scene::*;
+use flo_binding::*;
 use flo_canvas_events::*;
 
 use std::sync::*;
@@ -12,23 +15,44 @@ use std::sync::*;
 ///
 /// Creates a render window in a scene with the specified entity ID
 ///
-#[cfg(all(feature="render-opengl", not(feature="render-wgpu")))]
 pub fn create_render_window_entity(context: &Arc<SceneContext>, entity_id: EntityId, initial_size: (u64, u64)) -> Result<SimpleEntityChannel<RenderWindowRequest>, CreateEntityError> {
-    create_glutin_render_window_entity(context, entity_id, initial_size)
+    let initial_properties = WindowProperties {
+        size: BindRef::from(bind(initial_size)),
+        ..WindowProperties::from(&())
+    };
+
+    create_render_window_entity_with_properties(context, entity_id, &initial_properties)
 }
 
 ///
-/// Retrieves or creates a scene context for flo_draw
+/// Creates a render window in a scene with the specified entity ID, using the initial size, clear colour and transparency from a set of window properties
+///
+/// The frame rate limit binding is followed by the window. The other window properties are not followed by the entity: they can be
+/// updated by sending requests to it.
+///
+#[cfg(all(feature="render-opengl", not(feature="render-wgpu")))]
+pub fn create_render_window_entity_with_properties(context: &Arc<SceneContext>, entity_id: EntityId, initial_properties: &WindowProperties) -> Result<SimpleEntityChannel<RenderWindowRequest>, CreateEntityError> {
+    create_glutin_render_window_entity(context, entity_id, initial_properties)
+}
+
+///
+/// Creates a render window in a scene with the specified entity ID, using the initial size, clear colour and transparency from a set of window properties
+///
+/// The frame rate limit binding is followed by the window. The other window properties are not followed by the entity: they can be
+/// updated by sending requests to it.
 ///
 #[cfg(all(feature="render-wgpu"))]
-pub fn create_render_window_entity(context: &Arc<SceneContext>, entity_id: EntityId, initial_size: (u64, u64)) -> Result<SimpleEntityChannel<RenderWindowRequest>, CreateEntityError> {
-    create_wgpu_render_window_entity(context, entity_id, initial_size)
+pub fn create_render_window_entity_with_properties(context: &Arc<SceneContext>, entity_id: EntityId, initial_properties: &WindowProperties) -> Result<SimpleEntityChannel<RenderWindowRequest>, CreateEntityError> {
+    create_wgpu_render_window_entity(context, entity_id, initial_properties)
 }
 
 ///
-/// Retrieves or creates a scene context for flo_draw
+/// Creates a render window in a scene with the specified entity ID, using the initial size, clear colour and transparency from a set of window properties
+///
+/// The frame rate limit binding is followed by the window. The other window properties are not followed by the entity: they can be
+/// updated by sending requests to it.
 ///
 #[cfg(all(not(feature="render-wgpu"), not(feature="render-opengl")))]
-pub fn create_render_window_entity(context: &Arc<SceneContext>, entity_id: EntityId, initial_size: (u64, u64)) -> Result<SimpleEntityChannel<RenderWindowRequest>, CreateEntityError> {
+pub fn create_render_window_entity_with_properties(context: &Arc<SceneContext>, entity_id: EntityId, initial_properties: &WindowProperties) -> Result<SimpleEntityChannel<RenderWindowRequest>, CreateEntityError> {
     panic!("No default renderer was specified when flo_draw was compiled (use `render-wgpu` or `render-opengl`)")
 }
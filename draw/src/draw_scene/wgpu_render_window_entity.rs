@@ -1,5 +1,6 @@
 use crate::wgpu::*;
 use crate::window_properties::*;
+use crate::window_control::*;
 
 use futures::prelude::*;
 use futures::channel::mpsc;
@@ -14,7 +15,16 @@ use std::sync::*;
 ///
 /// Creates a render window in a scene with the specified entity ID
 ///
-pub fn create_wgpu_render_window_entity(context: &Arc<SceneContext>, entity_id: EntityId, initial_size: (u64, u64)) -> Result<SimpleEntityChannel<RenderWindowRequest>, CreateEntityError> {
+/// The initial size, clear colour and transparency of the window are read from the supplied properties, and the window follows
+/// the frame rate limit binding: the other properties are set by sending requests to the entity.
+///
+pub fn create_wgpu_render_window_entity(context: &Arc<SceneContext>, entity_id: EntityId, initial_properties: &WindowProperties) -> Result<SimpleEntityChannel<RenderWindowRequest>, CreateEntityError> {
+    let initial_size            = initial_properties.size.get();
+    let initial_clear_color     = initial_properties.initial_clear_color.get();
+    let transparent             = initial_properties.transparent.get();
+    let hdr                     = initial_properties.hdr.get();
+    let max_frame_rate          = initial_properties.max_frame_rate.clone();
+
     // This window can accept a couple of converted messages
     context.convert_message::<RenderRequest, RenderWindowRequest>()?;
     context.convert_message::<EventWindowRequest, RenderWindowRequest>()?;
@@ -26,27 +36,37 @@ pub fn create_wgpu_render_window_entity(context: &Arc<SceneContext>, entity_id:
         let fullscreen          = bind(false);
         let has_decorations     = bind(true);
         let mouse_pointer       = bind(MousePointer::SystemDefault);
+        let always_on_top       = bind(false);
+        let skip_taskbar        = bind(false);
         let size                = bind(initial_size);
 
         let window_properties   = WindowProperties { 
-            title:              BindRef::from(title.clone()), 
-            fullscreen:         BindRef::from(fullscreen.clone()), 
-            has_decorations:    BindRef::from(has_decorations.clone()), 
-            mouse_pointer:      BindRef::from(mouse_pointer.clone()), 
-            size:               BindRef::from(size.clone()),
+            title:                  BindRef::from(title.clone()), 
+            fullscreen:             BindRef::from(fullscreen.clone()), 
+            has_decorations:        BindRef::from(has_decorations.clone()), 
+            mouse_pointer:          BindRef::from(mouse_pointer.clone()), 
+            always_on_top:          BindRef::from(always_on_top.clone()), 
+            skip_taskbar:           BindRef::from(skip_taskbar.clone()), 
+            size:                   BindRef::from(size.clone()),
+            initial_clear_color:    BindRef::from(bind(initial_clear_color)),
+            transparent:            BindRef::from(bind(transparent)),
+            hdr:                    BindRef::from(bind(hdr)),
+            max_frame_rate:         max_frame_rate,
         };
         let mut event_publisher = Publisher::new(1000);
 
-        // Create a stream for publishing render requests
-        let (render_sender, render_receiver) = mpsc::channel(5);
+        // Create a stream for publishing render requests, and another for controlling the window
+        let (render_sender, render_receiver)    = mpsc::channel(5);
+        let (control_sender, control_receiver)  = mpsc::channel(5);
 
         // Create a window that subscribes to the publisher
         let winit_thread    = winit_thread();
-        winit_thread.send_event(WinitThreadEvent::CreateRenderWindow(render_receiver.boxed(), event_publisher.republish(), window_properties.into()));
+        winit_thread.send_event(WinitThreadEvent::CreateRenderWindow(render_receiver.boxed(), control_receiver.boxed(), event_publisher.republish(), window_properties.into()));
 
         // Run the main event loop
         let mut render_window_requests  = render_window_requests;
         let mut render_sender           = render_sender;
+        let mut control_sender          = control_sender;
 
         while let Some(request) = render_window_requests.next().await {
             let request: RenderWindowRequest = request;
@@ -95,6 +115,9 @@ pub fn create_wgpu_render_window_entity(context: &Arc<SceneContext>, entity_id:
                 RenderWindowRequest::SetFullScreen(new_fullscreen)      => { fullscreen.set(new_fullscreen); },
                 RenderWindowRequest::SetHasDecorations(new_decorations) => { has_decorations.set(new_decorations); },
                 RenderWindowRequest::SetMousePointer(new_mouse_pointer) => { mouse_pointer.set(new_mouse_pointer); },
+                RenderWindowRequest::SetAlwaysOnTop(new_always_on_top)  => { always_on_top.set(new_always_on_top); },
+                RenderWindowRequest::SetSkipTaskbar(new_skip_taskbar)   => { skip_taskbar.set(new_skip_taskbar); },
+                RenderWindowRequest::DragWindow                         => { control_sender.send(WindowControl::DragWindow).await.ok(); },
             }
         }
     })
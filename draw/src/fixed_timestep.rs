@@ -0,0 +1,118 @@
+use std::time::{Duration, Instant};
+
+/// The slowest rate a fixed timestep loop can run at, in steps per second
+const MIN_STEPS_PER_SECOND: f64 = 0.001;
+
+/// The fastest rate a fixed timestep loop can run at, in steps per second
+const MAX_STEPS_PER_SECOND: f64 = 1_000_000.0;
+
+///
+/// The result of advancing a fixed timestep loop to the time of a new frame
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct TimestepUpdate {
+    /// The number of fixed-length simulation steps that should be run before rendering this frame
+    pub steps: usize,
+
+    /// How far the frame is between the previous simulation state and the current one (0 = previous state, 1 = current state)
+    pub alpha: f64,
+}
+
+///
+/// Tracks the time for a simulation that updates at a fixed rate while being rendered at a different rate
+///
+/// Games usually want their simulation to run in fixed-length steps so that it behaves the same way regardless of how fast
+/// frames are being displayed. When a new frame is ready to be drawn (eg, when the window sends `DrawEvent::NewFrame`), call
+/// `advance_to()` to find out how many simulation steps to run, then render by interpolating between the state before the
+/// last step and the state after it using the `alpha` value that was returned. This adds a latency of up to one step, but
+/// keeps motion smooth when the display rate doesn't match the simulation rate.
+///
+/// The number of steps that can be run for a single frame is limited so that a slow frame (or a pause, such as when the
+/// window is being dragged) doesn't cause the simulation to fall further and further behind: time beyond the limit is
+/// discarded.
+///
+#[derive(Clone, Debug)]
+pub struct FixedTimestep {
+    /// The length of a single simulation step
+    step: Duration,
+
+    /// The maximum number of steps to run for a single frame
+    max_steps: usize,
+
+    /// Time that has passed but which has not yet been used up by a simulation step
+    accumulated: Duration,
+
+    /// The time passed to the last call to `advance_to()`
+    last_time: Option<Instant>,
+}
+
+impl FixedTimestep {
+    ///
+    /// Creates a new fixed timestep loop that runs the specified number of simulation steps per second
+    ///
+    /// The rate is clamped to between 0.001 and 1,000,000 steps per second, so a rate of 0 or less (or NaN) runs a step every
+    /// 1000 seconds.
+    ///
+    pub fn new(steps_per_second: f64) -> FixedTimestep {
+        let steps_per_second = if steps_per_second.is_nan() { MIN_STEPS_PER_SECOND } else { steps_per_second.clamp(MIN_STEPS_PER_SECOND, MAX_STEPS_PER_SECOND) };
+
+        FixedTimestep {
+            step:           Duration::from_secs_f64(1.0 / steps_per_second),
+            max_steps:      8,
+            accumulated:    Duration::from_secs(0),
+            last_time:      None,
+        }
+    }
+
+    ///
+    /// Sets the maximum number of simulation steps that can be run for a single frame (defaults to 8)
+    ///
+    pub fn with_max_steps(mut self, max_steps: usize) -> FixedTimestep {
+        self.max_steps = max_steps.max(1);
+
+        self
+    }
+
+    ///
+    /// The length of time that each simulation step represents
+    ///
+    pub fn step_duration(&self) -> Duration {
+        self.step
+    }
+
+    ///
+    /// Advances the loop to the time of a new frame, returning the number of steps to run and the interpolation position
+    ///
+    /// The first call starts the clock, so it returns no steps.
+    ///
+    pub fn advance_to(&mut self, now: Instant) -> TimestepUpdate {
+        let elapsed     = self.last_time.map(|last_time| now.saturating_duration_since(last_time)).unwrap_or(Duration::from_secs(0));
+        self.last_time  = Some(now);
+
+        self.advance(elapsed)
+    }
+
+    ///
+    /// Advances the loop by a specific amount of time, returning the number of steps to run and the interpolation position
+    ///
+    pub fn advance(&mut self, elapsed: Duration) -> TimestepUpdate {
+        self.accumulated += elapsed;
+
+        // Use up as much of the accumulated time as possible in whole steps
+        let mut steps = 0;
+        while self.accumulated >= self.step && steps < self.max_steps {
+            self.accumulated    -= self.step;
+            steps               += 1;
+        }
+
+        // Discard any time we couldn't catch up with
+        if self.accumulated >= self.step {
+            self.accumulated = Duration::from_secs(0);
+        }
+
+        TimestepUpdate {
+            steps:  steps,
+            alpha:  self.accumulated.as_secs_f64() / self.step.as_secs_f64(),
+        }
+    }
+}
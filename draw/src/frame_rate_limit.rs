@@ -0,0 +1,119 @@
+use flo_render::*;
+use flo_binding::*;
+
+use futures::prelude::*;
+use futures::task::{Poll, Context};
+use futures_timer::{Delay};
+
+use std::pin::*;
+use std::time::{Duration, Instant};
+
+/// The slowest frame rate that the stream can be limited to, in frames per second
+const MIN_FRAME_RATE: f64 = 0.001;
+
+///
+/// Stream that limits the rate at which frames are produced by a stream of render actions
+///
+pub struct FrameRateLimitedStream<TStream> {
+    /// The stream of render actions that is being limited
+    stream: TStream,
+
+    /// The maximum number of frames per second, or None if the frame rate is not limited
+    max_frame_rate: BindRef<Option<f64>>,
+
+    /// The time that the last frame was released
+    last_frame: Option<Instant>,
+
+    /// A frame that's waiting for the delay to expire before it's released
+    waiting_frame: Option<(Vec<RenderAction>, Delay)>,
+}
+
+///
+/// Limits the rate at which a stream of render actions will display frames
+///
+/// A frame is a batch of render actions that includes `ShowFrameBuffer`. Frames that arrive faster than the maximum frame rate
+/// are held back until enough time has passed since the previous frame: other batches are passed straight through unless a frame
+/// is waiting in front of them. This is independent of vsync, so it can be used with offscreen renderers as well as windows.
+///
+/// The frame rate is read from the binding for each frame, so it can be changed while the stream is running. A frame rate of
+/// `None` (or one that is not positive, or NaN) leaves the stream unlimited. Positive frame rates below 0.001 frames per second
+/// are clamped to that rate.
+///
+pub fn limit_frame_rate<TStream>(render_actions: TStream, max_frame_rate: BindRef<Option<f64>>) -> FrameRateLimitedStream<TStream>
+where
+    TStream: Unpin + Stream<Item=Vec<RenderAction>>,
+{
+    FrameRateLimitedStream {
+        stream:         render_actions,
+        max_frame_rate: max_frame_rate,
+        last_frame:     None,
+        waiting_frame:  None,
+    }
+}
+
+impl<TStream> FrameRateLimitedStream<TStream> {
+    ///
+    /// Returns the time that the next frame can be released, if it needs to be delayed
+    ///
+    fn next_frame_time(&self) -> Option<Instant> {
+        let max_frame_rate  = self.max_frame_rate.get()?;
+        let last_frame      = self.last_frame?;
+
+        if max_frame_rate > 0.0 {
+            // Very small rates would overflow the duration, so they're clamped
+            let max_frame_rate = max_frame_rate.max(MIN_FRAME_RATE);
+
+            Some(last_frame + Duration::from_secs_f64(1.0 / max_frame_rate))
+        } else {
+            None
+        }
+    }
+}
+
+impl<TStream> Stream for FrameRateLimitedStream<TStream>
+where
+    TStream: Unpin + Stream<Item=Vec<RenderAction>>,
+{
+    type Item = Vec<RenderAction>;
+
+    fn poll_next(mut self: Pin<&mut Self>, context: &mut Context<'_>) -> Poll<Option<Vec<RenderAction>>> {
+        // Release the frame that's waiting once its delay has expired
+        if let Some((_, delay)) = &mut self.waiting_frame {
+            if delay.poll_unpin(context).is_pending() {
+                return Poll::Pending;
+            }
+
+            let (frame, _)      = self.waiting_frame.take().unwrap();
+            self.last_frame     = Some(Instant::now());
+
+            return Poll::Ready(Some(frame));
+        }
+
+        match self.stream.poll_next_unpin(context) {
+            Poll::Ready(Some(actions)) => {
+                // Only batches that display a frame are limited
+                if !actions.iter().any(|action| action == &RenderAction::ShowFrameBuffer) {
+                    return Poll::Ready(Some(actions));
+                }
+
+                // Delay the frame if it's arriving too soon after the last one
+                let now = Instant::now();
+                if let Some(next_frame_time) = self.next_frame_time() {
+                    if next_frame_time > now {
+                        let mut delay = Delay::new(next_frame_time - now);
+
+                        if delay.poll_unpin(context).is_pending() {
+                            self.waiting_frame = Some((actions, delay));
+                            return Poll::Pending;
+                        }
+                    }
+                }
+
+                self.last_frame = Some(Instant::now());
+                Poll::Ready(Some(actions))
+            }
+
+            other => other
+        }
+    }
+}
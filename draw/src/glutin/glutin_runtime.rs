@@ -1,5 +1,9 @@
 use crate::events::*;
 use crate::window_properties::*;
+use crate::held_keys::*;
+use crate::deferred_leave::*;
+use crate::window_hints::*;
+use crate::monitor_info::*;
 
 use super::glutin_window::*;
 use super::glutin_thread::*;
@@ -47,6 +51,12 @@ pub (super) struct GlutinRuntime {
     /// The current state of each pointer (as a glutin device)
     pub (super) pointer_state: HashMap<DeviceId, PointerState>,
 
+    /// The keys that are currently held down in each window (used to detect key repeats)
+    pub (super) held_keys: HashMap<WindowId, HeldKeys>,
+
+    /// The 'leave' events being held back for each window while its pointers have a button held down
+    pub (super) deferred_leave: HashMap<WindowId, DeferredLeave>,
+
     /// Set to true when we'll set the control flow to 'Exit' once the current set of events have finished processing
     pub (super) will_exit: bool,
 
@@ -146,7 +156,11 @@ impl GlutinRuntime {
             HoveredFile(_path)                                              => vec![],
             HoveredFileCancelled                                            => vec![],
             ReceivedCharacter(_c)                                           => vec![],
-            Focused(_focused)                                               => vec![],
+            Focused(focused)                                                => {
+                // We won't see the key up events for any keys released while the window doesn't have focus
+                if !focused { if let Some(held_keys) = self.held_keys.get_mut(&window_id) { held_keys.clear(); } }
+                vec![]
+            },
             ModifiersChanged(_state)                                        => vec![],
             TouchpadPressure { device_id: _, pressure: _, stage: _ }        => vec![],
             TouchpadMagnify { device_id: _, delta: _, phase: _ }            => vec![],
@@ -169,8 +183,15 @@ impl GlutinRuntime {
 
                 // Generate the event for this keypress
                 match input.state {
-                    ElementState::Pressed   => vec![DrawEvent::KeyDown(input.scancode as _, key)],
-                    ElementState::Released  => vec![DrawEvent::KeyUp(input.scancode as _, key)]
+                    ElementState::Pressed   => {
+                        let is_repeat = self.held_keys.entry(window_id).or_insert_with(HeldKeys::new).press(input.scancode as _);
+                        vec![DrawEvent::KeyDown(input.scancode as _, key, is_repeat)]
+                    },
+
+                    ElementState::Released  => {
+                        if let Some(held_keys) = self.held_keys.get_mut(&window_id) { held_keys.release(input.scancode as _); }
+                        vec![DrawEvent::KeyUp(input.scancode as _, key)]
+                    }
                 }
             },
 
@@ -244,6 +265,11 @@ impl GlutinRuntime {
         };
 
         if let Some(window_events) = self.window_events.get_mut(&window_id) {
+            // Pointers that have a button pressed in this window don't leave it until they're released
+            let draw_events = self.deferred_leave.entry(window_id)
+                .or_insert_with(|| DeferredLeave::new())
+                .filter_events(draw_events);
+
             // Dispatch the draw events using a process
             if draw_events.len() > 0 {
                 // Need to republish the window events so we can share with the process
@@ -312,12 +338,15 @@ impl GlutinRuntime {
         use GlutinThreadEvent::*;
 
         match event {
-            CreateRenderWindow(actions, events, window_properties) => {
+            CreateRenderWindow(actions, controls, events, window_properties) => {
                 // Get the initial set of properties for the window
                 let title               = window_properties.title().get();
                 let (size_x, size_y)    = window_properties.size().get();
                 let fullscreen          = window_properties.fullscreen().get();
                 let decorations         = window_properties.has_decorations().get();
+                let always_on_top       = window_properties.always_on_top().get();
+                let skip_taskbar        = window_properties.skip_taskbar().get();
+                let transparent         = window_properties.transparent().get();
 
                 let fullscreen          = if fullscreen { Some(Fullscreen::Borderless(None)) } else { None };
 
@@ -326,16 +355,23 @@ impl GlutinRuntime {
                     .with_title(title)
                     .with_inner_size(winit::dpi::LogicalSize::new(size_x as f64, size_y as _))
                     .with_fullscreen(fullscreen)
-                    .with_decorations(decorations);
+                    .with_decorations(decorations)
+                    .with_transparent(transparent);
+                let window_builder      = with_window_hints(window_builder, always_on_top, skip_taskbar);
                 let display_builder     = DisplayBuilder::new()
                     .with_window_builder(Some(window_builder));
                 let template            = ConfigTemplateBuilder::new()
                     .prefer_hardware_accelerated(Some(true))
-                    .with_alpha_size(8);
+                    .with_alpha_size(8)
+                    .with_stencil_size(8)
+                    .with_transparency(transparent);
 
                 let (window, gl_config) = display_builder
                     .build(window_target, template, |configs| configs.reduce(|a, b| {
-                        if a.num_samples() > b.num_samples() {
+                        if transparent && a.supports_transparency() != b.supports_transparency() {
+                            // Transparent windows need a config that can be composited with the desktop, if one is available
+                            if a.supports_transparency() == Some(true) { a } else { b }
+                        } else if a.num_samples() > b.num_samples() {
                             a
                         } else {
                             b
@@ -397,15 +433,25 @@ impl GlutinRuntime {
                     let window_events = initial_events;
 
                     // Process the actions for the window
-                    send_actions_to_window(window, suspend_resume_subscriber, actions, window_events, window_properties).await;
+                    send_actions_to_window(window, suspend_resume_subscriber, actions, controls, window_events, window_properties).await;
 
                     // Stop processing events for the window once there are no more actions
                     glutin_thread().send_event(GlutinThreadEvent::StopSendingToWindow(window_id));
                 });
             }
 
+            QueryMonitors(send_monitors) => {
+                let monitors = window_target.available_monitors()
+                    .map(|monitor| MonitorInfo::from_winit(&monitor))
+                    .collect();
+
+                send_monitors.send(monitors).ok();
+            }
+
             StopSendingToWindow(window_id) => {
                 self.window_events.remove(&window_id);
+                self.deferred_leave.remove(&window_id);
+                self.held_keys.remove(&window_id);
 
                 if self.window_events.len() == 0 && self.will_stop_when_no_windows {
                     self.will_exit = true;
@@ -1,10 +1,14 @@
 use super::glutin_runtime::*;
 use super::glutin_thread_event::*;
 
+use crate::monitor_info::*;
+
 use ::desync::*;
 
 use winit::event_loop::{EventLoopBuilder, EventLoopProxy};
 use once_cell::sync::{Lazy};
+use futures::executor;
+use futures::channel::oneshot;
 
 use std::mem;
 use std::sync::*;
@@ -48,6 +52,20 @@ pub fn glutin_thread() -> Arc<GlutinThread> {
     })
 }
 
+///
+/// Returns information about the monitors that windows can be displayed on
+///
+/// This can be called before or after any windows are created (the glutin event thread is started if it's not already running).
+/// It waits for the event thread to respond, so it should not be called from a future running on that thread.
+///
+pub fn available_monitors() -> Vec<MonitorInfo> {
+    let (send_monitors, recv_monitors) = oneshot::channel();
+
+    glutin_thread().send_event(GlutinThreadEvent::QueryMonitors(send_monitors));
+
+    executor::block_on(recv_monitors).unwrap_or_else(|_| vec![])
+}
+
 struct StopGlutinWhenDropped;
 impl Drop for StopGlutinWhenDropped {
     fn drop(&mut self) {
@@ -143,6 +161,8 @@ fn run_glutin_thread(send_proxy: mpsc::Sender<EventLoopProxy<GlutinThreadEvent>>
         will_exit:                  false,
         pointer_id:                 HashMap::new(),
         pointer_state:              HashMap::new(),
+        held_keys:                  HashMap::new(),
+        deferred_leave:             HashMap::new(),
         suspended:                  true,
     };
 
@@ -1,11 +1,14 @@
 use crate::events::*;
 use crate::window_properties::*;
+use crate::window_control::*;
+use crate::monitor_info::*;
 
 use flo_stream::*;
 use flo_render::*;
 
 use futures::future::{LocalBoxFuture};
 use futures::stream::{BoxStream};
+use futures::channel::oneshot;
 
 use winit::window::{WindowId};
 
@@ -13,8 +16,8 @@ use winit::window::{WindowId};
 /// Event that can be sent to a glutin thread
 ///
 pub enum GlutinThreadEvent {
-    /// Creates a window that will render the specified actions
-    CreateRenderWindow(BoxStream<'static, Vec<RenderAction>>, Publisher<DrawEvent>, WindowProperties),
+    /// Creates a window that will render the specified actions and perform the specified controls
+    CreateRenderWindow(BoxStream<'static, Vec<RenderAction>>, BoxStream<'static, WindowControl>, Publisher<DrawEvent>, WindowProperties),
 
     /// Runs a future on the Glutin thread
     RunProcess(Box<dyn Send+FnOnce() -> LocalBoxFuture<'static, ()>>),
@@ -25,6 +28,9 @@ pub enum GlutinThreadEvent {
     /// Stop sending events for the specified window
     StopSendingToWindow(WindowId),
 
+    /// Sends the list of monitors that are available to the specified channel
+    QueryMonitors(oneshot::Sender<Vec<MonitorInfo>>),
+
     /// Tells the UI thread to stop when there are no more windows open
     StopWhenAllWindowsClosed
 }
@@ -1,5 +1,9 @@
 use crate::events::*;
 use crate::window_properties::*;
+use crate::initial_clear::*;
+use crate::window_hints::*;
+use crate::window_control::*;
+use crate::frame_rate_limit::*;
 
 use flo_stream::*;
 use flo_render::*;
@@ -72,26 +76,34 @@ where
 ///
 /// Sends render actions to a window
 ///0
-pub (super) async fn send_actions_to_window<RenderStream, SuspendResumeStream, DrawEventPublisher, TConfig, TSurfaceType>(window: GlutinWindow<TConfig>, suspend_resume: SuspendResumeStream, render_actions: RenderStream, events: DrawEventPublisher, window_properties: WindowProperties) 
+pub (super) async fn send_actions_to_window<RenderStream, ControlStream, SuspendResumeStream, DrawEventPublisher, TConfig, TSurfaceType>(window: GlutinWindow<TConfig>, suspend_resume: SuspendResumeStream, render_actions: RenderStream, window_controls: ControlStream, events: DrawEventPublisher, window_properties: WindowProperties) 
 where
     RenderStream:           Unpin + Stream<Item=Vec<RenderAction>>,
+    ControlStream:          Unpin + Stream<Item=WindowControl>,
     SuspendResumeStream:    Unpin + Stream<Item=SuspendResume>,
     DrawEventPublisher:     MessagePublisher<Message=DrawEvent>,
     TConfig:                GlConfig + GetGlDisplay,
     TConfig::Target:        GlDisplay<WindowSurface=Surface<TSurfaceType>, Config=TConfig>,
     TSurfaceType:           SurfaceTypeTrait,
 {
+    // Limit the frame rate, and clear the window to its background colour before anything else is rendered
+    let render_actions      = limit_frame_rate(render_actions, window_properties.max_frame_rate.clone());
+    let render_actions      = with_initial_clear(render_actions, window_properties.initial_clear_color.get());
+
     // Read events from the render actions list
     let mut window          = window;
     let mut events          = events;
     let mut window_actions  = WindowUpdateStream { 
         suspend_resume:     suspend_resume,
         render_stream:      render_actions, 
+        control_stream:     window_controls,
         title_stream:       follow(window_properties.title),
         size:               follow(window_properties.size),
         fullscreen:         follow(window_properties.fullscreen),
         has_decorations:    follow(window_properties.has_decorations),
-        mouse_pointer:      follow(window_properties.mouse_pointer)
+        mouse_pointer:      follow(window_properties.mouse_pointer),
+        always_on_top:      follow(window_properties.always_on_top),
+        skip_taskbar:       follow(window_properties.skip_taskbar),
     };
 
     while let Some(next_action) = window_actions.next().await {
@@ -201,6 +213,19 @@ where
             WindowUpdate::SetMousePointer(MousePointer::SystemDefault) => {
                 window.window.as_ref().map(|ctxt| ctxt.set_cursor_visible(true));
             }
+
+            WindowUpdate::SetAlwaysOnTop(always_on_top) => {
+                window.window.as_ref().map(|ctxt| ctxt.set_window_level(window_level(always_on_top)));
+            }
+
+            WindowUpdate::SetSkipTaskbar(skip_taskbar) => {
+                window.window.as_ref().map(|ctxt| set_skip_taskbar(ctxt, skip_taskbar));
+            }
+
+            WindowUpdate::Control(WindowControl::DragWindow) => {
+                // Fails if no mouse button is held down (or the platform doesn't support it)
+                window.window.as_ref().map(|ctxt| ctxt.drag_window().ok());
+            }
         }
     }
 
@@ -215,35 +240,44 @@ enum WindowUpdate {
     Resumed,
     Suspended,
     Render(Vec<RenderAction>),
+    Control(WindowControl),
     SetTitle(String),
     SetSize((u64, u64)),
     SetFullscreen(bool),
     SetHasDecorations(bool),
-    SetMousePointer(MousePointer)
+    SetMousePointer(MousePointer),
+    SetAlwaysOnTop(bool),
+    SetSkipTaskbar(bool),
 }
 
 ///
 /// Stream that merges the streams from the window properties and the renderer into a single stream
 ///
-struct WindowUpdateStream<TSuspendResumeStream, TRenderStream, TTitleStream, TSizeStream, TFullscreenStream, TDecorationStream, TMousePointerStream> {
+struct WindowUpdateStream<TSuspendResumeStream, TRenderStream, TControlStream, TTitleStream, TSizeStream, TFullscreenStream, TDecorationStream, TMousePointerStream, TAlwaysOnTopStream, TSkipTaskbarStream> {
     suspend_resume:     TSuspendResumeStream,
     render_stream:      TRenderStream,
+    control_stream:     TControlStream,
     title_stream:       TTitleStream,
     size:               TSizeStream,
     fullscreen:         TFullscreenStream,
     has_decorations:    TDecorationStream,
-    mouse_pointer:      TMousePointerStream
+    mouse_pointer:      TMousePointerStream,
+    always_on_top:      TAlwaysOnTopStream,
+    skip_taskbar:       TSkipTaskbarStream,
 }
 
-impl<TSuspendResumeStream, TRenderStream, TTitleStream, TSizeStream, TFullscreenStream, TDecorationStream, TMousePointerStream> Stream for WindowUpdateStream<TSuspendResumeStream, TRenderStream, TTitleStream, TSizeStream, TFullscreenStream, TDecorationStream, TMousePointerStream>
+impl<TSuspendResumeStream, TRenderStream, TControlStream, TTitleStream, TSizeStream, TFullscreenStream, TDecorationStream, TMousePointerStream, TAlwaysOnTopStream, TSkipTaskbarStream> Stream for WindowUpdateStream<TSuspendResumeStream, TRenderStream, TControlStream, TTitleStream, TSizeStream, TFullscreenStream, TDecorationStream, TMousePointerStream, TAlwaysOnTopStream, TSkipTaskbarStream>
 where
     TSuspendResumeStream:   Unpin + Stream<Item=SuspendResume>,
     TRenderStream:          Unpin + Stream<Item=Vec<RenderAction>>,
+    TControlStream:         Unpin + Stream<Item=WindowControl>,
     TTitleStream:           Unpin + Stream<Item=String>,
     TSizeStream:            Unpin + Stream<Item=(u64, u64)>,
     TFullscreenStream:      Unpin + Stream<Item=bool>,
     TDecorationStream:      Unpin + Stream<Item=bool>,
-    TMousePointerStream:    Unpin + Stream<Item=MousePointer> 
+    TMousePointerStream:    Unpin + Stream<Item=MousePointer>,
+    TAlwaysOnTopStream:     Unpin + Stream<Item=bool>,
+    TSkipTaskbarStream:     Unpin + Stream<Item=bool>,
 {
     type Item = WindowUpdate;
 
@@ -265,6 +299,13 @@ where
             Poll::Pending           => { }
         }
 
+        // Then requests to control the window
+        match self.control_stream.poll_next_unpin(context) {
+            Poll::Ready(Some(item)) => { return Poll::Ready(Some(WindowUpdate::Control(item))); }
+            Poll::Ready(None)       => { }
+            Poll::Pending           => { }
+        }
+
         // The various binding streams
         match self.title_stream.poll_next_unpin(context) {
             Poll::Ready(Some(item)) => { return Poll::Ready(Some(WindowUpdate::SetTitle(item))); }
@@ -296,7 +337,47 @@ where
             Poll::Pending           => { }
         }
 
+        match self.always_on_top.poll_next_unpin(context) {
+            Poll::Ready(Some(item)) => { return Poll::Ready(Some(WindowUpdate::SetAlwaysOnTop(item))); }
+            Poll::Ready(None)       => { return Poll::Ready(None); }
+            Poll::Pending           => { }
+        }
+
+        match self.skip_taskbar.poll_next_unpin(context) {
+            Poll::Ready(Some(item)) => { return Poll::Ready(Some(WindowUpdate::SetSkipTaskbar(item))); }
+            Poll::Ready(None)       => { return Poll::Ready(None); }
+            Poll::Pending           => { }
+        }
+
         // No stream matched anything
         Poll::Pending
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use futures::stream;
+    use futures::executor;
+
+    #[test]
+    fn drag_window_control_generates_update() {
+        let mut window_updates = WindowUpdateStream {
+            suspend_resume:     stream::pending::<SuspendResume>(),
+            render_stream:      stream::pending::<Vec<RenderAction>>(),
+            control_stream:     stream::iter(vec![WindowControl::DragWindow]),
+            title_stream:       stream::pending::<String>(),
+            size:               stream::pending::<(u64, u64)>(),
+            fullscreen:         stream::pending::<bool>(),
+            has_decorations:    stream::pending::<bool>(),
+            mouse_pointer:      stream::pending::<MousePointer>(),
+            always_on_top:      stream::pending::<bool>(),
+            skip_taskbar:       stream::pending::<bool>(),
+        };
+
+        let update = executor::block_on(window_updates.next());
+
+        assert!(matches!(update, Some(WindowUpdate::Control(WindowControl::DragWindow))), "{:?}", update);
+    }
+}
@@ -7,4 +7,4 @@ mod glutin_thread_event;
 pub (crate) use self::glutin_thread::*;
 pub (crate) use self::glutin_thread_event::*;
 
-pub use self::glutin_thread::{with_2d_graphics};
+pub use self::glutin_thread::{with_2d_graphics, available_monitors};
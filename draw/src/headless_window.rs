@@ -0,0 +1,136 @@
+use crate::events::*;
+use crate::draw_scene::*;
+use crate::deferred_leave::*;
+
+use flo_stream::*;
+use flo_canvas::*;
+use flo_render_canvas::*;
+
+use futures::prelude::*;
+
+///
+/// A window with no on-screen representation, which can be used to test interactive applications
+///
+/// Events can be injected into a headless window using `inject_event()`: these are processed in the same way as the
+/// events from a real window (so pointers don't leave the window while a button is held down, and have their canvas
+/// coordinates filled in from the most recent drawing) before being published to any subscribers.
+///
+pub struct HeadlessWindow {
+    /// The renderer for the canvas displayed in this window (used to map window coordinates to canvas coordinates)
+    renderer: CanvasRenderer,
+
+    /// Holds back the 'leave' events for pointers that have a button held down in this window
+    deferred_leave: DeferredLeave,
+
+    /// Publisher for the events injected into this window
+    event_publisher: Publisher<DrawEvent>,
+
+    /// The scale factor of the window
+    scale: f64,
+
+    /// The width of the window
+    width: f64,
+
+    /// The height of the window
+    height: f64,
+}
+
+impl HeadlessWindow {
+    ///
+    /// Creates a new headless window with the specified size
+    ///
+    pub fn new(width: f64, height: f64) -> HeadlessWindow {
+        let mut renderer = CanvasRenderer::new();
+        renderer.set_viewport(0.0..(width as f32), 0.0..(height as f32), width as f32, height as f32, 1.0);
+
+        HeadlessWindow {
+            renderer:           renderer,
+            deferred_leave:     DeferredLeave::new(),
+            event_publisher:    Publisher::new(1000),
+            scale:              1.0,
+            width:              width,
+            height:             height,
+        }
+    }
+
+    ///
+    /// Returns a stream of the events injected into this window from now on
+    ///
+    pub fn subscribe(&mut self) -> impl Send + Unpin + Stream<Item=DrawEvent> {
+        self.event_publisher.subscribe()
+    }
+
+    ///
+    /// Sends some drawing instructions to the canvas displayed in this window, returning the render actions that they generate
+    ///
+    /// This blocks the calling thread until the drawing has been tessellated, so it must not be called from async code (where
+    /// it would stall the executor, and can deadlock a single-threaded one): use `draw_async()` there instead.
+    ///
+    pub fn draw<'a, DrawIter: IntoIterator<Item=&'a Draw>>(&mut self, drawing: DrawIter) -> Vec<RenderAction> {
+        self.renderer.render_actions_for(drawing)
+    }
+
+    ///
+    /// Sends some drawing instructions to the canvas displayed in this window, returning the render actions that they generate
+    ///
+    pub async fn draw_async<'a, DrawIter: IntoIterator<Item=&'a Draw>>(&mut self, drawing: DrawIter) -> Vec<RenderAction> {
+        self.renderer.render_actions_for_async(drawing).await
+    }
+
+    ///
+    /// Injects an event into this window, as if it had been generated by the windowing system
+    ///
+    pub async fn inject_event(&mut self, event: DrawEvent) {
+        self.inject_events(vec![event]).await
+    }
+
+    ///
+    /// Injects a set of events into this window, as if they had been generated by the windowing system
+    ///
+    pub async fn inject_events(&mut self, events: Vec<DrawEvent>) {
+        for event in self.deferred_leave.filter_events(events) {
+            let event = match event {
+                DrawEvent::Resize(width, height) => {
+                    self.width  = width;
+                    self.height = height;
+                    self.update_viewport();
+
+                    event
+                }
+
+                DrawEvent::Scale(scale) => {
+                    self.scale = scale;
+                    self.update_viewport();
+
+                    event
+                }
+
+                DrawEvent::Pointer(action, pointer_id, pointer_state) => {
+                    // Fill in the canvas location in the same way as the drawing window does
+                    let mut pointer_state               = pointer_state;
+                    let window_transform                = window_to_canvas_transform(&self.renderer, self.height);
+                    let (x, y)                          = pointer_state.location_in_window;
+                    let (cx, cy)                        = window_transform.transform_point(x as _, y as _);
+                    pointer_state.location_in_canvas    = Some((cx as _, cy as _));
+
+                    DrawEvent::Pointer(action, pointer_id, pointer_state)
+                }
+
+                other_event => other_event
+            };
+
+            self.event_publisher.publish(event).await;
+        }
+    }
+
+    ///
+    /// Updates the viewport of the renderer after the window size or scale has changed
+    ///
+    fn update_viewport(&mut self) {
+        let width   = self.width as f32;
+        let height  = self.height as f32;
+        let scale   = self.scale as f32;
+
+        self.renderer.set_viewport(0.0..width, 0.0..height, width, height, scale);
+    }
+}
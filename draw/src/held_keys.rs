@@ -0,0 +1,83 @@
+use std::collections::{HashSet};
+
+///
+/// Tracks which keys are currently held down, so that repeated key presses generated by the OS can be identified
+///
+/// Each window has its own set of held keys, so a key held down in one window isn't reported as a repeat in another.
+///
+pub (crate) struct HeldKeys {
+    /// The scancodes of the keys that are currently pressed
+    scancodes: HashSet<u64>,
+}
+
+impl HeldKeys {
+    ///
+    /// Creates a new key tracker with no keys held down
+    ///
+    pub fn new() -> HeldKeys {
+        HeldKeys {
+            scancodes: HashSet::new()
+        }
+    }
+
+    ///
+    /// Records that a key has been pressed, returning true if the key was already held down (ie, this press is a repeat)
+    ///
+    pub fn press(&mut self, scancode: u64) -> bool {
+        !self.scancodes.insert(scancode)
+    }
+
+    ///
+    /// Records that a key has been released
+    ///
+    pub fn release(&mut self, scancode: u64) {
+        self.scancodes.remove(&scancode);
+    }
+
+    ///
+    /// Forgets all of the keys that are held down (eg, when focus is lost and we'll stop receiving key release events)
+    ///
+    pub fn clear(&mut self) {
+        self.scancodes.clear();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn second_press_is_repeat() {
+        let mut held_keys = HeldKeys::new();
+
+        assert!(held_keys.press(42) == false);
+        assert!(held_keys.press(42) == true);
+        assert!(held_keys.press(42) == true);
+    }
+
+    #[test]
+    fn press_after_release_is_not_repeat() {
+        let mut held_keys = HeldKeys::new();
+
+        assert!(held_keys.press(42) == false);
+        held_keys.release(42);
+        assert!(held_keys.press(42) == false);
+    }
+
+    #[test]
+    fn other_keys_are_not_repeats() {
+        let mut held_keys = HeldKeys::new();
+
+        assert!(held_keys.press(42) == false);
+        assert!(held_keys.press(43) == false);
+    }
+
+    #[test]
+    fn press_after_clear_is_not_repeat() {
+        let mut held_keys = HeldKeys::new();
+
+        assert!(held_keys.press(42) == false);
+        held_keys.clear();
+        assert!(held_keys.press(42) == false);
+    }
+}
@@ -0,0 +1,58 @@
+use flo_canvas::{Color};
+use flo_render::*;
+
+use futures::prelude::*;
+use futures::stream;
+use futures::future;
+
+///
+/// Returns the render actions that clear a newly created window to its initial background colour
+///
+pub (crate) fn initial_clear_actions(clear_color: Color) -> Vec<RenderAction> {
+    let (r, g, b, a)    = clear_color.to_rgba_components();
+    let to_u8           = |component: f32| (component.max(0.0).min(1.0) * 255.0).round() as u8;
+
+    vec![
+        RenderAction::RenderToFrameBuffer,
+        RenderAction::Clear(Rgba8([to_u8(r), to_u8(g), to_u8(b), to_u8(a)])),
+        RenderAction::ShowFrameBuffer,
+    ]
+}
+
+///
+/// Adds the actions to clear a window to its initial background colour to the start of the stream of render actions for that window
+///
+/// This ensures that the window shows the intended background colour from the first time it's painted, instead of the platform's
+/// default colour, which would otherwise be visible until the first frame has been rendered.
+///
+pub (crate) fn with_initial_clear<RenderStream>(render_actions: RenderStream, clear_color: Color) -> impl Unpin + Stream<Item=Vec<RenderAction>>
+where
+    RenderStream: Unpin + Stream<Item=Vec<RenderAction>>,
+{
+    stream::once(future::ready(initial_clear_actions(clear_color)))
+        .chain(render_actions)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use futures::executor;
+
+    #[test]
+    fn clear_before_first_render() {
+        let render_actions  = stream::iter(vec![vec![RenderAction::SelectRenderTarget(RenderTargetId(0))]]);
+        let render_actions  = with_initial_clear(render_actions, Color::Rgba(1.0, 0.5, 0.0, 1.0));
+
+        let actions         = executor::block_on(render_actions.collect::<Vec<_>>());
+
+        // The initial clear should be displayed before any of the other actions are performed
+        assert!(actions.len() == 2);
+        assert!(actions[0] == vec![
+            RenderAction::RenderToFrameBuffer,
+            RenderAction::Clear(Rgba8([255, 128, 0, 255])),
+            RenderAction::ShowFrameBuffer,
+        ]);
+        assert!(actions[1] == vec![RenderAction::SelectRenderTarget(RenderTargetId(0))]);
+    }
+}
@@ -63,25 +63,38 @@ pub use flo_binding as binding;
 pub use flo_scene as scene;
 
 pub use flo_render::{initialize_offscreen_rendering};
-pub use flo_render_canvas::{render_canvas_offscreen};
+pub use flo_render_canvas::{render_canvas_offscreen, try_render_canvas_offscreen, render_canvas_offscreen_at_scale, OffscreenRenderer};
 
 mod render_window;
 mod drawing_window;
 mod window_properties;
+mod window_control;
+mod draw_event_multiplexer;
+mod held_keys;
+mod deferred_leave;
+mod initial_clear;
+mod headless_window;
+mod monitor_info;
+mod frame_rate_limit;
+mod fixed_timestep;
+mod canvas_viewport;
+
+#[cfg(any(feature="render-opengl", feature="render-wgpu"))]
+mod window_hints;
 
 /// The 'glutin' module provides an OpenGL implementation of the canvas using glutin for window management
 #[cfg(feature="render-opengl")]
 pub mod glutin;
 
 #[cfg(all(feature="render-opengl", not(feature="render-wgpu")))]
-pub use self::glutin::{with_2d_graphics};
+pub use self::glutin::{with_2d_graphics, available_monitors};
 
 /// The 'wgpu' module provides a winit-based wgpu implementation of a renderer
 #[cfg(feature="render-wgpu")]
 pub mod wgpu;
 
 #[cfg(all(feature="render-wgpu"))]
-pub use self::wgpu::{with_2d_graphics};
+pub use self::wgpu::{with_2d_graphics, available_monitors};
 
 /// The 'Scene' API provides a framework for building more complex software out of message-passing components
 pub mod draw_scene;
@@ -90,3 +103,9 @@ pub use self::events::*;
 pub use self::render_window::*;
 pub use self::drawing_window::*;
 pub use self::window_properties::*;
+pub use self::draw_event_multiplexer::*;
+pub use self::headless_window::*;
+pub use self::monitor_info::*;
+pub use self::frame_rate_limit::*;
+pub use self::fixed_timestep::*;
+pub use self::canvas_viewport::*;
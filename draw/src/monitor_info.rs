@@ -0,0 +1,44 @@
+#[cfg(any(feature="render-opengl", feature="render-wgpu"))]
+use winit::monitor::{MonitorHandle};
+
+///
+/// Describes a monitor that windows can be displayed on
+///
+/// Sizes and positions are in physical pixels: divide by the scale factor to get the logical size used when creating windows.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct MonitorInfo {
+    /// The name of the monitor, if the platform provides one
+    pub name: Option<String>,
+
+    /// The size of the monitor in physical pixels
+    pub size: (u32, u32),
+
+    /// The position of the top-left corner of the monitor on the desktop, in physical pixels
+    pub position: (i32, i32),
+
+    /// The ratio of physical pixels to logical pixels for this monitor
+    pub scale_factor: f64,
+
+    /// The refresh rate of the monitor in Hz, if known
+    pub refresh_rate: Option<f64>,
+}
+
+#[cfg(any(feature="render-opengl", feature="render-wgpu"))]
+impl MonitorInfo {
+    ///
+    /// Reads the information about a monitor from winit
+    ///
+    pub (crate) fn from_winit(monitor: &MonitorHandle) -> MonitorInfo {
+        let size        = monitor.size();
+        let position    = monitor.position();
+
+        MonitorInfo {
+            name:           monitor.name(),
+            size:           (size.width, size.height),
+            position:       (position.x, position.y),
+            scale_factor:   monitor.scale_factor(),
+            refresh_rate:   monitor.refresh_rate_millihertz().map(|millihertz| (millihertz as f64) / 1000.0),
+        }
+    }
+}
@@ -40,18 +40,24 @@ where
         let fullscreen      = follow(window_properties.fullscreen);
         let has_decorations = follow(window_properties.has_decorations);
         let mouse_pointer   = follow(window_properties.mouse_pointer);
+        let always_on_top   = follow(window_properties.always_on_top);
+        let skip_taskbar    = follow(window_properties.skip_taskbar);
 
         // Each one generates an event when it changes
         let title           = title.map(|new_title| EventWindowRequest::SetTitle(new_title));
         let fullscreen      = fullscreen.map(|fullscreen| EventWindowRequest::SetFullScreen(fullscreen));
         let has_decorations = has_decorations.map(|has_decorations| EventWindowRequest::SetHasDecorations(has_decorations));
         let mouse_pointer   = mouse_pointer.map(|mouse_pointer| EventWindowRequest::SetMousePointer(mouse_pointer));
+        let always_on_top   = always_on_top.map(|always_on_top| EventWindowRequest::SetAlwaysOnTop(always_on_top));
+        let skip_taskbar    = skip_taskbar.map(|skip_taskbar| EventWindowRequest::SetSkipTaskbar(skip_taskbar));
 
         let mut requests    = stream::select_all(vec![
             title.boxed(),
             fullscreen.boxed(),
             has_decorations.boxed(),
             mouse_pointer.boxed(),
+            always_on_top.boxed(),
+            skip_taskbar.boxed(),
         ]);
 
         // Pass the requests on to the underlying window
@@ -78,7 +84,7 @@ where
     let render_window_entity    = EntityId::new();
     let scene_context           = flo_draw_scene_context();
 
-    let render_channel          = create_render_window_entity(&scene_context, render_window_entity, properties.size().get()).unwrap();
+    let render_channel          = create_render_window_entity_with_properties(&scene_context, render_window_entity, &properties).unwrap();
 
     // The events send to a channel
     let (events_channel, events_stream) = SimpleEntityChannel::new(render_window_entity, 5);
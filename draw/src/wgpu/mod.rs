@@ -7,4 +7,4 @@ mod winit_thread_event;
 pub (crate) use self::winit_thread::*;
 pub (crate) use self::winit_thread_event::*;
 
-pub use self::winit_thread::{with_2d_graphics};
+pub use self::winit_thread::{with_2d_graphics, available_monitors};
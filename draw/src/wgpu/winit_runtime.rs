@@ -1,5 +1,9 @@
 use crate::events::*;
 use crate::window_properties::*;
+use crate::held_keys::*;
+use crate::deferred_leave::*;
+use crate::window_hints::*;
+use crate::monitor_info::*;
 
 use super::winit_window::*;
 use super::winit_thread::*;
@@ -54,6 +58,12 @@ pub (super) struct WinitRuntime {
     /// The current state of each pointer (as a winit device)
     pub (super) pointer_state: HashMap<DeviceId, PointerState>,
 
+    /// The keys that are currently held down in each window (used to detect key repeats)
+    pub (super) held_keys: HashMap<WindowId, HeldKeys>,
+
+    /// The 'leave' events being held back for each window while its pointers have a button held down
+    pub (super) deferred_leave: HashMap<WindowId, DeferredLeave>,
+
     /// Set to true when we'll set the control flow to 'Exit' once the current set of events have finished processing
     pub (super) will_exit: bool
 }
@@ -173,7 +183,11 @@ impl WinitRuntime {
             HoveredFile(_path)                                              => vec![],
             HoveredFileCancelled                                            => vec![],
             ReceivedCharacter(_c)                                           => vec![],
-            Focused(_focused)                                               => vec![],
+            Focused(focused)                                                => {
+                // We won't see the key up events for any keys released while the window doesn't have focus
+                if !focused { if let Some(held_keys) = self.held_keys.get_mut(&window_id) { held_keys.clear(); } }
+                vec![]
+            },
             ModifiersChanged(_state)                                        => vec![],
             TouchpadPressure { device_id: _, pressure: _, stage: _ }        => vec![],
             TouchpadMagnify { .. }                                          => vec![],
@@ -194,8 +208,15 @@ impl WinitRuntime {
 
                 // Generate the event for this keypress
                 match input.state {
-                    ElementState::Pressed   => vec![DrawEvent::KeyDown(input.scancode as _, key)],
-                    ElementState::Released  => vec![DrawEvent::KeyUp(input.scancode as _, key)]
+                    ElementState::Pressed   => {
+                        let is_repeat = self.held_keys.entry(window_id).or_insert_with(HeldKeys::new).press(input.scancode as _);
+                        vec![DrawEvent::KeyDown(input.scancode as _, key, is_repeat)]
+                    },
+
+                    ElementState::Released  => {
+                        if let Some(held_keys) = self.held_keys.get_mut(&window_id) { held_keys.release(input.scancode as _); }
+                        vec![DrawEvent::KeyUp(input.scancode as _, key)]
+                    }
                 }
             },
 
@@ -270,6 +291,11 @@ impl WinitRuntime {
         };
 
         if let Some(window_data) = self.window_events.get_mut(&window_id) {
+            // Pointers that have a button pressed in this window don't leave it until they're released
+            let draw_events = self.deferred_leave.entry(window_id)
+                .or_insert_with(|| DeferredLeave::new())
+                .filter_events(draw_events);
+
             // Dispatch the draw events using a process
             if draw_events.len() > 0 {
                 // Need to republish the window events so we can share with the process
@@ -305,12 +331,15 @@ impl WinitRuntime {
         use WinitThreadEvent::*;
 
         match event {
-            CreateRenderWindow(actions, events, window_properties) => {
+            CreateRenderWindow(actions, controls, events, window_properties) => {
                 // Get the initial set of properties for the window
                 let title               = window_properties.title().get();
                 let (size_x, size_y)    = window_properties.size().get();
                 let fullscreen          = window_properties.fullscreen().get();
                 let decorations         = window_properties.has_decorations().get();
+                let always_on_top       = window_properties.always_on_top().get();
+                let skip_taskbar        = window_properties.skip_taskbar().get();
+                let transparent         = window_properties.transparent().get();
 
                 let fullscreen          = if fullscreen { Some(Fullscreen::Borderless(None)) } else { None };
 
@@ -319,7 +348,9 @@ impl WinitRuntime {
                     .with_title(title)
                     .with_inner_size(winit::dpi::LogicalSize::new(size_x as f64, size_y as _))
                     .with_fullscreen(fullscreen)
-                    .with_decorations(decorations);
+                    .with_decorations(decorations)
+                    .with_transparent(transparent);
+                let window_builder      = with_window_hints(window_builder, always_on_top, skip_taskbar);
                 let window              = window_builder.build(window_target).expect("New window");
 
                 // Build a new Winit window
@@ -347,15 +378,25 @@ impl WinitRuntime {
                     let window_events = initial_events;
 
                     // Process the actions for the window
-                    send_actions_to_window(window, actions, window_events, window_properties).await;
+                    send_actions_to_window(window, actions, controls, window_events, window_properties).await;
 
                     // Stop processing events for the window once there are no more actions
                     winit_thread().send_event(WinitThreadEvent::StopSendingToWindow(window_id));
                 });
             }
 
+            QueryMonitors(send_monitors) => {
+                let monitors = window_target.available_monitors()
+                    .map(|monitor| MonitorInfo::from_winit(&monitor))
+                    .collect();
+
+                send_monitors.send(monitors).ok();
+            }
+
             StopSendingToWindow(window_id) => {
                 self.window_events.remove(&window_id);
+                self.deferred_leave.remove(&window_id);
+                self.held_keys.remove(&window_id);
                 self.pending_redraws.remove(&window_id);
 
                 if self.window_events.len() == 0 && self.will_stop_when_no_windows {
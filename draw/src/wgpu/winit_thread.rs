@@ -1,10 +1,14 @@
 use super::winit_runtime::*;
 use super::winit_thread_event::*;
 
+use crate::monitor_info::*;
+
 use ::desync::*;
 
 use winit::event_loop::{EventLoop, EventLoopProxy};
 use once_cell::sync::{Lazy};
+use futures::executor;
+use futures::channel::oneshot;
 
 use std::mem;
 use std::sync::*;
@@ -48,6 +52,20 @@ pub fn winit_thread() -> Arc<WinitThread> {
     })
 }
 
+///
+/// Returns information about the monitors that windows can be displayed on
+///
+/// This can be called before or after any windows are created (the winit event thread is started if it's not already running).
+/// It waits for the event thread to respond, so it should not be called from a future running on that thread.
+///
+pub fn available_monitors() -> Vec<MonitorInfo> {
+    let (send_monitors, recv_monitors) = oneshot::channel();
+
+    winit_thread().send_event(WinitThreadEvent::QueryMonitors(send_monitors));
+
+    executor::block_on(recv_monitors).unwrap_or_else(|_| vec![])
+}
+
 struct StopWinitWhenDropped;
 impl Drop for StopWinitWhenDropped {
     fn drop(&mut self) {
@@ -145,6 +163,8 @@ fn run_winit_thread(send_proxy: mpsc::Sender<EventLoopProxy<WinitThreadEvent>>)
         will_exit:                  false,
         pointer_id:                 HashMap::new(),
         pointer_state:              HashMap::new(),
+        held_keys:                  HashMap::new(),
+        deferred_leave:             HashMap::new(),
     };
 
     // Run the winit event loop
@@ -1,5 +1,7 @@
 use crate::events::*;
 use crate::window_properties::*;
+use crate::window_control::*;
+use crate::monitor_info::*;
 
 use flo_stream::*;
 use flo_render::*;
@@ -18,8 +20,8 @@ use std::fmt::*;
 /// Event that can be sent to a winit thread
 ///
 pub enum WinitThreadEvent {
-    /// Creates a window that will render the specified actions
-    CreateRenderWindow(BoxStream<'static, Vec<RenderAction>>, Publisher<DrawEvent>, WindowProperties),
+    /// Creates a window that will render the specified actions and perform the specified controls
+    CreateRenderWindow(BoxStream<'static, Vec<RenderAction>>, BoxStream<'static, WindowControl>, Publisher<DrawEvent>, WindowProperties),
 
     /// Runs a future on the winit thread
     RunProcess(Box<dyn Send+FnOnce() -> LocalBoxFuture<'static, ()>>),
@@ -36,6 +38,9 @@ pub enum WinitThreadEvent {
     /// Stop sending events for the specified window
     StopSendingToWindow(WindowId),
 
+    /// Sends the list of monitors that are available to the specified channel
+    QueryMonitors(oneshot::Sender<Vec<MonitorInfo>>),
+
     /// Tells the UI thread to stop when there are no more windows open
     StopWhenAllWindowsClosed,
 }
@@ -45,12 +50,13 @@ impl Debug for WinitThreadEvent {
         use self::WinitThreadEvent::*;
 
         match self {
-            CreateRenderWindow(_, _, _)     => write!(f, "CreateRenderWindow(...)"),
+            CreateRenderWindow(_, _, _, _)  => write!(f, "CreateRenderWindow(...)"),
             RunProcess(_)                   => write!(f, "RunProcess(...)"),
             WakeFuture(id)                  => write!(f, "WakeFuture({})", id),
             PresentSurface(id, _, _)        => write!(f, "PresentSurface({:?}, ...)", id),
             Yield(_)                        => write!(f, "Yield(...)"),
             StopSendingToWindow(id)         => write!(f, "StopSendingToWindow({:?})", id),
+            QueryMonitors(_)                => write!(f, "QueryMonitors(...)"),
             StopWhenAllWindowsClosed        => write!(f, "StopWhenAllWindowsClosed"),
         }
     }
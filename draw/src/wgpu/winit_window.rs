@@ -3,6 +3,10 @@ use super::winit_thread_event::*;
 
 use crate::events::*;
 use crate::window_properties::*;
+use crate::initial_clear::*;
+use crate::window_hints::*;
+use crate::window_control::*;
+use crate::frame_rate_limit::*;
 
 use flo_stream::*;
 use flo_render::*;
@@ -63,21 +67,31 @@ impl WinitWindow {
 /// Render actions are sent via the stream in `render_actions`. When a new frame is presented, a `DrawEvent::NewFrame` is sent to the event publisher. Finally the window 
 /// properties are watched and used to update the window properties.
 ///
-pub (super) async fn send_actions_to_window<RenderStream, EventPublisher>(window: WinitWindow, render_actions: RenderStream, events: EventPublisher, window_properties: WindowProperties)
+pub (super) async fn send_actions_to_window<RenderStream, ControlStream, EventPublisher>(window: WinitWindow, render_actions: RenderStream, window_controls: ControlStream, events: EventPublisher, window_properties: WindowProperties)
 where
     RenderStream:   Unpin + Stream<Item=Vec<RenderAction>>,
+    ControlStream:  Unpin + Stream<Item=WindowControl>,
     EventPublisher: MessagePublisher<Message=DrawEvent>,
 {
+    // Limit the frame rate, and clear the window to its background colour before anything else is rendered
+    let render_actions      = limit_frame_rate(render_actions, window_properties.max_frame_rate.clone());
+    let render_actions      = with_initial_clear(render_actions, window_properties.initial_clear_color.get());
+    let transparent         = window_properties.transparent.get();
+    let hdr                 = window_properties.hdr.get();
+
     // Read events from the render actions list
     let mut window          = window;
     let mut events          = events;
     let window_actions      = WindowUpdateStream { 
         render_stream:      render_actions, 
+        control_stream:     window_controls,
         title_stream:       follow(window_properties.title),
         size:               follow(window_properties.size),
         fullscreen:         follow(window_properties.fullscreen),
         has_decorations:    follow(window_properties.has_decorations),
-        mouse_pointer:      follow(window_properties.mouse_pointer)
+        mouse_pointer:      follow(window_properties.mouse_pointer),
+        always_on_top:      follow(window_properties.always_on_top),
+        skip_taskbar:       follow(window_properties.skip_taskbar),
     };
     let mut window_actions  = window_actions.ready_chunks(100);
 
@@ -107,8 +121,8 @@ where
                             compatible_surface:     Some(&surface),
                         }).await.expect("Could not acquire an adapter for winit/wgpu");
 
-                        // Fetch the device and the queue
-                        let features        = wgpu::Features::empty();
+                        // Fetch the device and the queue (enabling whichever texture compression formats the adapter supports)
+                        let features        = adapter.features() & (wgpu::Features::TEXTURE_COMPRESSION_BC | wgpu::Features::TEXTURE_COMPRESSION_ETC2 | wgpu::Features::TEXTURE_COMPRESSION_ASTC);
                         #[cfg(feature="wgpu-profiler")] let features = features | GpuProfiler::ALL_WGPU_TIMER_FEATURES;
                         let (device, queue) = adapter.request_device(&wgpu::DeviceDescriptor {
                             label:      None,
@@ -121,7 +135,9 @@ where
                         let queue           = Arc::new(queue);
                         let surface         = Arc::new(surface);
                         let adapter         = Arc::new(adapter);
-                        let renderer        = WgpuRenderer::from_surface(Arc::clone(&device), Arc::clone(&queue), Arc::clone(&surface), Arc::clone(&adapter));
+                        let mut renderer    = WgpuRenderer::from_surface(Arc::clone(&device), Arc::clone(&queue), Arc::clone(&surface), Arc::clone(&adapter));
+                        renderer.set_transparent(transparent);
+                        renderer.set_hdr(hdr);
 
                         window.device       = Some(device);
                         window.instance     = Some(instance);
@@ -201,6 +217,25 @@ where
                         winit_window.set_cursor_visible(true);
                     }
                 }
+
+                WindowUpdate::SetAlwaysOnTop(always_on_top) => {
+                    if let Some(winit_window) = &window.window {
+                        winit_window.set_window_level(window_level(always_on_top));
+                    }
+                }
+
+                WindowUpdate::SetSkipTaskbar(skip_taskbar) => {
+                    if let Some(winit_window) = &window.window {
+                        set_skip_taskbar(winit_window, skip_taskbar);
+                    }
+                }
+
+                WindowUpdate::Control(WindowControl::DragWindow) => {
+                    if let Some(winit_window) = &window.window {
+                        // Fails if no mouse button is held down (or the platform doesn't support it)
+                        winit_window.drag_window().ok();
+                    }
+                }
             }
         }
 
@@ -220,11 +255,14 @@ where
 ///
 enum WindowUpdate {
     Render(Vec<RenderAction>),
+    Control(WindowControl),
     SetTitle(String),
     SetSize((u64, u64)),
     SetFullscreen(bool),
     SetHasDecorations(bool),
-    SetMousePointer(MousePointer)
+    SetMousePointer(MousePointer),
+    SetAlwaysOnTop(bool),
+    SetSkipTaskbar(bool),
 }
 
 impl fmt::Debug for WindowUpdate {
@@ -233,11 +271,14 @@ impl fmt::Debug for WindowUpdate {
 
         match self {
             Render(actions)             => write!(f, "Render({} actions)", actions.len()),
+            Control(control)            => write!(f, "Control({:?})", control),
             SetTitle(title)             => write!(f, "SetTitle({})", title),
             SetSize(sz)                 => write!(f, "SetSize({:?})", sz),
             SetFullscreen(val)          => write!(f, "SetFullscreen({:?})", val),
             SetHasDecorations(val)      => write!(f, "SetHasDecorations({:?})", val),
             SetMousePointer(ptr)        => write!(f, "SetMousePointer({:?})", ptr),
+            SetAlwaysOnTop(val)         => write!(f, "SetAlwaysOnTop({:?})", val),
+            SetSkipTaskbar(val)         => write!(f, "SetSkipTaskbar({:?})", val),
         }
     }
 }
@@ -245,23 +286,29 @@ impl fmt::Debug for WindowUpdate {
 ///
 /// Stream that merges the streams from the window properties and the renderer into a single stream
 ///
-struct WindowUpdateStream<TRenderStream, TTitleStream, TSizeStream, TFullscreenStream, TDecorationStream, TMousePointerStream> {
+struct WindowUpdateStream<TRenderStream, TControlStream, TTitleStream, TSizeStream, TFullscreenStream, TDecorationStream, TMousePointerStream, TAlwaysOnTopStream, TSkipTaskbarStream> {
     render_stream:      TRenderStream,
+    control_stream:     TControlStream,
     title_stream:       TTitleStream,
     size:               TSizeStream,
     fullscreen:         TFullscreenStream,
     has_decorations:    TDecorationStream,
-    mouse_pointer:      TMousePointerStream
+    mouse_pointer:      TMousePointerStream,
+    always_on_top:      TAlwaysOnTopStream,
+    skip_taskbar:       TSkipTaskbarStream,
 }
 
-impl<TRenderStream, TTitleStream, TSizeStream, TFullscreenStream, TDecorationStream, TMousePointerStream> Stream for WindowUpdateStream<TRenderStream, TTitleStream, TSizeStream, TFullscreenStream, TDecorationStream, TMousePointerStream>
+impl<TRenderStream, TControlStream, TTitleStream, TSizeStream, TFullscreenStream, TDecorationStream, TMousePointerStream, TAlwaysOnTopStream, TSkipTaskbarStream> Stream for WindowUpdateStream<TRenderStream, TControlStream, TTitleStream, TSizeStream, TFullscreenStream, TDecorationStream, TMousePointerStream, TAlwaysOnTopStream, TSkipTaskbarStream>
 where
     TRenderStream:          Unpin + Stream<Item=Vec<RenderAction>>,
+    TControlStream:         Unpin + Stream<Item=WindowControl>,
     TTitleStream:           Unpin + Stream<Item=String>,
     TSizeStream:            Unpin + Stream<Item=(u64, u64)>,
     TFullscreenStream:      Unpin + Stream<Item=bool>,
     TDecorationStream:      Unpin + Stream<Item=bool>,
-    TMousePointerStream:    Unpin + Stream<Item=MousePointer> 
+    TMousePointerStream:    Unpin + Stream<Item=MousePointer>,
+    TAlwaysOnTopStream:     Unpin + Stream<Item=bool>,
+    TSkipTaskbarStream:     Unpin + Stream<Item=bool>,
 {
     type Item = WindowUpdate;
 
@@ -275,6 +322,13 @@ where
             Poll::Pending           => { }
         }
 
+        // Then requests to control the window
+        match self.control_stream.poll_next_unpin(context) {
+            Poll::Ready(Some(item)) => { return Poll::Ready(Some(WindowUpdate::Control(item))); }
+            Poll::Ready(None)       => { }
+            Poll::Pending           => { }
+        }
+
         // The various binding streams
         match self.title_stream.poll_next_unpin(context) {
             Poll::Ready(Some(item)) => { return Poll::Ready(Some(WindowUpdate::SetTitle(item))); }
@@ -306,6 +360,18 @@ where
             Poll::Pending           => { }
         }
 
+        match self.always_on_top.poll_next_unpin(context) {
+            Poll::Ready(Some(item)) => { return Poll::Ready(Some(WindowUpdate::SetAlwaysOnTop(item))); }
+            Poll::Ready(None)       => { return Poll::Ready(None); }
+            Poll::Pending           => { }
+        }
+
+        match self.skip_taskbar.poll_next_unpin(context) {
+            Poll::Ready(Some(item)) => { return Poll::Ready(Some(WindowUpdate::SetSkipTaskbar(item))); }
+            Poll::Ready(None)       => { return Poll::Ready(None); }
+            Poll::Pending           => { }
+        }
+
         // No stream matched anything
         Poll::Pending
     }
@@ -0,0 +1,11 @@
+///
+/// One-off actions that can be performed on a window
+///
+/// Window properties describe the state of a window, and are followed by the window for as long as it is open: these are
+/// instead actions that happen once when they're requested.
+///
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum WindowControl {
+    /// Starts moving the window with the mouse pointer (this should be requested while a mouse button is held down)
+    DragWindow,
+}
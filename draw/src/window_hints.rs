@@ -0,0 +1,55 @@
+use winit::window::{Window, WindowBuilder, WindowLevel};
+
+///
+/// Returns the winit window level to use for a window that may be always-on-top
+///
+pub (crate) fn window_level(always_on_top: bool) -> WindowLevel {
+    if always_on_top {
+        WindowLevel::AlwaysOnTop
+    } else {
+        WindowLevel::Normal
+    }
+}
+
+///
+/// Applies the window hints from a set of window properties to a winit window builder
+///
+pub (crate) fn with_window_hints(window_builder: WindowBuilder, always_on_top: bool, skip_taskbar: bool) -> WindowBuilder {
+    let window_builder = window_builder.with_window_level(window_level(always_on_top));
+
+    #[cfg(target_os = "windows")]
+    let window_builder = {
+        use winit::platform::windows::{WindowBuilderExtWindows};
+        window_builder.with_skip_taskbar(skip_taskbar)
+    };
+
+    #[cfg(not(target_os = "windows"))]
+    let _ = skip_taskbar;
+
+    window_builder
+}
+
+///
+/// Updates whether or not a window is shown in the taskbar (this is only supported on Windows, and is ignored on other platforms)
+///
+pub (crate) fn set_skip_taskbar(window: &Window, skip_taskbar: bool) {
+    #[cfg(target_os = "windows")]
+    {
+        use winit::platform::windows::{WindowExtWindows};
+        window.set_skip_taskbar(skip_taskbar);
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    let _ = (window, skip_taskbar);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn always_on_top_maps_to_window_level() {
+        assert!(window_level(true) == WindowLevel::AlwaysOnTop);
+        assert!(window_level(false) == WindowLevel::Normal);
+    }
+}
@@ -1,4 +1,5 @@
 use flo_binding::*;
+use flo_canvas::{Color};
 use flo_canvas_events::*;
 
 ///
@@ -32,6 +33,42 @@ pub trait FloWindowProperties {
     /// The mouse pointer to show for a window
     ///
     fn mouse_pointer(&self) -> BindRef<MousePointer>;
+
+    ///
+    /// Set to true if the window should be displayed above all other windows
+    ///
+    fn always_on_top(&self) -> BindRef<bool> { BindRef::from(bind(false)) }
+
+    ///
+    /// Set to true if the window should not be shown in the taskbar (only supported on Windows)
+    ///
+    fn skip_taskbar(&self) -> BindRef<bool> { BindRef::from(bind(false)) }
+
+    ///
+    /// Set to true if the window should be transparent, so areas of the canvas that are not fully opaque show the windows behind it
+    ///
+    /// This is read when the window is created, and is not supported on every platform (the window will be opaque if it isn't)
+    ///
+    fn transparent(&self) -> BindRef<bool> { BindRef::from(bind(false)) }
+
+    ///
+    /// Set to true if the window should use a high dynamic range surface format
+    ///
+    /// This is read when the window is created, and is only supported by the wgpu renderer on displays with an HDR surface format
+    /// (the window will use a standard format if one isn't available). Canvas colours are limited to the 0-1 range: colours brighter
+    /// than 1.0 can be drawn by rendering floating-point textures (`RenderAction::CreateTextureRgbaF16`) to the window.
+    ///
+    fn hdr(&self) -> BindRef<bool> { BindRef::from(bind(false)) }
+
+    ///
+    /// The maximum number of frames per second that the window should display, or None to display frames as fast as they're rendered
+    ///
+    fn max_frame_rate(&self) -> BindRef<Option<f64>> { BindRef::from(bind(None)) }
+
+    ///
+    /// The colour to clear the window to when it is first displayed, before anything has been rendered to it
+    ///
+    fn initial_clear_color(&self) -> BindRef<Color> { BindRef::from(bind(Color::Rgba(0.0, 0.0, 0.0, 1.0))) }
 }
 
 ///
@@ -62,11 +99,17 @@ impl<'a> FloWindowProperties for &'a str {
 ///
 #[derive(Clone)]
 pub struct WindowProperties {
-    pub title:              BindRef<String>,
-    pub size:               BindRef<(u64, u64)>,
-    pub fullscreen:         BindRef<bool>,
-    pub has_decorations:    BindRef<bool>,
-    pub mouse_pointer:      BindRef<MousePointer>
+    pub title:                  BindRef<String>,
+    pub size:                   BindRef<(u64, u64)>,
+    pub fullscreen:             BindRef<bool>,
+    pub has_decorations:        BindRef<bool>,
+    pub mouse_pointer:          BindRef<MousePointer>,
+    pub always_on_top:          BindRef<bool>,
+    pub skip_taskbar:           BindRef<bool>,
+    pub transparent:            BindRef<bool>,
+    pub hdr:                    BindRef<bool>,
+    pub max_frame_rate:         BindRef<Option<f64>>,
+    pub initial_clear_color:    BindRef<Color>,
 }
 
 impl WindowProperties {
@@ -75,11 +118,17 @@ impl WindowProperties {
     ///
     pub fn from<T: FloWindowProperties>(properties: &T) -> WindowProperties {
         WindowProperties {
-            title:              properties.title(),
-            size:               properties.size(),
-            fullscreen:         properties.fullscreen(),
-            has_decorations:    properties.has_decorations(),
-            mouse_pointer:      properties.mouse_pointer()
+            title:                  properties.title(),
+            size:                   properties.size(),
+            fullscreen:             properties.fullscreen(),
+            has_decorations:        properties.has_decorations(),
+            mouse_pointer:          properties.mouse_pointer(),
+            always_on_top:          properties.always_on_top(),
+            skip_taskbar:           properties.skip_taskbar(),
+            transparent:            properties.transparent(),
+            hdr:                    properties.hdr(),
+            max_frame_rate:         properties.max_frame_rate(),
+            initial_clear_color:    properties.initial_clear_color(),
         }
     }
 }
@@ -90,4 +139,10 @@ impl FloWindowProperties for WindowProperties {
     fn fullscreen(&self) -> BindRef<bool>               { self.fullscreen.clone() }
     fn has_decorations(&self) -> BindRef<bool>          { self.has_decorations.clone() }
     fn mouse_pointer(&self) -> BindRef<MousePointer>    { self.mouse_pointer.clone() }
+    fn always_on_top(&self) -> BindRef<bool>            { self.always_on_top.clone() }
+    fn skip_taskbar(&self) -> BindRef<bool>             { self.skip_taskbar.clone() }
+    fn transparent(&self) -> BindRef<bool>              { self.transparent.clone() }
+    fn hdr(&self) -> BindRef<bool>                      { self.hdr.clone() }
+    fn max_frame_rate(&self) -> BindRef<Option<f64>>    { self.max_frame_rate.clone() }
+    fn initial_clear_color(&self) -> BindRef<Color>     { self.initial_clear_color.clone() }
 }
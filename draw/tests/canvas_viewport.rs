@@ -0,0 +1,43 @@
+use flo_draw::*;
+use flo_draw::canvas::*;
+
+fn is_close(point: Option<(f32, f32)>, expected: (f32, f32)) -> bool {
+    match point {
+        Some((x, y))    => (x - expected.0).abs() < 0.01 && (y - expected.1).abs() < 0.01,
+        None            => false,
+    }
+}
+
+#[test]
+fn map_pointer_to_viewport() {
+    let left    = CanvasViewport::new(0.0, 0.0, 500.0, 1000.0).show_region(0.0, 0.0, 1000.0, 1000.0);
+    let right   = CanvasViewport::new(500.0, 0.0, 1000.0, 1000.0).show_region(250.0, 250.0, 750.0, 750.0);
+
+    // Left viewport shows the whole scene at half scale, centred vertically
+    assert!(is_close(left.canvas_to_scene(250.0, 500.0), (500.0, 500.0)));
+
+    // Right viewport shows the middle of the scene at full scale
+    assert!(is_close(right.canvas_to_scene(750.0, 500.0), (500.0, 500.0)));
+    assert!(right.canvas_to_scene(250.0, 500.0) == None);
+
+    // Points are mapped using the viewport they fall in
+    let viewports = vec![left, right];
+    let (idx, point)    = viewport_at_point(&viewports, 750.0, 500.0).expect("Viewport");
+    assert!(idx == 1);
+    assert!(is_close(Some(point), (500.0, 500.0)));
+    assert!(viewport_at_point(&viewports, 1500.0, 500.0) == None);
+}
+
+#[test]
+fn draw_sprite_is_clipped() {
+    let viewport        = CanvasViewport::new(0.0, 0.0, 500.0, 1000.0);
+    let mut drawing     = vec![];
+
+    viewport.draw_sprite(&mut drawing, SpriteId(1));
+
+    let clip_pos        = drawing.iter().position(|draw| draw == &Draw::Clip).expect("Clip");
+    let sprite_pos      = drawing.iter().position(|draw| draw == &Draw::DrawSprite(SpriteId(1))).expect("DrawSprite");
+
+    assert!(clip_pos < sprite_pos);
+    assert!(drawing.last() == Some(&Draw::PopState));
+}
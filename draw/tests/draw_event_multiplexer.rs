@@ -0,0 +1,25 @@
+use flo_draw::*;
+
+use futures::prelude::*;
+use futures::stream;
+use futures::executor;
+
+#[test]
+fn two_subscribers_receive_same_event() {
+    let events          = stream::iter(vec![DrawEvent::Redraw, DrawEvent::NewFrame]);
+    let multiplexer     = DrawEventMultiplexer::new(events);
+
+    let mut subscriber1 = multiplexer.subscribe();
+    let mut subscriber2 = multiplexer.subscribe();
+
+    executor::block_on(async {
+        assert!(subscriber1.next().await == Some(DrawEvent::Redraw));
+        assert!(subscriber2.next().await == Some(DrawEvent::Redraw));
+
+        assert!(subscriber2.next().await == Some(DrawEvent::NewFrame));
+        assert!(subscriber1.next().await == Some(DrawEvent::NewFrame));
+
+        assert!(subscriber1.next().await == None);
+        assert!(subscriber2.next().await == None);
+    });
+}
@@ -0,0 +1,63 @@
+use flo_draw::*;
+
+use std::time::{Duration, Instant};
+
+#[test]
+fn runs_whole_steps_and_interpolates_remainder() {
+    let mut timestep    = FixedTimestep::new(60.0);
+    let step            = timestep.step_duration();
+
+    // Two and a half steps should run two steps and leave the frame halfway to the next one
+    let update          = timestep.advance(step * 2 + step / 2);
+
+    assert!(update.steps == 2, "{:?}", update);
+    assert!((update.alpha - 0.5).abs() < 0.01, "{:?}", update);
+
+    // The remaining half step is carried over to the next frame
+    let update          = timestep.advance(step / 2 + Duration::from_millis(1));
+
+    assert!(update.steps == 1, "{:?}", update);
+    assert!(update.alpha < 0.1, "{:?}", update);
+}
+
+#[test]
+fn slow_display_runs_several_steps_per_frame() {
+    // Simulate at 60Hz while displaying at 30Hz
+    let mut timestep    = FixedTimestep::new(60.0);
+    let start           = Instant::now();
+    timestep.advance_to(start);
+
+    let total_steps     = (1..=30)
+        .map(|frame| timestep.advance_to(start + Duration::from_secs_f64((frame as f64) / 30.0)).steps)
+        .sum::<usize>();
+
+    assert!(total_steps >= 59 && total_steps <= 60, "{} steps", total_steps);
+}
+
+#[test]
+fn discards_time_beyond_max_steps() {
+    let mut timestep    = FixedTimestep::new(60.0).with_max_steps(4);
+
+    // A long pause should only run the maximum number of steps and not carry the rest over
+    let update          = timestep.advance(Duration::from_secs(1));
+    assert!(update.steps == 4, "{:?}", update);
+    assert!(update.alpha == 0.0, "{:?}", update);
+
+    let update          = timestep.advance(Duration::from_secs(0));
+    assert!(update.steps == 0, "{:?}", update);
+}
+
+#[test]
+fn invalid_rates_are_clamped() {
+    // Rates that would produce an invalid step length are clamped instead of panicking
+    for steps_per_second in [0.0, -60.0, f64::NAN, f64::NEG_INFINITY] {
+        let timestep = FixedTimestep::new(steps_per_second);
+        assert!(timestep.step_duration() == Duration::from_secs(1000), "{} {:?}", steps_per_second, timestep.step_duration());
+    }
+
+    let mut timestep    = FixedTimestep::new(f64::INFINITY);
+    assert!(timestep.step_duration().as_nanos() >= 999 && timestep.step_duration().as_nanos() <= 1000, "{:?}", timestep.step_duration());
+
+    let update          = timestep.advance(Duration::from_micros(2));
+    assert!(update.steps == 2 && update.alpha.is_finite(), "{:?}", update);
+}
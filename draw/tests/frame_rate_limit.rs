@@ -0,0 +1,59 @@
+use flo_draw::*;
+use flo_draw::binding::*;
+use flo_render::*;
+
+use futures::prelude::*;
+use futures::stream;
+use futures::executor;
+
+use std::time::{Duration, Instant};
+
+#[test]
+fn frame_rate_does_not_exceed_limit() {
+    // A stream that can produce frames as fast as they're read, limited to 20fps
+    let frames          = stream::iter((0..1000).map(|_| vec![RenderAction::ShowFrameBuffer]));
+    let mut frames      = limit_frame_rate(frames, BindRef::from(bind(Some(20.0))));
+
+    // Count the frames that are released in half a second
+    let interval        = Duration::from_millis(500);
+    let start           = Instant::now();
+    let mut num_frames  = 0;
+
+    executor::block_on(async {
+        while let Some(_frame) = frames.next().await {
+            if start.elapsed() > interval {
+                break;
+            }
+
+            num_frames += 1;
+        }
+    });
+
+    // The first frame isn't delayed, so there can be one more frame than the limit allows over the interval
+    assert!(num_frames <= 11, "{} frames", num_frames);
+    assert!(num_frames >= 5, "{} frames", num_frames);
+}
+
+#[test]
+fn tiny_frame_rate_does_not_panic() {
+    // 1/1e-320 is infinite, so this rate has to be clamped rather than converted directly into a duration
+    let frames          = stream::iter((0..10).map(|_| vec![RenderAction::ShowFrameBuffer]));
+    let mut frames      = limit_frame_rate(frames, BindRef::from(bind(Some(1e-320))));
+
+    // The first frame is released immediately, and the second one is held back
+    assert!(frames.next().now_or_never().is_some());
+    assert!(frames.next().now_or_never().is_none());
+}
+
+#[test]
+fn invalid_frame_rates_are_unlimited() {
+    for frame_rate in [0.0, -10.0, f64::NAN] {
+        let frames          = stream::iter((0..10).map(|_| vec![RenderAction::ShowFrameBuffer]));
+        let mut frames      = limit_frame_rate(frames, BindRef::from(bind(Some(frame_rate))));
+
+        // Every frame should be available straight away
+        for _ in 0..10 {
+            assert!(frames.next().now_or_never().is_some(), "{}", frame_rate);
+        }
+    }
+}
@@ -0,0 +1,48 @@
+use flo_draw::*;
+use flo_draw::canvas::*;
+
+use futures::prelude::*;
+use futures::executor;
+
+#[test]
+fn injected_click_reaches_subscriber() {
+    let mut window      = HeadlessWindow::new(800.0, 600.0);
+    let mut events      = window.subscribe();
+
+    // Canvas with a height of 1000 units, centred in the window
+    window.draw(&vec![Draw::ClearCanvas(Color::Rgba(0.0, 0.0, 0.0, 0.0)), Draw::CanvasHeight(1000.0)]);
+
+    executor::block_on(async {
+        let mut pointer_state               = PointerState::new();
+        pointer_state.location_in_window    = (400.0, 300.0);
+        pointer_state.buttons               = vec![Button::Left];
+
+        window.inject_event(DrawEvent::Pointer(PointerAction::ButtonDown, PointerId(0), pointer_state)).await;
+
+        // The event should arrive at the subscriber with its canvas coordinates filled in
+        match events.next().await {
+            Some(DrawEvent::Pointer(PointerAction::ButtonDown, PointerId(0), pointer_state)) => {
+                let (x, y) = pointer_state.location_in_canvas.unwrap();
+
+                assert!(pointer_state.location_in_window == (400.0, 300.0));
+                assert!(pointer_state.buttons == vec![Button::Left]);
+                assert!(x.abs() < 0.01, "{:?}", (x, y));
+                assert!(y.abs() < 0.01, "{:?}", (x, y));
+            }
+
+            other => { panic!("Unexpected event: {:?}", other); }
+        }
+    });
+}
+
+#[test]
+fn draw_from_async_code() {
+    let mut window = HeadlessWindow::new(800.0, 600.0);
+
+    // draw_async() can be awaited alongside the other async window functions
+    let actions = executor::block_on(async {
+        window.draw_async(&vec![Draw::ClearCanvas(Color::Rgba(0.0, 0.0, 0.0, 0.0)), Draw::CanvasHeight(1000.0)]).await
+    });
+
+    assert!(!actions.is_empty());
+}
@@ -0,0 +1,10 @@
+use flo_draw::*;
+
+#[test]
+#[ignore]       // Needs a display to query (run with `cargo test -- --ignored` on a machine with a monitor)
+fn at_least_one_monitor() {
+    let monitors = available_monitors();
+
+    assert!(monitors.len() > 0);
+    assert!(monitors.iter().any(|monitor| monitor.size.0 > 0 && monitor.size.1 > 0), "{:?}", monitors);
+}
@@ -0,0 +1,10 @@
+fn color_post_process(col: vec4<f32>) -> vec4<f32> {
+    let display_col = color_post_process_display(col);
+
+    return vec4<f32>(
+        srgb_to_linear(display_col[0]),
+        srgb_to_linear(display_col[1]),
+        srgb_to_linear(display_col[2]),
+        display_col[3]
+    );
+}
@@ -0,0 +1,15 @@
+fn color_post_process(col: vec4<f32>) -> vec4<f32> {
+    let display_col = color_post_process_display(col);
+    let alpha       = display_col[3];
+
+    if (alpha <= 0.0) {
+        return display_col;
+    }
+
+    return vec4<f32>(
+        srgb_to_linear(display_col[0] / alpha) * alpha,
+        srgb_to_linear(display_col[1] / alpha) * alpha,
+        srgb_to_linear(display_col[2] / alpha) * alpha,
+        alpha
+    );
+}
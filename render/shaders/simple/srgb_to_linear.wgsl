@@ -0,0 +1,7 @@
+fn srgb_to_linear(val: f32) -> f32 {
+    if (val <= 0.04045) {
+        return val / 12.92;
+    } else {
+        return pow((val + 0.055) / 1.055, 2.4);
+    }
+}
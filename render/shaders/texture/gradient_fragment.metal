@@ -36,7 +36,7 @@ fragment float4 gradient_fragment(
     const half4 color_sample = texture.sample(texture_sampler, in.v_TexCoord);
 
     float4 color  = float4(color_sample);
-    color[3]      *= *texture_alpha;
+    color         *= *texture_alpha;
 
     return color;
 }
@@ -52,7 +52,7 @@ fragment float4 gradient_clip_mask_multisample_fragment(
 
     // Apply the clip mask
     float4 color  = apply_clip_mask(static_cast<float4>(color_sample), in.v_PaperCoord, clip_mask_texture);
-    color[3]      *= *texture_alpha;
+    color         *= *texture_alpha;
 
     return color;
 }
@@ -66,7 +66,7 @@ fragment float4 gradient_fragment_invert_color_alpha(
     const half4 color_sample = texture.sample(texture_sampler, in.v_TexCoord);
 
     float4 color  = float4(color_sample);
-    color[3]      *= *texture_alpha;
+    color         *= *texture_alpha;
 
     return invert_color_alpha(color);
 }
@@ -80,6 +80,66 @@ fragment float4 gradient_clip_mask_multisample_fragment_invert_color_alpha(
     constexpr metal::sampler texture_sampler (metal::mag_filter::linear, metal::min_filter::linear);
     const half4 color_sample    = texture.sample(texture_sampler, in.v_TexCoord);
 
+    // Apply the clip mask
+    float4 color  = apply_clip_mask(static_cast<float4>(color_sample), in.v_PaperCoord, clip_mask_texture);
+    color         *= *texture_alpha;
+
+    return invert_color_alpha(color);
+}
+
+fragment float4 gradient_straight_fragment(
+      GradientData                in [[stage_in]],
+      constant float              *texture_alpha [[ buffer(FragmentAlpha) ]],
+      metal::texture1d<half>      texture [[ texture(FragmentIndexTexture) ]]) {
+    constexpr metal::sampler texture_sampler (metal::mag_filter::linear, metal::min_filter::linear);
+
+    const half4 color_sample = texture.sample(texture_sampler, in.v_TexCoord);
+
+    float4 color  = float4(color_sample);
+    color[3]      *= *texture_alpha;
+
+    return color;
+}
+
+fragment float4 gradient_straight_clip_mask_multisample_fragment(
+      GradientData                in [[stage_in]],
+      constant float              *texture_alpha [[ buffer(FragmentAlpha) ]],
+      metal::texture1d<half>      texture [[ texture(FragmentIndexTexture) ]],
+      metal::texture2d_ms<half>   clip_mask_texture [[ texture(FragmentIndexClipMaskTexture) ]]) {
+    // Color from the gradient
+    constexpr metal::sampler texture_sampler (metal::mag_filter::linear, metal::min_filter::linear);
+    const half4 color_sample    = texture.sample(texture_sampler, in.v_TexCoord);
+
+    // Apply the clip mask
+    float4 color  = apply_clip_mask(static_cast<float4>(color_sample), in.v_PaperCoord, clip_mask_texture);
+    color[3]      *= *texture_alpha;
+
+    return color;
+}
+
+fragment float4 gradient_straight_fragment_invert_color_alpha(
+      GradientData                in [[stage_in]],
+      constant float              *texture_alpha [[ buffer(FragmentAlpha) ]],
+      metal::texture1d<half>      texture [[ texture(FragmentIndexTexture) ]]) {
+    constexpr metal::sampler texture_sampler (metal::mag_filter::linear, metal::min_filter::linear);
+
+    const half4 color_sample = texture.sample(texture_sampler, in.v_TexCoord);
+
+    float4 color  = float4(color_sample);
+    color[3]      *= *texture_alpha;
+
+    return invert_color_alpha(color);
+}
+
+fragment float4 gradient_straight_clip_mask_multisample_fragment_invert_color_alpha(
+      GradientData                in [[stage_in]],
+      constant float              *texture_alpha [[ buffer(FragmentAlpha) ]],
+      metal::texture1d<half>      texture [[ texture(FragmentIndexTexture) ]],
+      metal::texture2d_ms<half>   clip_mask_texture [[ texture(FragmentIndexClipMaskTexture) ]]) {
+    // Color from the gradient
+    constexpr metal::sampler texture_sampler (metal::mag_filter::linear, metal::min_filter::linear);
+    const half4 color_sample    = texture.sample(texture_sampler, in.v_TexCoord);
+
     // Apply the clip mask
     float4 color  = apply_clip_mask(static_cast<float4>(color_sample), in.v_PaperCoord, clip_mask_texture);
     color[3]      *= *texture_alpha;
@@ -2,16 +2,20 @@ mod identities;
 mod render_action;
 mod render_action_type;
 mod render_target_type;
+mod render_capabilities;
 mod color;
 mod blend_mode;
 mod shader_type;
 mod texture_filter;
+mod texture_compression;
 
 pub use self::identities::*;
 pub use self::render_action::*;
 pub use self::render_action_type::*;
 pub use self::render_target_type::*;
+pub use self::render_capabilities::*;
 pub use self::color::*;
 pub use self::blend_mode::*;
 pub use self::shader_type::*;
 pub use self::texture_filter::*;
+pub use self::texture_compression::*;
@@ -3,6 +3,7 @@ use super::identities::*;
 use super::blend_mode::*;
 use super::shader_type::*;
 use super::texture_filter::*;
+use super::texture_compression::*;
 use super::render_target_type::*;
 
 use crate::buffer::*;
@@ -122,6 +123,25 @@ pub enum RenderAction {
     ///
     CreateTextureMono(TextureId, Size2D),
 
+    ///
+    /// Creates a 16-bit floating point RGBA 2D texture of the specified size
+    ///
+    /// The data for this texture is written using `WriteTextureData` as little-endian 16-bit floats (8 bytes per pixel). The
+    /// colour values are not clamped to the 0-1 range, so this can be used to draw colours brighter than 1.0 onto an HDR target.
+    /// Values use the same (extended) sRGB encoding as other colours. Check `supports_action()` before using this.
+    ///
+    CreateTextureRgbaF16(TextureId, Size2D),
+
+    ///
+    /// Creates a block-compressed 2D texture of the specified size
+    ///
+    /// The data for this texture is written using `WriteTextureData` as compressed blocks: the region written must be aligned
+    /// to the block size of the format. The width and height must be multiples of the block size. Check `supports_action()`
+    /// before using this, as not all renderers can use all formats. Compressed textures can't be rendered to, so `CreateMipMaps`
+    /// and `FilterTexture` leave them unchanged.
+    ///
+    CreateTextureCompressed(TextureId, Size2D, TextureCompression),
+
     ///
     /// Creates a 1 dimensional 8-bit BGRA texture of the specified size
     ///
@@ -154,6 +174,24 @@ pub enum RenderAction {
     ///
     CopyTexture(TextureId, TextureId),
 
+    ///
+    /// Copies a region of one texture to a position in another texture
+    ///
+    /// The region is given as the minimum and maximum coordinates in the source texture, and is clipped to fit in both textures.
+    /// The two textures must be different and have the same format. Regions in compressed textures must be aligned to the
+    /// block size of the format.
+    ///
+    CopyTextureRegion(TextureId, Position2D, Position2D, TextureId, Position2D),
+
+    ///
+    /// Sets whether or not the colours in a texture have premultiplied alpha
+    ///
+    /// Textures created by the `CreateTexture` actions start out with straight alpha, and the textures for render targets have
+    /// premultiplied alpha. The `Texture` and `LinearGradient` shaders use this to decide how to apply alpha from the texture.
+    /// Creating a new texture with the same ID resets this.
+    ///
+    SetTexturePremultiplied(TextureId, bool),
+
     ///
     /// Applies a set of filters to a texture
     ///
@@ -185,6 +223,27 @@ pub enum RenderAction {
     /// Renders triangles using an index buffer
     ///
     DrawIndexedTriangles(VertexBufferId, IndexBufferId, usize),
+
+    ///
+    /// Starts adding a clipping region to the stencil buffer of the current render target
+    ///
+    /// Until the following `EndStencilClip`, draw calls mark the pixels they cover as being inside the clipping region instead
+    /// of updating the colour of the render target. Clipping regions nest: only pixels that are inside all of the regions that
+    /// are already active are marked, so the new clipping region is the intersection of the shapes.
+    ///
+    PushStencilClip,
+
+    ///
+    /// Finishes the clipping region started by `PushStencilClip`: future draw calls only update the pixels inside it
+    ///
+    EndStencilClip,
+
+    ///
+    /// Removes the most recently pushed stencil clipping region, so future draw calls use the clipping region that was active before it
+    ///
+    /// All of the stencil clipping regions are removed when a different render target is selected, and at the end of each set of render actions.
+    ///
+    PopStencilClip,
 }
 
 impl Default for FrameBufferRegion {
@@ -245,18 +304,25 @@ impl RenderAction {
             DrawFrameBuffer(render_id, region, alpha)                       => format!("DrawFrameBuffer({:?}, {:?}, {:?})", render_id, region, alpha),
             CreateTextureBgra(texture_id, size)                             => format!("CreateTextureBgra({:?}, {:?})", texture_id, size),
             CreateTextureMono(texture_id, size)                             => format!("CreateTextureMono({:?}, {:?})", texture_id, size),
+            CreateTextureRgbaF16(texture_id, size)                          => format!("CreateTextureRgbaF16({:?}, {:?})", texture_id, size),
+            CreateTextureCompressed(texture_id, size, compression)          => format!("CreateTextureCompressed({:?}, {:?}, {:?})", texture_id, size, compression),
             Create1DTextureBgra(texture_id, w)                              => format!("Create1DTextureBgra({:?}, {:?})", texture_id, w),
             Create1DTextureMono(texture_id, w)                              => format!("Create1DTextureMono({:?}, {:?})", texture_id, w),
             WriteTextureData(texture_id, pos, size, bytes)                  => format!("WriteTextureData({:?}, {:?}, {:?}, [{} bytes])", texture_id, pos, size, bytes.len()),
             WriteTexture1D(texture_id, x, w, bytes)                         => format!("WriteTexture1D({:?}, {:?}, {:?}, [{} bytes])", texture_id, x, w, bytes.len()),
             CreateMipMaps(texture_id)                                       => format!("CreateMipMaps({:?})", texture_id),
+            SetTexturePremultiplied(texture_id, premultiplied)              => format!("SetTexturePremultiplied({:?}, {:?})", texture_id, premultiplied),
             FilterTexture(texture_id, filter)                               => format!("FilterTexture({:?}, {:?})", texture_id, filter),
             CopyTexture(id1, id2)                                           => format!("CopyTexture({:?}, {:?})", id1, id2),
+            CopyTextureRegion(id1, min, max, id2, pos)                      => format!("CopyTextureRegion({:?}, {:?}, {:?}, {:?}, {:?})", id1, min, max, id2, pos),
             FreeTexture(texture_id)                                         => format!("FreeTexture({:?})", texture_id),
             Clear(bg_col)                                                   => format!("Clear({:?})", bg_col),
             UseShader(shader_type)                                          => format!("UseShader({:?})", shader_type),
             DrawTriangles(buffer_id, range)                                 => format!("DrawTriangles({:?}, {:?})", buffer_id, range),
             DrawIndexedTriangles(buffer_id, index_id, len)                  => format!("DrawIndexedTriangles({:?}, {:?}, {:?})", buffer_id, index_id, len),
+            PushStencilClip                                                 => format!("PushStencilClip"),
+            EndStencilClip                                                  => format!("EndStencilClip"),
+            PopStencilClip                                                  => format!("PopStencilClip"),
         }
     }
 }
@@ -22,18 +22,25 @@ pub enum RenderActionType {
     DrawFrameBuffer,
     CreateTextureBgra,
     CreateTextureMono,
+    CreateTextureRgbaF16,
+    CreateTextureCompressed,
     Create1DTextureBgra,
     Create1DTextureMono,
     WriteTextureData,
     WriteTexture1D,
     CreateMipMaps,
     CopyTexture,
+    CopyTextureRegion,
+    SetTexturePremultiplied,
     FilterTexture,
     FreeTexture,
     Clear,
     UseShader,
     DrawTriangles,
     DrawIndexedTriangles,
+    PushStencilClip,
+    EndStencilClip,
+    PopStencilClip,
 
     RenderPassSetPipeline,
     RenderPassDrawFramebuffer,
@@ -58,18 +65,25 @@ impl From<&RenderAction> for RenderActionType {
             RenderAction::DrawFrameBuffer(_, _, _)          => RenderActionType::DrawFrameBuffer,
             RenderAction::CreateTextureBgra(_, _)           => RenderActionType::CreateTextureBgra,
             RenderAction::CreateTextureMono(_, _)           => RenderActionType::CreateTextureMono,
+            RenderAction::CreateTextureRgbaF16(_, _)        => RenderActionType::CreateTextureRgbaF16,
+            RenderAction::CreateTextureCompressed(_, _, _)  => RenderActionType::CreateTextureCompressed,
             RenderAction::Create1DTextureBgra(_, _)         => RenderActionType::Create1DTextureBgra,
             RenderAction::Create1DTextureMono(_, _)         => RenderActionType::Create1DTextureMono,
             RenderAction::WriteTextureData(_, _, _, _)      => RenderActionType::WriteTextureData,
             RenderAction::WriteTexture1D(_, _, _, _)        => RenderActionType::WriteTexture1D,
             RenderAction::CreateMipMaps(_)                  => RenderActionType::CreateMipMaps,
             RenderAction::CopyTexture(_, _)                 => RenderActionType::CopyTexture,
+            RenderAction::CopyTextureRegion(_, _, _, _, _)  => RenderActionType::CopyTextureRegion,
+            RenderAction::SetTexturePremultiplied(_, _)     => RenderActionType::SetTexturePremultiplied,
             RenderAction::FilterTexture(_, _)               => RenderActionType::FilterTexture,
             RenderAction::FreeTexture(_)                    => RenderActionType::FreeTexture,
             RenderAction::Clear(_)                          => RenderActionType::Clear,
             RenderAction::UseShader(_)                      => RenderActionType::UseShader,
             RenderAction::DrawTriangles(_, _)               => RenderActionType::DrawTriangles,
             RenderAction::DrawIndexedTriangles(_, _, _)     => RenderActionType::DrawIndexedTriangles,
+            RenderAction::PushStencilClip                   => RenderActionType::PushStencilClip,
+            RenderAction::EndStencilClip                    => RenderActionType::EndStencilClip,
+            RenderAction::PopStencilClip                    => RenderActionType::PopStencilClip,
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,36 @@
+use super::render_action::*;
+use super::render_action_type::*;
+
+///
+/// Trait implemented by renderers that can report which render actions they are able to process
+///
+/// Some of the rendering back-ends do not implement every action: these will usually be ignored when they
+/// are sent to the renderer, so this can be used to determine which parts of a rendering will not appear
+/// on a particular back-end.
+///
+pub trait RenderCapabilities {
+    ///
+    /// Returns true if this renderer implements the specified action, or false if it will be ignored
+    ///
+    fn supports_action(&self, action: &RenderAction) -> bool;
+
+    ///
+    /// Returns the types of the actions in a list that are not supported by this renderer (each type is returned once, in
+    /// the order that it's first encountered)
+    ///
+    fn unsupported_actions<'a, ActionIter: IntoIterator<Item=&'a RenderAction>>(&self, actions: ActionIter) -> Vec<RenderActionType> {
+        let mut unsupported = vec![];
+
+        for action in actions {
+            if !self.supports_action(action) {
+                let action_type = RenderActionType::from(action);
+
+                if !unsupported.contains(&action_type) {
+                    unsupported.push(action_type);
+                }
+            }
+        }
+
+        unsupported
+    }
+}
@@ -15,9 +15,14 @@ pub enum ShaderType {
     DashedLine { dash_texture: TextureId, clip_texture: Option<TextureId> },
 
     /// Colour derived from a texture with a transform mapping from canvas coordinates to texture coordinates
+    ///
+    /// The texture is treated as having straight alpha unless it has been marked with `SetTexturePremultiplied`
     Texture { texture: TextureId, texture_transform: Matrix, repeat: bool, alpha: f32, clip_texture: Option<TextureId> },
 
     /// Colour derived from a 1D texture using a transform mapping (used for rendering linear gradients)
+    ///
+    /// As for `Texture`, the gradient texture is treated as having straight alpha unless it has been marked with
+    /// `SetTexturePremultiplied`. Premultiplied gradients avoid dark fringes where transparent and opaque stops are blended.
     LinearGradient { texture: TextureId, texture_transform: Matrix, repeat: bool, alpha: f32, clip_texture: Option<TextureId> }
 }
 
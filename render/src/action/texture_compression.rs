@@ -0,0 +1,53 @@
+///
+/// Block-compressed formats that can be used for textures
+///
+/// Compressed textures are stored as blocks of pixels that are decompressed by the GPU when they are sampled, which makes them
+/// suitable for large textures that do not change (they cannot be used as render targets). Which formats are available depends
+/// on the GPU: `supports_action()` can be used to check before creating a compressed texture.
+///
+#[derive(Copy, Clone, PartialEq, Debug, Eq, Hash)]
+pub enum TextureCompression {
+    /// BC1 (DXT1) compression: 8 bytes per 4x4 block, with RGB colour and 1-bit alpha
+    Bc1,
+
+    /// BC3 (DXT5) compression: 16 bytes per 4x4 block, with RGB colour and interpolated alpha
+    Bc3,
+
+    /// ETC2 RGBA compression: 16 bytes per 4x4 block
+    Etc2Rgba8,
+
+    /// ASTC compression with 4x4 blocks: 16 bytes per block
+    Astc4x4,
+}
+
+impl TextureCompression {
+    ///
+    /// The width and height in pixels of a single compressed block
+    ///
+    pub fn block_dimensions(&self) -> (usize, usize) {
+        (4, 4)
+    }
+
+    ///
+    /// Returns true if a texture of the specified size can use this format
+    ///
+    /// Compressed textures must be a whole number of blocks in both dimensions
+    ///
+    pub fn is_valid_size(&self, width: usize, height: usize) -> bool {
+        let (block_width, block_height) = self.block_dimensions();
+
+        width > 0 && height > 0 && (width % block_width) == 0 && (height % block_height) == 0
+    }
+
+    ///
+    /// The number of bytes used to store a single compressed block
+    ///
+    pub fn bytes_per_block(&self) -> usize {
+        match self {
+            TextureCompression::Bc1         => 8,
+            TextureCompression::Bc3         => 16,
+            TextureCompression::Etc2Rgba8   => 16,
+            TextureCompression::Astc4x4     => 16,
+        }
+    }
+}
@@ -12,7 +12,11 @@ use crate::buffer::*;
 
 #[cfg(feature="profile")]
 use crate::profiler::*;
+#[cfg(feature="logging")]
+use crate::render_log::*;
 
+#[cfg(feature="logging")]
+use std::time::{Instant};
 use std::mem;
 use std::ptr;
 use std::ops::{Range};
@@ -54,6 +58,12 @@ pub struct GlRenderer {
     /// The shader programs
     shader_programs: ShaderCollection<StandardShaderProgram, ShaderUniform>,
 
+    /// The number of stencil clipping regions that are currently active
+    stencil_clip_depth: u8,
+
+    /// Set to true while a stencil clipping region is being drawn (between `PushStencilClip` and `EndStencilClip`)
+    drawing_stencil_clip: bool,
+
     #[cfg(feature="profile")]
     profiler: RenderProfiler<RenderActionType>,
 }
@@ -77,6 +87,8 @@ impl GlRenderer {
             transform_matrix:               None,
             render_targets:                 vec![],
             shader_programs:                shader_programs,
+            stencil_clip_depth:             0,
+            drawing_stencil_clip:           false,
 
             #[cfg(feature="profile")]
             profiler:                       RenderProfiler::new(),
@@ -109,6 +121,8 @@ impl GlRenderer {
     pub fn render<Actions: IntoIterator<Item=RenderAction>>(&mut self, actions: Actions) {
         #[cfg(feature="profile")]
         self.profiler.start_frame();
+        #[cfg(feature="logging")]
+        let frame_start = Instant::now();
 
         // Enable options
         self.enable_options();
@@ -144,18 +158,25 @@ impl GlRenderer {
                 ShowFrameBuffer                                                                 => { /* This doesn't double-buffer so nothing to do */ }
                 CreateTextureBgra(texture_id, Size2D(width, height))                            => { self.create_bgra_texture(texture_id, width, height); }
                 CreateTextureMono(texture_id, Size2D(width, height))                            => { self.create_mono_texture(texture_id, width, height); }
+                CreateTextureCompressed(_, _, _)                                                => { /* Compressed textures are not supported by this renderer */ }
+                CreateTextureRgbaF16(_, _)                                                      => { /* Floating point textures are not supported by this renderer */ }
                 Create1DTextureBgra(texture_id, Size1D(width))                                  => { self.create_1d_bgra_texture(texture_id, width); }
                 Create1DTextureMono(texture_id, Size1D(width))                                  => { self.create_1d_mono_texture(texture_id, width); }
                 WriteTextureData(texture_id, Position2D(x1, y1), Position2D(x2, y2), data)      => { self.write_texture_data_2d(texture_id, (x1, y1), (x2, y2), &*data); }
                 WriteTexture1D(texture_id, Position1D(x1), Position1D(x2), data)                => { self.write_texture_data_1d(texture_id, x1, x2, &*data); }
                 CreateMipMaps(texture_id)                                                       => { self.create_mipmaps(texture_id); }
                 CopyTexture(source, target)                                                     => { self.copy_texture(source, target); }
+                CopyTextureRegion(_, _, _, _, _)                                                => { /* Copying texture regions is not supported by this renderer */ }
+                SetTexturePremultiplied(texture_id, premultiplied)                              => { self.set_texture_premultiplied(texture_id, premultiplied); }
                 FilterTexture(texture, filter)                                                  => { self.filter_texture(texture, filter); }
                 FreeTexture(texture_id)                                                         => { self.free_texture(texture_id); }
                 Clear(color)                                                                    => { self.clear(color); }
                 UseShader(shader_type)                                                          => { self.use_shader(shader_type); }
                 DrawTriangles(buffer_id, buffer_range)                                          => { self.draw_triangles(buffer_id, buffer_range); }
                 DrawIndexedTriangles(vertex_buffer, index_buffer, num_vertices)                 => { self.draw_indexed_triangles(vertex_buffer, index_buffer, num_vertices); }
+                PushStencilClip                                                                 => { self.push_stencil_clip(); }
+                EndStencilClip                                                                  => { self.end_stencil_clip(); }
+                PopStencilClip                                                                  => { self.pop_stencil_clip(); }
             }
 
             panic_on_gl_error("Post-action");
@@ -173,6 +194,9 @@ impl GlRenderer {
 
         panic_on_gl_error("Render tidy up");
 
+        #[cfg(feature="logging")]
+        log_render_event(RenderLogEvent::FrameCompleted { duration: frame_start.elapsed() });
+
         #[cfg(feature="profile")]
         {
             self.profiler.finish_frame();
@@ -193,6 +217,9 @@ impl GlRenderer {
             self.shader_programs.use_program(StandardShaderProgram::default());
 
             self.blend_mode(BlendMode::SourceOver, false);
+
+            // No stencil clipping regions are active to start with
+            self.reset_stencil_clip();
         }
     }
 
@@ -202,6 +229,10 @@ impl GlRenderer {
     fn disable_options(&self) {
         unsafe {
             gl::Disable(gl::BLEND);
+
+            // Remove any stencil clipping that was left active
+            gl::Disable(gl::STENCIL_TEST);
+            gl::ColorMask(gl::TRUE, gl::TRUE, gl::TRUE, gl::TRUE);
         }
     }
 
@@ -239,6 +270,9 @@ impl GlRenderer {
         let vertex_array        = VertexArray::new();
         buffer.static_draw(&vertices);
 
+        #[cfg(feature="logging")]
+        log_render_event(RenderLogEvent::BufferAllocated { kind: "vertex", buffer_id: buffer_id, bytes: vertices.len() * mem::size_of::<Vertex2D>() });
+
         unsafe {
             // Bind a vertex array object to it
             gl::BindVertexArray(*vertex_array);
@@ -273,6 +307,9 @@ impl GlRenderer {
         let mut buffer          = Buffer::new();
         buffer.static_draw(&indices);
 
+        #[cfg(feature="logging")]
+        log_render_event(RenderLogEvent::BufferAllocated { kind: "index", buffer_id: buffer_id, bytes: indices.len() * mem::size_of::<u16>() });
+
         // Store in the buffers collections
         self.index_buffers[buffer_id] = Some(buffer);
     }
@@ -543,6 +580,15 @@ impl GlRenderer {
         }
     }
 
+    ///
+    /// Sets whether or not a texture contains premultiplied colours
+    ///
+    fn set_texture_premultiplied(&mut self, TextureId(texture_id): TextureId, premultiplied: bool) {
+        if let Some(Some(texture)) = self.textures.get_mut(texture_id) {
+            texture.premultiplied = premultiplied;
+        }
+    }
+
     ///
     /// Modifies a texture by applying a filter to it
     ///
@@ -817,12 +863,18 @@ impl GlRenderer {
         if let Some(render_target) = &self.render_targets[render_id] {
             self.active_render_target = Some(RenderTargetId(render_id));
 
+            #[cfg(feature="logging")]
+            log_render_event(RenderLogEvent::RenderTargetSelected { render_target: Some(RenderTargetId(render_id)) });
+
             unsafe {
                 let (width, height) = render_target.get_size();
 
                 gl::BindFramebuffer(gl::FRAMEBUFFER, **render_target);
                 gl::Viewport(0, 0, width as gl::types::GLsizei, height as gl::types::GLsizei);
             }
+
+            // Stencil clipping regions belong to the render target they were drawn in
+            self.reset_stencil_clip();
         }
     }
 
@@ -831,6 +883,10 @@ impl GlRenderer {
     ///
     fn select_main_frame_buffer(&mut self) {
         self.active_render_target = None;
+        self.reset_stencil_clip();
+
+        #[cfg(feature="logging")]
+        log_render_event(RenderLogEvent::RenderTargetSelected { render_target: None });
 
         if let Some(default_render_target) = &self.default_render_target {
             unsafe {
@@ -873,35 +929,8 @@ impl GlRenderer {
                             gl::Uniform1i(source_texture, 0);
                         });
 
-                    // Create the vertices for the two triangles making up the screen
-                    let min_x               = region.min_x();
-                    let min_y               = region.min_y();
-                    let max_x               = region.max_x();
-                    let max_y               = region.max_y();
-
-                    let vertices            = vec![
-                        Vertex2D::with_pos(min_x, min_y), Vertex2D::with_pos(max_x, min_y), Vertex2D::with_pos(min_x, max_y),
-                        Vertex2D::with_pos(max_x, min_y), Vertex2D::with_pos(min_x, max_y), Vertex2D::with_pos(max_x, max_y),
-                    ];
-                    let mut buffer          = Buffer::new();
-                    let vertex_array        = VertexArray::new();
-                    buffer.static_draw(&vertices);
-
-                    // Bind a vertex array object to it
-                    gl::BindVertexArray(*vertex_array);
-                    gl::BindBuffer(gl::ARRAY_BUFFER, *buffer);
-
-                    Vertex2D::define_attributes();
-
-                    // Clear the bindings
-                    gl::BindBuffer(gl::ARRAY_BUFFER, 0);
-                    gl::BindVertexArray(0);
-
-                    // Render a quad filling the screen
-                    gl::BindVertexArray(*vertex_array);
-                    gl::DrawArrays(gl::TRIANGLES, 0, 6);
-
-                    gl::BindVertexArray(0);
+                    // Render a quad covering the region
+                    Self::draw_quad((region.min_x(), region.min_y()), (region.max_x(), region.max_y()));
                 } else {
                     // Blit the framebuffer if we're using a renderbuffer directly instead of a backing texture (won't blend or obey the alpha value)
                     let (x, y)          = (0, 0);
@@ -923,6 +952,36 @@ impl GlRenderer {
         panic_on_gl_error("Draw frame buffer");
     }
 
+    ///
+    /// Draws a quad using the current shader program (coordinates are passed directly to the shader)
+    ///
+    unsafe fn draw_quad((min_x, min_y): (f32, f32), (max_x, max_y): (f32, f32)) {
+        // Create the vertices for the two triangles making up the quad
+        let vertices            = vec![
+            Vertex2D::with_pos(min_x, min_y), Vertex2D::with_pos(max_x, min_y), Vertex2D::with_pos(min_x, max_y),
+            Vertex2D::with_pos(max_x, min_y), Vertex2D::with_pos(min_x, max_y), Vertex2D::with_pos(max_x, max_y),
+        ];
+        let mut buffer          = Buffer::new();
+        let vertex_array        = VertexArray::new();
+        buffer.static_draw(&vertices);
+
+        // Bind a vertex array object to it
+        gl::BindVertexArray(*vertex_array);
+        gl::BindBuffer(gl::ARRAY_BUFFER, *buffer);
+
+        Vertex2D::define_attributes();
+
+        // Clear the bindings
+        gl::BindBuffer(gl::ARRAY_BUFFER, 0);
+        gl::BindVertexArray(0);
+
+        // Render the quad
+        gl::BindVertexArray(*vertex_array);
+        gl::DrawArrays(gl::TRIANGLES, 0, 6);
+
+        gl::BindVertexArray(0);
+    }
+
     ///
     /// Releases an existing render target
     ///
@@ -979,8 +1038,8 @@ impl GlRenderer {
             Some(Texture { texture, clip_texture: None, .. })           => Some(StandardShaderProgram::Texture(StandardShaderVariant::NoClipping, self.alpha_blend_step_for_texture(texture), post_processing)),
             Some(Texture { texture, clip_texture: Some(_), .. })        => Some(StandardShaderProgram::Texture(StandardShaderVariant::ClippingMask, self.alpha_blend_step_for_texture(texture), post_processing)),
 
-            Some(LinearGradient { clip_texture: None, .. })             => Some(StandardShaderProgram::LinearGradient(StandardShaderVariant::NoClipping, post_processing)),
-            Some(LinearGradient { clip_texture: Some(_), .. })          => Some(StandardShaderProgram::LinearGradient(StandardShaderVariant::ClippingMask, post_processing)),
+            Some(LinearGradient { texture, clip_texture: None, .. })    => Some(StandardShaderProgram::LinearGradient(StandardShaderVariant::NoClipping, self.alpha_blend_step_for_texture(texture), post_processing)),
+            Some(LinearGradient { texture, clip_texture: Some(_), .. }) => Some(StandardShaderProgram::LinearGradient(StandardShaderVariant::ClippingMask, self.alpha_blend_step_for_texture(texture), post_processing)),
 
             None                                                        => None
         }
@@ -1093,13 +1152,15 @@ impl GlRenderer {
 
             LinearGradient { texture, texture_transform, repeat, alpha, clip_texture } => {
                 let textures            = &self.textures;
+                let alpha_blend_step    = self.alpha_blend_step_for_texture(&texture);
                 let TextureId(texture)  = texture;
                 let texture             = if texture < self.textures.len() { self.textures[texture].as_ref() } else { None };
                 let clip_texture        = clip_texture.and_then(|TextureId(texture_id)| textures[texture_id].as_ref());
                 let variant             = if clip_texture.is_some() { StandardShaderVariant::ClippingMask } else { StandardShaderVariant::NoClipping };
                 let texture_transform   = texture_transform.to_opengl_matrix();
+                is_premultiplied        = texture.map(|texture| texture.premultiplied).unwrap_or(false);
 
-                let program             = self.shader_programs.use_program(StandardShaderProgram::LinearGradient(variant, premultiply));
+                let program             = self.shader_programs.use_program(StandardShaderProgram::LinearGradient(variant, alpha_blend_step, premultiply));
                 if let Some(clip_texture) = clip_texture { program.use_texture(ShaderUniform::ClipTexture, "t_ClipMask", clip_texture, 2); }
 
                 // Set up the texture program
@@ -1200,6 +1261,111 @@ impl GlRenderer {
         }
     }
 
+    ///
+    /// Starts drawing a new clipping region into the stencil buffer
+    ///
+    fn push_stencil_clip(&mut self) {
+        unsafe {
+            if self.stencil_clip_depth == 0 && !self.drawing_stencil_clip {
+                // Render targets only get a stencil buffer when they're first used for stencil clipping
+                if let Some(RenderTargetId(render_id)) = self.active_render_target {
+                    self.render_targets[render_id].as_mut().map(|render_target| render_target.ensure_stencil());
+                }
+
+                // The stencil buffer is reset when the first clipping region is pushed
+                gl::ClearBufferiv(gl::STENCIL, 0, &0);
+                gl::Enable(gl::STENCIL_TEST);
+            }
+
+            // Increment the stencil value for the pixels that are inside all of the current clipping regions, without updating the colour
+            gl::ColorMask(gl::FALSE, gl::FALSE, gl::FALSE, gl::FALSE);
+            gl::StencilFunc(gl::EQUAL, self.stencil_clip_depth as _, 0xff);
+            gl::StencilOp(gl::KEEP, gl::KEEP, gl::INCR);
+        }
+
+        self.drawing_stencil_clip = true;
+    }
+
+    ///
+    /// Finishes drawing a clipping region into the stencil buffer, and clips future rendering against it
+    ///
+    fn end_stencil_clip(&mut self) {
+        if !self.drawing_stencil_clip {
+            return;
+        }
+
+        self.drawing_stencil_clip   = false;
+        self.stencil_clip_depth     = self.stencil_clip_depth.saturating_add(1);
+
+        self.use_stencil_clip();
+    }
+
+    ///
+    /// Removes the most recent clipping region from the stencil buffer
+    ///
+    fn pop_stencil_clip(&mut self) {
+        if self.stencil_clip_depth == 0 {
+            return;
+        }
+
+        self.drawing_stencil_clip   = false;
+        self.stencil_clip_depth     -= 1;
+
+        unsafe {
+            // Any pixel with a stencil value above the new depth is reset to the new depth
+            gl::ColorMask(gl::FALSE, gl::FALSE, gl::FALSE, gl::FALSE);
+            gl::StencilFunc(gl::LESS, self.stencil_clip_depth as _, 0xff);
+            gl::StencilOp(gl::KEEP, gl::KEEP, gl::REPLACE);
+
+            // Draw a quad over the whole render target with the default shader to update the stencil
+            let shader = self.shader_programs.use_program(StandardShaderProgram::default());
+            shader.uniform_location(ShaderUniform::Transform, "transform").map(|transform_uniform| {
+                gl::UniformMatrix4fv(transform_uniform, 1, gl::FALSE, Matrix::identity().to_opengl_matrix().as_ptr());
+            });
+
+            Self::draw_quad((-1.0, -1.0), (1.0, 1.0));
+        }
+
+        // Clip against the remaining regions
+        self.use_stencil_clip();
+
+        // Restore the shader that was in use before the clipping region was removed
+        if let Some(active_shader) = self.active_shader {
+            self.use_shader(active_shader);
+        }
+
+        panic_on_gl_error("Pop stencil clip");
+    }
+
+    ///
+    /// Removes all of the active stencil clipping regions (without updating the stencil buffer)
+    ///
+    fn reset_stencil_clip(&mut self) {
+        self.stencil_clip_depth     = 0;
+        self.drawing_stencil_clip   = false;
+
+        unsafe {
+            gl::Disable(gl::STENCIL_TEST);
+            gl::ColorMask(gl::TRUE, gl::TRUE, gl::TRUE, gl::TRUE);
+        }
+    }
+
+    ///
+    /// Sets up the stencil test so that future rendering is clipped against the active stencil clipping regions
+    ///
+    fn use_stencil_clip(&mut self) {
+        unsafe {
+            gl::ColorMask(gl::TRUE, gl::TRUE, gl::TRUE, gl::TRUE);
+
+            if self.stencil_clip_depth == 0 {
+                gl::Disable(gl::STENCIL_TEST);
+            } else {
+                gl::StencilFunc(gl::EQUAL, self.stencil_clip_depth as _, 0xff);
+                gl::StencilOp(gl::KEEP, gl::KEEP, gl::KEEP);
+            }
+        }
+    }
+
     ///
     /// Sets the transformation matrix for this renderer
     ///
@@ -1239,4 +1405,28 @@ impl GlRenderer {
             gl::Flush();
         }
     }
+
+    ///
+    /// Submits all pending commands to the device and blocks until they have finished executing
+    ///
+    pub fn finish(&mut self) {
+        unsafe {
+            gl::Finish();
+        }
+    }
+}
+
+impl RenderCapabilities for GlRenderer {
+    ///
+    /// Returns true if this renderer implements the specified action
+    ///
+    fn supports_action(&self, action: &RenderAction) -> bool {
+        // The OpenGL renderer supports all of the render actions except compressed or floating point textures and copying texture regions
+        match action {
+            RenderAction::CreateTextureCompressed(_, _, _)  => false,
+            RenderAction::CreateTextureRgbaF16(_, _)        => false,
+            RenderAction::CopyTextureRegion(_, _, _, _, _)  => false,
+            _                                               => true,
+        }
+    }
 }
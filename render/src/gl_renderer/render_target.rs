@@ -6,6 +6,17 @@ use gl;
 
 use std::ops::{Deref};
 
+///
+/// The stencil buffer attached to a render target
+///
+enum StencilAttachment {
+    /// Stencil buffer stored in a render buffer
+    RenderBuffer(gl::types::GLuint),
+
+    /// Stencil buffer stored in a multisampled texture (needed when the colour attachment is a multisampled texture)
+    MultisampledTexture(gl::types::GLuint),
+}
+
 ///
 /// An OpenGL render target
 ///
@@ -19,8 +30,11 @@ pub struct RenderTarget {
     /// The texture attached to the framebuffer (if we're tracking it)
     texture: Option<Texture>,
 
+    /// The stencil buffer attached to the framebuffer (created the first time the render target is used for stencil clipping)
+    stencil: Option<StencilAttachment>,
+
     /// The target type of this render surface
-    _render_type: RenderTargetType,
+    render_type: RenderTargetType,
 
     /// Set to true if this should drop its frame buffer when done
     drop_frame_buffer: bool,
@@ -134,13 +148,79 @@ impl RenderTarget {
                 frame_buffer:       frame_buffer,
                 texture:            texture,
                 render_buffer:      render_buffer,
+                stencil:            None,
                 size:               (width, height),
-                _render_type:       render_type,
+                render_type:        render_type,
                 drop_frame_buffer:  true
             }
         }
     }
 
+    ///
+    /// Ensures that this render target has a stencil buffer, creating one if needed
+    ///
+    /// Stencil buffers are only needed for stencil clipping, so they're created on demand rather than when the render target
+    /// is created. Render targets that reference an existing framebuffer are left alone, as they already have whatever stencil
+    /// buffer the framebuffer was created with.
+    ///
+    pub fn ensure_stencil(&mut self) {
+        if self.stencil.is_some() || !self.drop_frame_buffer {
+            return;
+        }
+
+        unsafe {
+            // Bind this render target's framebuffer while attaching the stencil
+            let mut old_frame_buffer = 0;
+            gl::GetIntegerv(gl::DRAW_FRAMEBUFFER_BINDING, &mut old_frame_buffer);
+            gl::BindFramebuffer(gl::FRAMEBUFFER, self.frame_buffer);
+
+            let (width, height) = self.size;
+            self.stencil        = Some(Self::attach_stencil(width, height, self.render_type));
+
+            gl::BindFramebuffer(gl::FRAMEBUFFER, old_frame_buffer as _);
+            panic_on_gl_error("Create stencil buffer");
+        }
+    }
+
+    ///
+    /// Creates a stencil buffer matching a render target type and attaches it to the currently bound framebuffer
+    ///
+    unsafe fn attach_stencil(width: u16, height: u16, render_type: RenderTargetType) -> StencilAttachment {
+        match render_type {
+            RenderTargetType::MultisampledTexture | RenderTargetType::MonochromeMultisampledTexture => {
+                // Renderbuffers can't be mixed with multisampled textures that don't use fixed sample locations, so the stencil needs to be a texture too
+                let mut stencil_texture = 0;
+                gl::GenTextures(1, &mut stencil_texture);
+
+                gl::BindTexture(gl::TEXTURE_2D_MULTISAMPLE, stencil_texture);
+                gl::TexImage2DMultisample(gl::TEXTURE_2D_MULTISAMPLE, 4, gl::DEPTH24_STENCIL8, width as _, height as _, gl::FALSE);
+                gl::BindTexture(gl::TEXTURE_2D_MULTISAMPLE, 0);
+
+                gl::FramebufferTexture2D(gl::FRAMEBUFFER, gl::DEPTH_STENCIL_ATTACHMENT, gl::TEXTURE_2D_MULTISAMPLE, stencil_texture, 0);
+
+                StencilAttachment::MultisampledTexture(stencil_texture)
+            }
+
+            RenderTargetType::Standard | RenderTargetType::StandardForReading | RenderTargetType::Multisampled | RenderTargetType::Monochrome => {
+                let samples = if render_type == RenderTargetType::Multisampled { 4 } else { 0 };
+
+                let mut stencil_buffer = 0;
+                gl::GenRenderbuffers(1, &mut stencil_buffer);
+
+                let mut old_renderbuffer = 0;
+                gl::GetIntegerv(gl::RENDERBUFFER_BINDING, &mut old_renderbuffer);
+
+                gl::BindRenderbuffer(gl::RENDERBUFFER, stencil_buffer);
+                gl::RenderbufferStorageMultisample(gl::RENDERBUFFER, samples, gl::DEPTH24_STENCIL8, width as gl::types::GLsizei, height as gl::types::GLsizei);
+                gl::FramebufferRenderbuffer(gl::FRAMEBUFFER, gl::DEPTH_STENCIL_ATTACHMENT, gl::RENDERBUFFER, stencil_buffer);
+
+                gl::BindRenderbuffer(gl::RENDERBUFFER, old_renderbuffer as u32);
+
+                StencilAttachment::RenderBuffer(stencil_buffer)
+            }
+        }
+    }
+
     ///
     /// Creates a render target from an existing texture
     ///
@@ -187,8 +267,9 @@ impl RenderTarget {
                 frame_buffer:       frame_buffer,
                 texture:            Some(texture),
                 render_buffer:      render_buffer,
+                stencil:            None,
                 size:               (width as _, height as _),
-                _render_type:       render_type,
+                render_type:        render_type,
                 drop_frame_buffer:  true
             })
         }
@@ -208,8 +289,9 @@ impl RenderTarget {
             frame_buffer:       current_frame_buffer as u32,
             texture:            None,
             render_buffer:      None,
+            stencil:            None,
             drop_frame_buffer:  false,
-            _render_type:       RenderTargetType::Standard,
+            render_type:        RenderTargetType::Standard,
             size:               (width, height)
         }
     }
@@ -239,6 +321,12 @@ impl Drop for RenderTarget {
             if let Some(render_buffer) = self.render_buffer {
                 gl::DeleteRenderbuffers(1, &render_buffer);
             }
+
+            match self.stencil {
+                Some(StencilAttachment::RenderBuffer(stencil_buffer))           => { gl::DeleteRenderbuffers(1, &stencil_buffer); }
+                Some(StencilAttachment::MultisampledTexture(stencil_texture))   => { gl::DeleteTextures(1, &stencil_texture); }
+                None                                                            => { }
+            }
         }
     }
 }
@@ -6,6 +6,7 @@ use std::ffi::{CString};
 ///
 /// The types of shader that we can create
 ///
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum GlShaderType {
     Vertex,
     Fragment
@@ -37,6 +38,9 @@ impl Shader {
     /// Compiles a shader program
     ///
     pub fn compile<'a, AttributeIter: IntoIterator<Item=&'a str>>(program: &str, shader_type: GlShaderType, attributes: AttributeIter) -> Shader {
+        #[cfg(feature="logging")]
+        crate::render_log::log_render_event(crate::render_log::RenderLogEvent::ShaderCompiled { description: format!("{:?}", shader_type) });
+
         unsafe {
             // Create the shader
             let shader_type = match shader_type {
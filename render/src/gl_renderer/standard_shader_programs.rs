@@ -65,7 +65,7 @@ pub enum StandardShaderProgram {
     Texture(StandardShaderVariant, AlphaBlendStep, ColorPostProcessingStep),
 
     /// Uses a 1D texture input to render a linear gradient fill
-    LinearGradient(StandardShaderVariant, ColorPostProcessingStep),
+    LinearGradient(StandardShaderVariant, AlphaBlendStep, ColorPostProcessingStep),
 
     /// Uses a 1D texture to draw dashed lines
     DashedLine(StandardShaderVariant, ColorPostProcessingStep),
@@ -205,26 +205,26 @@ impl StandardShaderProgram {
             use StandardShaderProgram::*;
 
             match program_type {
-                Simple(variant, post_process)               => { Self::load_shader(&simple_vertex, &vec!["a_Pos", "a_Color", "a_TexCoord"], &simple_fragment, &vec![], &variant.defines().into_iter().chain(post_process.defines()).collect()) }
-                Texture(variant, alpha_mode, post_process)  => { Self::load_shader(&texture_vertex, &vec!["a_Pos", "a_Color", "a_TexCoord"], &texture_fragment, &vec![], &variant.defines().into_iter().chain(post_process.defines()).chain(alpha_mode.defines()).collect()) }
-                LinearGradient(variant, post_process)       => { Self::load_shader(&gradient_vertex, &vec!["a_Pos", "a_Color", "a_TexCoord"], &gradient_fragment, &vec![], &variant.defines().into_iter().chain(post_process.defines()).collect()) }
-                DashedLine(variant, post_process)           => { Self::load_shader(&simple_vertex, &vec!["a_Pos", "a_Color", "a_TexCoord"], &dashed_line_fragment, &vec![], &variant.defines().into_iter().chain(post_process.defines()).collect()) }
-
-                MsaaResolve(4, post_process)                => { Self::load_shader(&msaa_vertex, &vec![], &msaa4_resolve, &vec![], &post_process.defines()) }
-                MsaaResolve(_num_samples, _post_process)    => { unimplemented!() }
-
-                PremultiplyAlpha                            => { Self::load_shader(&filter_vertex, &vec![], &premultiply, &vec![], &vec![]) }
-                Blur9Horizontal                             => { Self::load_shader(&filter_vertex, &vec![], &blur9, &vec![], &vec!["FILTER_HORIZ"]) }
-                Blur9Vertical                               => { Self::load_shader(&filter_vertex, &vec![], &blur9, &vec![], &vec!["FILTER_VERT"]) }
-                Blur29Horizontal                            => { Self::load_shader(&filter_vertex, &vec![], &blur29, &vec![], &vec!["FILTER_HORIZ"]) }
-                Blur29Vertical                              => { Self::load_shader(&filter_vertex, &vec![], &blur29, &vec![], &vec!["FILTER_VERT"]) }
-                Blur61Horizontal                            => { Self::load_shader(&filter_vertex, &vec![], &blur61, &vec![], &vec!["FILTER_HORIZ"]) }
-                Blur61Vertical                              => { Self::load_shader(&filter_vertex, &vec![], &blur61, &vec![], &vec!["FILTER_VERT"]) }
-                BlurTextureHorizontal                       => { Self::load_shader(&filter_vertex, &vec![], &blur_texture, &vec![], &vec!["FILTER_HORIZ"]) }
-                BlurTextureVertical                         => { Self::load_shader(&filter_vertex, &vec![], &blur_texture, &vec![], &vec!["FILTER_VERT"]) }
-                FilterAlphaBlend                            => { Self::load_shader(&filter_vertex, &vec![], &filter_alpha_blend, &vec![], &vec![]) }
-                FilterMask                                  => { Self::load_shader(&filter_vertex, &vec![], &filter_mask, &vec![], &vec![]) }
-                FilterDisplacementMap(source_format)        => { Self::load_shader(&filter_vertex, &vec![], &filter_displacement_map, &vec![], &source_format.defines()) }
+                Simple(variant, post_process)                       => { Self::load_shader(&simple_vertex, &vec!["a_Pos", "a_Color", "a_TexCoord"], &simple_fragment, &vec![], &variant.defines().into_iter().chain(post_process.defines()).collect()) }
+                Texture(variant, alpha_mode, post_process)          => { Self::load_shader(&texture_vertex, &vec!["a_Pos", "a_Color", "a_TexCoord"], &texture_fragment, &vec![], &variant.defines().into_iter().chain(post_process.defines()).chain(alpha_mode.defines()).collect()) }
+                LinearGradient(variant, alpha_mode, post_process)   => { Self::load_shader(&gradient_vertex, &vec!["a_Pos", "a_Color", "a_TexCoord"], &gradient_fragment, &vec![], &variant.defines().into_iter().chain(post_process.defines()).chain(alpha_mode.defines()).collect()) }
+                DashedLine(variant, post_process)                   => { Self::load_shader(&simple_vertex, &vec!["a_Pos", "a_Color", "a_TexCoord"], &dashed_line_fragment, &vec![], &variant.defines().into_iter().chain(post_process.defines()).collect()) }
+
+                MsaaResolve(4, post_process)                        => { Self::load_shader(&msaa_vertex, &vec![], &msaa4_resolve, &vec![], &post_process.defines()) }
+                MsaaResolve(_num_samples, _post_process)            => { unimplemented!() }
+
+                PremultiplyAlpha                                    => { Self::load_shader(&filter_vertex, &vec![], &premultiply, &vec![], &vec![]) }
+                Blur9Horizontal                                     => { Self::load_shader(&filter_vertex, &vec![], &blur9, &vec![], &vec!["FILTER_HORIZ"]) }
+                Blur9Vertical                                       => { Self::load_shader(&filter_vertex, &vec![], &blur9, &vec![], &vec!["FILTER_VERT"]) }
+                Blur29Horizontal                                    => { Self::load_shader(&filter_vertex, &vec![], &blur29, &vec![], &vec!["FILTER_HORIZ"]) }
+                Blur29Vertical                                      => { Self::load_shader(&filter_vertex, &vec![], &blur29, &vec![], &vec!["FILTER_VERT"]) }
+                Blur61Horizontal                                    => { Self::load_shader(&filter_vertex, &vec![], &blur61, &vec![], &vec!["FILTER_HORIZ"]) }
+                Blur61Vertical                                      => { Self::load_shader(&filter_vertex, &vec![], &blur61, &vec![], &vec!["FILTER_VERT"]) }
+                BlurTextureHorizontal                               => { Self::load_shader(&filter_vertex, &vec![], &blur_texture, &vec![], &vec!["FILTER_HORIZ"]) }
+                BlurTextureVertical                                 => { Self::load_shader(&filter_vertex, &vec![], &blur_texture, &vec![], &vec!["FILTER_VERT"]) }
+                FilterAlphaBlend                                    => { Self::load_shader(&filter_vertex, &vec![], &filter_alpha_blend, &vec![], &vec![]) }
+                FilterMask                                          => { Self::load_shader(&filter_vertex, &vec![], &filter_mask, &vec![], &vec![]) }
+                FilterDisplacementMap(source_format)                => { Self::load_shader(&filter_vertex, &vec![], &filter_displacement_map, &vec![], &source_format.defines()) }
             }
         }
     }
@@ -8,9 +8,13 @@ mod offscreen;
 #[cfg(feature="profile")]
 mod profiler;
 
+#[cfg(feature="logging")]
+mod render_log;
+
 pub use self::action::*;
 pub use self::buffer::*;
 pub use self::offscreen::*;
+#[cfg(feature="logging")] pub use self::render_log::*;
 #[cfg(feature="gl")] pub use self::gl_renderer::GlRenderer;
 #[cfg(feature="osx-metal")] pub use self::metal_renderer::MetalRenderer;
 #[cfg(feature="render-wgpu")] pub use self::wgpu_renderer::WgpuRenderer;
@@ -13,7 +13,7 @@ use metal;
 
 use std::sync::*;
 use std::ops::{Range};
-use std::collections::{HashMap};
+use std::collections::{HashMap, HashSet};
 
 ///
 /// Renderer that can write to a surface using Apple's Metal API
@@ -43,6 +43,9 @@ pub struct MetalRenderer {
     /// The tetures for this renderer
     textures: Vec<Option<metal::Texture>>,
 
+    /// The IDs of the textures that contain premultiplied colours
+    premultiplied_textures: HashSet<usize>,
+
     /// The cache of render pipeline states used by this renderer
     pipeline_states: HashMap<PipelineConfiguration, metal::RenderPipelineState>
 }
@@ -95,15 +98,16 @@ impl MetalRenderer {
         let shader_library  = device.new_library_with_data(include_bytes![concat!(env!("OUT_DIR"), "/flo.metallib")]).unwrap();
 
         MetalRenderer {
-            device:             device,
-            flip_y:             false,
-            command_queue:      command_queue,
-            vertex_buffers:     vec![],
-            index_buffers:      vec![],
-            render_targets:     vec![],
-            textures:           vec![],
-            shader_library:     shader_library,
-            pipeline_states:    HashMap::new()
+            device:                 device,
+            flip_y:                 false,
+            command_queue:          command_queue,
+            vertex_buffers:         vec![],
+            index_buffers:          vec![],
+            render_targets:         vec![],
+            textures:               vec![],
+            premultiplied_textures: HashSet::new(),
+            shader_library:         shader_library,
+            pipeline_states:        HashMap::new()
         }
     }
 
@@ -116,15 +120,16 @@ impl MetalRenderer {
         let shader_library  = device.new_library_with_data(include_bytes![concat!(env!("OUT_DIR"), "/flo.metallib")]).unwrap();
 
         MetalRenderer {
-            device:             device,
-            flip_y:             flip_y,
-            command_queue:      command_queue,
-            vertex_buffers:     vec![],
-            index_buffers:      vec![],
-            render_targets:     vec![],
-            textures:           vec![],
-            shader_library:     shader_library,
-            pipeline_states:    HashMap::new()
+            device:                 device,
+            flip_y:                 flip_y,
+            command_queue:          command_queue,
+            vertex_buffers:         vec![],
+            index_buffers:          vec![],
+            render_targets:         vec![],
+            textures:               vec![],
+            premultiplied_textures: HashSet::new(),
+            shader_library:         shader_library,
+            pipeline_states:        HashMap::new()
         }
     }
 
@@ -257,18 +262,23 @@ impl MetalRenderer {
                 ShowFrameBuffer                                                                 => { /* This doesn't double-buffer so nothing to do */ }
                 CreateTextureBgra(texture_id, Size2D(width, height))                            => { self.create_bgra_texture(texture_id, width, height); }
                 CreateTextureMono(texture_id, Size2D(width, height))                            => { self.create_mono_texture(texture_id, width, height); }
+                CreateTextureCompressed(_, _, _)                                                => { /* Compressed textures are not supported by this renderer */ }
+                CreateTextureRgbaF16(_, _)                                                      => { /* Floating point textures are not supported by this renderer */ }
+                SetTexturePremultiplied(texture_id, premultiplied)                              => { self.set_texture_premultiplied(texture_id, premultiplied); }
                 Create1DTextureBgra(texture_id, Size1D(width))                                  => { self.create_bgra_1d_texture(texture_id, width); }
                 Create1DTextureMono(texture_id, Size1D(width))                                  => { self.create_mono_1d_texture(texture_id, width); }
                 WriteTextureData(texture_id, Position2D(x1, y1), Position2D(x2, y2), data)      => { self.write_texture_data_2d(texture_id, x1, y1, x2, y2, data); }
                 WriteTexture1D(texture_id, Position1D(x1), Position1D(x2), data)                => { self.write_texture_data_1d(texture_id, x1, x2, data); }
                 CreateMipMaps(texture_id)                                                       => { self.create_mipmaps(texture_id, &mut render_state); }
                 CopyTexture(src_texture, tgt_texture)                                           => { self.copy_texture(src_texture, tgt_texture, &mut render_state); }
+                CopyTextureRegion(_, _, _, _, _)                                                => { /* Copying texture regions is not supported by this renderer */ }
                 FilterTexture(texture, filter)                                                  => { self.filter_texture(texture, filter, &mut render_state); }
                 FreeTexture(texture_id)                                                         => { self.free_texture(texture_id); }
                 Clear(color)                                                                    => { self.clear(color, &mut render_state); }
                 UseShader(shader_type)                                                          => { self.use_shader(shader_type, &mut render_state); }
                 DrawTriangles(buffer_id, buffer_range)                                          => { self.draw_triangles(buffer_id, buffer_range, &mut render_state); }
                 DrawIndexedTriangles(vertex_buffer, index_buffer, num_vertices)                 => { self.draw_indexed_triangles(vertex_buffer, index_buffer, num_vertices, &mut render_state); }
+                PushStencilClip | EndStencilClip | PopStencilClip                               => { /* Stencil clipping is not supported by this renderer */ }
             }
         }
 
@@ -290,6 +300,17 @@ impl MetalRenderer {
         command_buffer.commit();
     }
 
+    ///
+    /// Blocks until the GPU has finished executing all of the command buffers committed by this renderer
+    ///
+    pub fn finish(&mut self) {
+        // Command buffers complete in order, so waiting for an empty buffer waits for everything committed before it
+        let command_buffer = self.command_queue.new_command_buffer();
+
+        command_buffer.commit();
+        command_buffer.wait_until_completed();
+    }
+
     ///
     /// Sets the active transformation matrix
     ///
@@ -394,9 +415,10 @@ impl MetalRenderer {
         // Create the render target
         let new_render_target = RenderTarget::new(&self.device, width, height, render_target_type);
 
-        // Store in this object
+        // Store in this object (render targets have premultiplied alpha)
         self.textures[texture_id]       = Some(new_render_target.render_texture().clone());
         self.render_targets[render_id]  = Some(new_render_target);
+        self.premultiplied_textures.insert(texture_id);
     }
 
     ///
@@ -536,6 +558,7 @@ impl MetalRenderer {
         }
 
         self.textures[texture_id] = Some(texture);
+        self.premultiplied_textures.remove(&texture_id);
     }
 
     ///
@@ -758,7 +781,12 @@ impl MetalRenderer {
         self.setup_command_encoder(state);
 
         // Store the target texture
+        let src_is_premultiplied = self.premultiplied_textures.contains(&src_texture_id);
         self.store_texture(tgt_texture_id, tgt_texture);
+
+        if src_is_premultiplied {
+            self.premultiplied_textures.insert(tgt_texture_id);
+        }
     }
 
     ///
@@ -775,6 +803,21 @@ impl MetalRenderer {
         if texture_id < self.textures.len() {
             self.textures[texture_id] = None;
         }
+
+        self.premultiplied_textures.remove(&texture_id);
+    }
+
+    ///
+    /// Sets whether or not a texture contains premultiplied colours
+    ///
+    fn set_texture_premultiplied(&mut self, TextureId(texture_id): TextureId, premultiplied: bool) {
+        if let Some(Some(_)) = self.textures.get(texture_id) {
+            if premultiplied {
+                self.premultiplied_textures.insert(texture_id);
+            } else {
+                self.premultiplied_textures.remove(&texture_id);
+            }
+        }
     }
 
     ///
@@ -793,10 +836,11 @@ impl MetalRenderer {
     ///
     fn use_shader(&mut self, shader_type: ShaderType, state: &mut RenderState) {
         // Reset the current shader state
-        state.pipeline_config.vertex_shader = String::from("simple_vertex");
-        state.fill_texture                  = None;
-        state.clip_texture                  = None;
-        state.texture_transform             = None;
+        state.pipeline_config.vertex_shader             = String::from("simple_vertex");
+        state.pipeline_config.source_is_premultiplied   = false;
+        state.fill_texture                              = None;
+        state.clip_texture                              = None;
+        state.texture_transform                         = None;
 
         // Update the state according to the shader type
         match shader_type {
@@ -834,18 +878,24 @@ impl MetalRenderer {
             }
 
             ShaderType::LinearGradient { texture: TextureId(gradient_texture), texture_transform, repeat, alpha, clip_texture: None } => { 
+                let premultiplied                       = self.premultiplied_textures.contains(&gradient_texture);
+
                 state.pipeline_config.vertex_shader     = String::from("gradient_vertex");
-                state.pipeline_config.fragment_shader   = String::from("gradient_fragment");
+                state.pipeline_config.fragment_shader   = if premultiplied { String::from("gradient_fragment") } else { String::from("gradient_straight_fragment") };
                 state.texture_transform                 = Some(MatrixBuffer::from_matrix(&self.device, texture_transform));
+                state.pipeline_config.source_is_premultiplied = premultiplied;
                 state.texture_alpha                     = Some(alpha as _);
 
                 state.fill_texture                      = self.textures[gradient_texture].clone();
             }
 
             ShaderType::LinearGradient { texture: TextureId(gradient_texture), texture_transform, repeat, alpha, clip_texture: Some(TextureId(clip_texture)) } => { 
+                let premultiplied                       = self.premultiplied_textures.contains(&gradient_texture);
+
                 state.pipeline_config.vertex_shader     = String::from("gradient_vertex");
-                state.pipeline_config.fragment_shader   = String::from("gradient_clip_mask_multisample_fragment");
+                state.pipeline_config.fragment_shader   = if premultiplied { String::from("gradient_clip_mask_multisample_fragment") } else { String::from("gradient_straight_clip_mask_multisample_fragment") };
                 state.texture_transform                 = Some(MatrixBuffer::from_matrix(&self.device, texture_transform));
+                state.pipeline_config.source_is_premultiplied = premultiplied;
                 state.texture_alpha                     = Some(alpha as _);
 
                 state.fill_texture                      = self.textures[gradient_texture].clone();
@@ -884,6 +934,25 @@ impl MetalRenderer {
     }
 }
 
+impl RenderCapabilities for MetalRenderer {
+    ///
+    /// Returns true if this renderer implements the specified action
+    ///
+    fn supports_action(&self, action: &RenderAction) -> bool {
+        match action {
+            RenderAction::FilterTexture(_, _)                       => false,
+            RenderAction::UseShader(ShaderType::DashedLine { .. })  => false,
+            RenderAction::CreateTextureCompressed(_, _, _)          => false,
+            RenderAction::CreateTextureRgbaF16(_, _)                => false,
+            RenderAction::CopyTextureRegion(_, _, _, _, _)          => false,
+            RenderAction::PushStencilClip                           |
+            RenderAction::EndStencilClip                            |
+            RenderAction::PopStencilClip                            => false,
+            _                                                       => true,
+        }
+    }
+}
+
 
 ///
 /// Converts a canvas transform to a rendering matrix
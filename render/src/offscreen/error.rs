@@ -35,3 +35,12 @@ pub enum RenderInitError {
     /// Could not set the active context
     ContextDidNotStart
 }
+
+///
+/// Errors that can happen when trying to create an offscreen render target
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum RenderTargetError {
+    /// The requested width or height is larger than the maximum allowed dimension
+    TooLarge { width: usize, height: usize, max_dimension: usize },
+}
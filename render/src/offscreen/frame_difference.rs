@@ -0,0 +1,94 @@
+///
+/// The result of comparing two rendered RGBA frames (such as the output of `OffscreenRenderTarget::realize()`)
+///
+/// This is useful for visual regression tests: render a scene and compare it against a reference frame, allowing for
+/// small differences caused by different GPUs or drivers with the tolerance.
+///
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct FrameDifference {
+    /// The largest difference found between the same channel of a pixel in the two frames
+    pub max_channel_delta: u8,
+
+    /// The number of pixels where at least one channel differs by more than the tolerance
+    pub differing_pixels: usize,
+
+    /// An RGBA image the same size as the frames: the colour channels are the absolute differences between the two frames, and
+    /// the alpha channel is 255 for pixels that differ by more than the tolerance and 0 for pixels that match
+    pub diff_image: Vec<u8>,
+}
+
+impl FrameDifference {
+    ///
+    /// Compares two RGBA frames of the same size, counting pixels as different if any channel differs by more than `tolerance`
+    ///
+    pub fn compare(frame_a: &[u8], frame_b: &[u8], tolerance: u8) -> FrameDifference {
+        assert!(frame_a.len() == frame_b.len(), "Cannot compare frames of different sizes ({} and {} bytes)", frame_a.len(), frame_b.len());
+
+        let mut max_channel_delta   = 0;
+        let mut differing_pixels    = 0;
+        let mut diff_image          = Vec::with_capacity(frame_a.len());
+
+        for (pixel_a, pixel_b) in frame_a.chunks_exact(4).zip(frame_b.chunks_exact(4)) {
+            let delta               = [0, 1, 2, 3].map(|channel| (pixel_a[channel] as i16 - pixel_b[channel] as i16).unsigned_abs() as u8);
+            let pixel_max_delta     = delta.iter().copied().max().unwrap_or(0);
+            let differs             = pixel_max_delta > tolerance;
+
+            max_channel_delta       = max_channel_delta.max(pixel_max_delta);
+            if differs { differing_pixels += 1; }
+
+            diff_image.extend_from_slice(&[delta[0], delta[1], delta[2], if differs { 255 } else { 0 }]);
+        }
+
+        FrameDifference {
+            max_channel_delta:  max_channel_delta,
+            differing_pixels:   differing_pixels,
+            diff_image:         diff_image,
+        }
+    }
+
+    ///
+    /// True if no pixels differ by more than the tolerance
+    ///
+    pub fn is_match(&self) -> bool {
+        self.differing_pixels == 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn gradient_frame() -> Vec<u8> {
+        (0..(64*64)).flat_map(|idx| [(idx % 64) as u8 * 4, (idx / 64) as u8 * 4, 128, 255]).collect()
+    }
+
+    #[test]
+    fn frame_matches_itself() {
+        let frame       = gradient_frame();
+        let difference  = FrameDifference::compare(&frame, &frame, 0);
+
+        assert!(difference.is_match());
+        assert!(difference.max_channel_delta == 0);
+        assert!(difference.differing_pixels == 0);
+        assert!(difference.diff_image.iter().all(|channel| *channel == 0));
+    }
+
+    #[test]
+    fn one_pixel_changed() {
+        let frame           = gradient_frame();
+        let mut changed     = frame.clone();
+        changed[(10 + 20*64)*4 + 2] = 100;
+
+        let difference      = FrameDifference::compare(&frame, &changed, 0);
+
+        assert!(!difference.is_match());
+        assert!(difference.max_channel_delta == 28, "{}", difference.max_channel_delta);
+        assert!(difference.differing_pixels == 1, "{}", difference.differing_pixels);
+        assert!(difference.diff_image[(10 + 20*64)*4..(10 + 20*64)*4+4] == [0, 0, 28, 255]);
+
+        // The change is ignored if it's within the tolerance
+        let difference      = FrameDifference::compare(&frame, &changed, 28);
+        assert!(difference.is_match());
+        assert!(difference.max_channel_delta == 28);
+    }
+}
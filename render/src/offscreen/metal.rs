@@ -90,6 +90,14 @@ impl OffscreenRenderTarget for MetalOffscreenRenderTarget {
         buffer.wait_until_completed();
     }
 
+    ///
+    /// Blocks until all of the rendering sent to this render target has been completed by the GPU
+    ///
+    fn finish(&mut self) {
+        // Rendering already waits for the command buffer to complete, but this will also wait for anything else using the renderer's queue
+        self.renderer.finish();
+    }
+
     ///
     /// Consumes this render target and returns the realized pixels as a byte array
     ///
@@ -106,3 +114,10 @@ impl OffscreenRenderTarget for MetalOffscreenRenderTarget {
         result
     }
 }
+
+impl RenderCapabilities for MetalOffscreenRenderTarget {
+    #[inline]
+    fn supports_action(&self, action: &RenderAction) -> bool {
+        self.renderer.supports_action(action)
+    }
+}
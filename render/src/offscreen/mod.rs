@@ -1,5 +1,7 @@
 mod error;
 mod offscreen_trait;
+mod frame_difference;
+mod run_length_image;
 
 #[cfg(feature="opengl")]                                                    mod opengl;
 #[cfg(all(feature="opengl", target_os = "windows"))]                        mod opengl_wgl_init;
@@ -10,6 +12,8 @@ mod offscreen_trait;
 
 pub use self::error::*;
 pub use self::offscreen_trait::*;
+pub use self::frame_difference::*;
+pub use self::run_length_image::*;
 
 #[cfg(all(feature="opengl", target_os = "windows"))]                        pub use self::opengl_wgl_init::*;
 #[cfg(all(feature="opengl", target_os = "linux"))]                          pub use self::opengl_egl_init::*;
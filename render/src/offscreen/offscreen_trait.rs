@@ -1,5 +1,11 @@
+use super::error::*;
 use crate::action::*;
 
+///
+/// The default maximum width or height of an offscreen render target
+///
+pub const DEFAULT_MAX_RENDER_DIMENSION: usize = 16384;
+
 ///
 /// Trait implemented by FlowBetween offscreen render targets
 ///
@@ -9,6 +15,11 @@ pub trait OffscreenRenderTarget {
     ///
     fn render<ActionIter: IntoIterator<Item=RenderAction>>(&mut self, actions: ActionIter);
 
+    ///
+    /// Blocks until all of the rendering sent to this render target has been completed by the GPU
+    ///
+    fn finish(&mut self);
+
     ///
     /// Consumes this render target and returns the realized pixels as a byte array
     ///
@@ -19,10 +30,25 @@ pub trait OffscreenRenderTarget {
 /// Trait implemented by objects that represent a offscreen drawing context
 ///
 pub trait OffscreenRenderContext {
-    type RenderTarget: OffscreenRenderTarget;
+    type RenderTarget: OffscreenRenderTarget + RenderCapabilities;
 
     ///
     /// Creates a new render target for this context
     ///
     fn create_render_target(&mut self, width: usize, height: usize) -> Self::RenderTarget;
+
+    ///
+    /// Creates a new render target for this context, returning an error instead of allocating it if the width or height is
+    /// larger than `max_dimension`
+    ///
+    /// This should be used instead of `create_render_target()` when the size comes from an untrusted source, as an oversized
+    /// render target can exhaust the available memory. `DEFAULT_MAX_RENDER_DIMENSION` is a reasonable limit for most uses.
+    ///
+    fn try_create_render_target(&mut self, width: usize, height: usize, max_dimension: usize) -> Result<Self::RenderTarget, RenderTargetError> {
+        if width > max_dimension || height > max_dimension {
+            Err(RenderTargetError::TooLarge { width, height, max_dimension })
+        } else {
+            Ok(self.create_render_target(width, height))
+        }
+    }
 }
@@ -57,6 +57,12 @@ impl OffscreenRenderTarget for OpenGlOffscreenRenderer {
             let mut previous_frame_buffer = 0;
             gl::GetIntegerv(gl::DRAW_FRAMEBUFFER_BINDING, &mut previous_frame_buffer);
 
+            // The main render target only needs a stencil buffer if stencil clipping is used
+            let actions = actions.into_iter().collect::<Vec<_>>();
+            if actions.iter().any(|action| matches!(action, RenderAction::PushStencilClip)) {
+                self.main_render_target.ensure_stencil();
+            }
+
             // Render to the main render target
             gl::BindFramebuffer(gl::FRAMEBUFFER, *self.main_render_target);
             self.renderer.prepare_to_render_to_active_framebuffer(self.width, self.height);
@@ -72,6 +78,13 @@ impl OffscreenRenderTarget for OpenGlOffscreenRenderer {
         }
     }
 
+    ///
+    /// Blocks until all of the rendering sent to this render target has been completed by the GPU
+    ///
+    fn finish(&mut self) {
+        self.renderer.finish();
+    }
+
     ///
     /// Consumes this render target and returns the realized pixels as a byte array
     ///
@@ -92,3 +105,10 @@ impl OffscreenRenderTarget for OpenGlOffscreenRenderer {
         pixels
     }
 }
+
+impl RenderCapabilities for OpenGlOffscreenRenderer {
+    #[inline]
+    fn supports_action(&self, action: &RenderAction) -> bool {
+        self.renderer.supports_action(action)
+    }
+}
@@ -0,0 +1,131 @@
+///
+/// A run of identical pixels in a run-length encoded image
+///
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct PixelRun {
+    /// The number of pixels in this run
+    pub length: u32,
+
+    /// The RGBA value of every pixel in this run
+    pub pixel: [u8; 4],
+}
+
+///
+/// An RGBA image stored as runs of identical pixels
+///
+/// Runs continue from the end of one row to the start of the next, so large empty areas such as the background of a
+/// diagram or a user interface encode to very few runs. This is a compact way to store or send the output of an offscreen
+/// render target (eg, `RunLengthImage::encode(width, height, &render_target.realize())`) when most of the frame is empty.
+///
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct RunLengthImage {
+    /// The width of the image in pixels
+    width: usize,
+
+    /// The height of the image in pixels
+    height: usize,
+
+    /// The runs making up the image, in the same order as the pixels
+    runs: Vec<PixelRun>,
+}
+
+impl RunLengthImage {
+    ///
+    /// Encodes an image from a set of RGBA pixels (as returned by `OffscreenRenderTarget::realize()`)
+    ///
+    pub fn encode(width: usize, height: usize, pixels: &[u8]) -> RunLengthImage {
+        assert!(pixels.len() == width * height * 4, "Expected {} bytes for a {}x{} image, but found {}", width * height * 4, width, height, pixels.len());
+
+        let mut runs: Vec<PixelRun> = vec![];
+
+        for pixel in pixels.chunks_exact(4) {
+            let pixel = [pixel[0], pixel[1], pixel[2], pixel[3]];
+
+            match runs.last_mut() {
+                Some(last_run) if last_run.pixel == pixel && last_run.length < u32::MAX  => { last_run.length += 1; }
+                _                                                                       => { runs.push(PixelRun { length: 1, pixel: pixel }); }
+            }
+        }
+
+        RunLengthImage {
+            width:  width,
+            height: height,
+            runs:   runs,
+        }
+    }
+
+    ///
+    /// Decodes this image back to a set of RGBA pixels
+    ///
+    pub fn decode(&self) -> Vec<u8> {
+        let mut pixels = Vec::with_capacity(self.width * self.height * 4);
+
+        for run in self.runs.iter() {
+            for _ in 0..run.length {
+                pixels.extend_from_slice(&run.pixel);
+            }
+        }
+
+        pixels
+    }
+
+    ///
+    /// The width of this image in pixels
+    ///
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    ///
+    /// The height of this image in pixels
+    ///
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    ///
+    /// The runs of pixels that make up this image
+    ///
+    pub fn runs(&self) -> &[PixelRun] {
+        &self.runs
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn mostly_empty_frame_encodes_compactly() {
+        // A 200x200 transparent frame with a 10x10 red square in the middle
+        let (width, height) = (200, 200);
+        let mut pixels      = vec![0u8; width * height * 4];
+
+        for y in 95..105 {
+            for x in 95..105 {
+                let pos = (x + y*width) * 4;
+                pixels[pos..pos+4].copy_from_slice(&[255, 0, 0, 255]);
+            }
+        }
+
+        let image = RunLengthImage::encode(width, height, &pixels);
+
+        // One run of red per row of the square, and the transparent runs in between
+        assert!(image.runs().len() == 21, "{} runs", image.runs().len());
+        assert!(image.runs().len() < (width * height) / 100);
+
+        assert!(image.decode() == pixels);
+    }
+
+    #[test]
+    fn noisy_frame_decodes_exactly() {
+        let (width, height) = (17, 13);
+        let pixels          = (0..(width * height * 4)).map(|idx| ((idx * 7919) % 3) as u8).collect::<Vec<_>>();
+
+        let image           = RunLengthImage::encode(width, height, &pixels);
+
+        assert!(image.width() == 17);
+        assert!(image.height() == 13);
+        assert!(image.decode() == pixels);
+    }
+}
@@ -1,9 +1,11 @@
-#[cfg(all(test, any(feature = "opengl", feature = "osx-metal")))]
+#[cfg(all(test, any(feature = "opengl", feature = "osx-metal", feature = "render-wgpu")))]
 mod test {
     use crate::action::*;
     use crate::buffer::*;
     use crate::offscreen::*;
 
+    use std::sync::*;
+
     #[test]
     fn clear_offscreen() {
         // Initialise offscreen rendering
@@ -263,4 +265,351 @@ mod test {
             }
         }
     }
+
+    #[test]
+    fn report_unsupported_filter_texture() {
+        // Initialise offscreen rendering
+        let context         = initialize_offscreen_rendering();
+        let mut context     = match context {
+            Ok(context)     => context,
+            Err(RenderInitError::CannotCreateGraphicsDevice)    => { println!("Test not run: graphics device unavailable"); return; }
+            Err(other)      => { panic!("Unexpected error: {:?}", other); }
+        };
+
+        // Check a set of actions that includes a texture filter
+        use self::RenderAction::*;
+
+        let renderer        = context.create_render_target(100, 100);
+        let actions         = vec![
+            CreateTextureBgra(TextureId(0), Size2D(100, 100)),
+            Clear(Rgba8([128, 128, 128, 255])),
+            FilterTexture(TextureId(0), vec![TextureFilter::AlphaBlend(0.5)]),
+        ];
+
+        let unsupported     = renderer.unsupported_actions(&actions);
+
+        // The metal renderer does not implement texture filters yet, but the OpenGL renderer does
+        #[cfg(feature="osx-metal")]
+        assert!(unsupported == vec![RenderActionType::FilterTexture]);
+
+        #[cfg(not(feature="osx-metal"))]
+        assert!(unsupported.is_empty());
+    }
+
+    #[test]
+    fn nested_stencil_clips() {
+        // Initialise offscreen rendering
+        let context         = initialize_offscreen_rendering();
+        let mut context     = match context {
+            Ok(context)     => context,
+            Err(RenderInitError::CannotCreateGraphicsDevice)    => { println!("Test not run: graphics device unavailable"); return; }
+            Err(other)      => { panic!("Unexpected error: {:?}", other); }
+        };
+
+        use self::RenderAction::*;
+
+        let mut renderer    = context.create_render_target(100, 100);
+        if !renderer.supports_action(&PushStencilClip) {
+            println!("Test not run: stencil clipping is not supported by this renderer");
+            return;
+        }
+
+        // Rectangles are made of two triangles
+        let rect            = |(min_x, min_y): (f32, f32), (max_x, max_y): (f32, f32), color: [u8; 4]| vec![
+            Vertex2D { pos: [min_x, min_y], tex_coord: [0.0, 0.0], color: color },
+            Vertex2D { pos: [max_x, min_y], tex_coord: [0.0, 0.0], color: color },
+            Vertex2D { pos: [min_x, max_y], tex_coord: [0.0, 0.0], color: color },
+            Vertex2D { pos: [max_x, min_y], tex_coord: [0.0, 0.0], color: color },
+            Vertex2D { pos: [min_x, max_y], tex_coord: [0.0, 0.0], color: color },
+            Vertex2D { pos: [max_x, max_y], tex_coord: [0.0, 0.0], color: color },
+        ];
+
+        let black           = [0, 0, 0, 255];
+        let white           = [255, 255, 255, 255];
+        let red             = [255, 0, 0, 255];
+
+        renderer.render(vec![
+            Clear(Rgba8([128, 128, 128, 255])),
+            UseShader(ShaderType::Simple { clip_texture: None }),
+
+            CreateVertex2DBuffer(VertexBufferId(0), rect((-0.5, -1.0), (0.5, 1.0), black)),
+            CreateVertex2DBuffer(VertexBufferId(1), rect((-1.0, -0.5), (1.0, 0.5), black)),
+            CreateVertex2DBuffer(VertexBufferId(2), rect((-1.0, -1.0), (1.0, 1.0), black)),
+            CreateVertex2DBuffer(VertexBufferId(3), rect((-1.0, 0.8), (1.0, 1.0), white)),
+            CreateVertex2DBuffer(VertexBufferId(4), rect((-1.0, -1.0), (1.0, -0.8), red)),
+
+            // Clip to a vertical strip, then to a horizontal strip inside it, and fill everything: only the intersection should be filled
+            PushStencilClip,
+            DrawTriangles(VertexBufferId(0), 0..6),
+            EndStencilClip,
+            PushStencilClip,
+            DrawTriangles(VertexBufferId(1), 0..6),
+            EndStencilClip,
+            DrawTriangles(VertexBufferId(2), 0..6),
+
+            // Popping the horizontal strip should leave the vertical strip as the clip region
+            PopStencilClip,
+            DrawTriangles(VertexBufferId(3), 0..6),
+
+            // Popping the vertical strip should remove the clipping entirely
+            PopStencilClip,
+            DrawTriangles(VertexBufferId(4), 0..6),
+        ]);
+
+        let image           = renderer.realize();
+
+        assert!(image.len() == 100*100*4);
+
+        for y in 0..100 {
+            for x in 0..100 {
+                let pos         = (x + y*100) * 4;
+                let pixel       = (image[pos], image[pos+1], image[pos+2], image[pos+3]);
+
+                let in_strip    = x >= 25 && x < 75;
+                let expected    = if y < 10 {
+                    (255, 0, 0, 255)
+                } else if in_strip && y >= 25 && y < 75 {
+                    (0, 0, 0, 255)
+                } else if in_strip && y >= 90 {
+                    (255, 255, 255, 255)
+                } else {
+                    (128, 128, 128, 255)
+                };
+
+                if pixel != expected {
+                    println!("{} {} {:?} {:?}", x, y, pixel, expected);
+                }
+
+                assert!(pixel == expected);
+            }
+        }
+    }
+
+    #[test]
+    fn clear_render_target_leaves_frame_buffer() {
+        // Initialise offscreen rendering
+        let context         = initialize_offscreen_rendering();
+        let mut context     = match context {
+            Ok(context)     => context,
+            Err(RenderInitError::CannotCreateGraphicsDevice)    => { println!("Test not run: graphics device unavailable"); return; }
+            Err(RenderInitError::CannotOpenGraphicsDevice)      => { println!("Test not run: graphics device unavailable"); return; }
+            Err(other)      => { panic!("Unexpected error: {:?}", other); }
+        };
+
+        // Clear the frame buffer, then clear an offscreen render target to a different colour
+        use self::RenderAction::*;
+
+        let mut renderer    = context.create_render_target(100, 100);
+        renderer.render(vec![
+            Clear(Rgba8([128, 128, 128, 255])),
+
+            CreateRenderTarget(RenderTargetId(0), TextureId(0), Size2D(100, 100), RenderTargetType::Standard),
+            SelectRenderTarget(RenderTargetId(0)),
+            Clear(Rgba8([255, 0, 0, 255])),
+
+            RenderToFrameBuffer,
+        ]);
+
+        let image           = renderer.realize();
+
+        // Clearing the render target should not have affected the frame buffer
+        assert!(image.len() == 100*100*4);
+        assert!(image.chunks(4).all(|pixel| pixel == &[128, 128, 128, 255]), "{:?}", &image[0..4]);
+    }
+
+    #[test]
+    fn finish_waits_for_all_draws() {
+        // Initialise offscreen rendering
+        let context         = initialize_offscreen_rendering();
+        let mut context     = match context {
+            Ok(context)     => context,
+            Err(RenderInitError::CannotCreateGraphicsDevice)    => { println!("Test not run: graphics device unavailable"); return; }
+            Err(RenderInitError::CannotOpenGraphicsDevice)      => { println!("Test not run: graphics device unavailable"); return; }
+            Err(other)      => { panic!("Unexpected error: {:?}", other); }
+        };
+
+        // Cover the render target with two triangles, submitted separately
+        use self::RenderAction::*;
+
+        let mut renderer    = context.create_render_target(100, 100);
+        let black           = [0, 0, 0, 255];
+        renderer.render(vec![
+            Clear(Rgba8([128, 128, 128, 255])),
+            UseShader(ShaderType::Simple { clip_texture: None }),
+            CreateVertex2DBuffer(VertexBufferId(0), vec![
+                Vertex2D { pos: [-1.0, -1.0],   tex_coord: [0.0, 0.0], color: black },
+                Vertex2D { pos: [1.0, 1.0],     tex_coord: [0.0, 0.0], color: black },
+                Vertex2D { pos: [1.0, -1.0],    tex_coord: [0.0, 0.0], color: black },
+            ]),
+            DrawTriangles(VertexBufferId(0), 0..3)
+        ]);
+        renderer.render(vec![
+            CreateVertex2DBuffer(VertexBufferId(1), vec![
+                Vertex2D { pos: [-1.0, -1.0],   tex_coord: [0.0, 0.0], color: black },
+                Vertex2D { pos: [-1.0, 1.0],    tex_coord: [0.0, 0.0], color: black },
+                Vertex2D { pos: [1.0, 1.0],     tex_coord: [0.0, 0.0], color: black },
+            ]),
+            DrawTriangles(VertexBufferId(1), 0..3)
+        ]);
+        renderer.finish();
+
+        let image           = renderer.realize();
+
+        // Both draws should be in the result
+        assert!(image.len() == 100*100*4);
+        assert!(image.chunks(4).all(|pixel| pixel == &black), "{:?}", image.chunks(4).filter(|pixel| pixel != &black).next());
+    }
+
+    #[test]
+    fn transparent_gradient_has_no_dark_fringe() {
+        // Initialise offscreen rendering
+        let context         = initialize_offscreen_rendering();
+        let mut context     = match context {
+            Ok(context)     => context,
+            Err(RenderInitError::CannotCreateGraphicsDevice)    => { println!("Test not run: graphics device unavailable"); return; }
+            Err(RenderInitError::CannotOpenGraphicsDevice)      => { println!("Test not run: graphics device unavailable"); return; }
+            Err(other)      => { panic!("Unexpected error: {:?}", other); }
+        };
+
+        // Gradient textures are premultiplied: this goes from transparent to opaque white
+        let gradient        = (0..256).flat_map(|idx| vec![idx as u8; 4]).collect::<Vec<_>>();
+
+        // Map x coordinates from -1..1 to 0..1 for the gradient
+        let transform       = Matrix([
+            [0.5, 0.0, 0.0, 0.5],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0]
+        ]);
+
+        // Fill the whole of a white render target with the gradient
+        use self::RenderAction::*;
+
+        let mut renderer    = context.create_render_target(100, 100);
+        let white           = [255, 255, 255, 255];
+        renderer.render(vec![
+            Create1DTextureBgra(TextureId(0), Size1D(256)),
+            SetTexturePremultiplied(TextureId(0), true),
+            WriteTexture1D(TextureId(0), Position1D(0), Position1D(256), Arc::new(gradient)),
+            CreateMipMaps(TextureId(0)),
+
+            Clear(Rgba8(white)),
+            BlendMode(crate::action::BlendMode::SourceOver),
+            UseShader(ShaderType::LinearGradient { texture: TextureId(0), texture_transform: transform, repeat: false, alpha: 1.0, clip_texture: None }),
+            CreateVertex2DBuffer(VertexBufferId(0), vec![
+                Vertex2D { pos: [-1.0, -1.0],   tex_coord: [0.0, 0.0], color: white },
+                Vertex2D { pos: [1.0, 1.0],     tex_coord: [0.0, 0.0], color: white },
+                Vertex2D { pos: [1.0, -1.0],    tex_coord: [0.0, 0.0], color: white },
+                Vertex2D { pos: [-1.0, -1.0],   tex_coord: [0.0, 0.0], color: white },
+                Vertex2D { pos: [-1.0, 1.0],    tex_coord: [0.0, 0.0], color: white },
+                Vertex2D { pos: [1.0, 1.0],     tex_coord: [0.0, 0.0], color: white },
+            ]),
+            DrawTriangles(VertexBufferId(0), 0..6)
+        ]);
+
+        let image           = renderer.realize();
+
+        // White blended over white should stay white everywhere (straight alpha would darken the middle of the gradient)
+        assert!(image.len() == 100*100*4);
+        for pixel in image.chunks(4) {
+            assert!(pixel.iter().all(|component| *component >= 253), "{:?}", pixel);
+        }
+    }
+
+    #[test]
+    fn straight_alpha_gradient() {
+        // Initialise offscreen rendering
+        let context         = initialize_offscreen_rendering();
+        let mut context     = match context {
+            Ok(context)     => context,
+            Err(RenderInitError::CannotCreateGraphicsDevice)    => { println!("Test not run: graphics device unavailable"); return; }
+            Err(RenderInitError::CannotOpenGraphicsDevice)      => { println!("Test not run: graphics device unavailable"); return; }
+            Err(other)      => { panic!("Unexpected error: {:?}", other); }
+        };
+
+        // Gradient textures that aren't marked as premultiplied have straight alpha: this is white at 50% alpha
+        use self::RenderAction::*;
+
+        let mut renderer    = context.create_render_target(100, 100);
+        let white           = [255, 255, 255, 255];
+        renderer.render(vec![
+            Create1DTextureBgra(TextureId(0), Size1D(1)),
+            WriteTexture1D(TextureId(0), Position1D(0), Position1D(1), Arc::new(vec![255, 255, 255, 128])),
+
+            Clear(Rgba8([0, 0, 0, 255])),
+            BlendMode(crate::action::BlendMode::SourceOver),
+            UseShader(ShaderType::LinearGradient { texture: TextureId(0), texture_transform: Matrix::identity(), repeat: false, alpha: 1.0, clip_texture: None }),
+            CreateVertex2DBuffer(VertexBufferId(0), vec![
+                Vertex2D { pos: [-1.0, -1.0],   tex_coord: [0.0, 0.0], color: white },
+                Vertex2D { pos: [1.0, 1.0],     tex_coord: [0.0, 0.0], color: white },
+                Vertex2D { pos: [1.0, -1.0],    tex_coord: [0.0, 0.0], color: white },
+                Vertex2D { pos: [-1.0, -1.0],   tex_coord: [0.0, 0.0], color: white },
+                Vertex2D { pos: [-1.0, 1.0],    tex_coord: [0.0, 0.0], color: white },
+                Vertex2D { pos: [1.0, 1.0],     tex_coord: [0.0, 0.0], color: white },
+            ]),
+            DrawTriangles(VertexBufferId(0), 0..6)
+        ]);
+
+        let image           = renderer.realize();
+
+        // Blending over black should give a mid-grey (treating the texture as premultiplied would produce white)
+        assert!(image.len() == 100*100*4);
+        for pixel in image.chunks(4) {
+            assert!(pixel[0..3].iter().all(|component| (*component as i32 - 128).abs() <= 2), "{:?}", pixel);
+        }
+    }
+
+    #[cfg(all(feature="logging", not(feature="osx-metal")))]
+    #[test]
+    fn logging_reports_render_events() {
+        use std::sync::*;
+
+        // Logger that records the messages logged by the renderer
+        struct TestLogger(Mutex<Vec<String>>);
+
+        impl log::Log for TestLogger {
+            fn enabled(&self, metadata: &log::Metadata) -> bool { metadata.target() == "flo_render" }
+            fn log(&self, record: &log::Record) { if self.enabled(record.metadata()) { self.0.lock().unwrap().push(format!("{}", record.args())); } }
+            fn flush(&self) { }
+        }
+
+        static LOGGER: TestLogger = TestLogger(Mutex::new(vec![]));
+        log::set_logger(&LOGGER).ok();
+        log::set_max_level(log::LevelFilter::Debug);
+
+        // Initialise offscreen rendering
+        let context         = initialize_offscreen_rendering();
+        let mut context     = match context {
+            Ok(context)     => context,
+            Err(RenderInitError::CannotCreateGraphicsDevice)    => { println!("Test not run: graphics device unavailable"); return; }
+            Err(RenderInitError::CannotOpenGraphicsDevice)      => { println!("Test not run: graphics device unavailable"); return; }
+            Err(other)      => { panic!("Unexpected error: {:?}", other); }
+        };
+
+        // Draw a triangle via an offscreen render target
+        use self::RenderAction::*;
+
+        let mut renderer    = context.create_render_target(100, 100);
+        let black           = [0, 0, 0, 255];
+        renderer.render(vec![
+            CreateRenderTarget(RenderTargetId(0), TextureId(0), Size2D(100, 100), RenderTargetType::Standard),
+            SelectRenderTarget(RenderTargetId(0)),
+            Clear(Rgba8([128, 128, 128, 255])),
+            UseShader(ShaderType::Simple { clip_texture: None }),
+            CreateVertex2DBuffer(VertexBufferId(0), vec![
+                Vertex2D { pos: [-1.0, -1.0],   tex_coord: [0.0, 0.0], color: black },
+                Vertex2D { pos: [1.0, 1.0],     tex_coord: [0.0, 0.0], color: black },
+                Vertex2D { pos: [1.0, -1.0],    tex_coord: [0.0, 0.0], color: black },
+            ]),
+            DrawTriangles(VertexBufferId(0), 0..3),
+            RenderToFrameBuffer,
+        ]);
+
+        let messages        = LOGGER.0.lock().unwrap().clone();
+        println!("{:?}", messages);
+
+        for event_name in ["shader_compiled", "buffer_allocated", "render_target_selected", "frame_completed"].iter() {
+            assert!(messages.iter().any(|msg| msg.starts_with(event_name)), "Missing {}", event_name);
+        }
+    }
 }
@@ -40,20 +40,29 @@ struct WgpuOffscreenRenderTarget {
 /// This version is the Metal version for Mac OS X
 ///
 pub async fn wgpu_initialize_offscreen_rendering() -> Result<impl OffscreenRenderContext, RenderInitError> {
+    create_offscreen_context().await
+}
+
+///
+/// Creates the WGPU device and queue used for offscreen rendering
+///
+async fn create_offscreen_context() -> Result<WgpuOffscreenRenderContext, RenderInitError> {
     // Create a new WGPU instance and adapter
     let instance    = wgpu::Instance::new(wgpu::InstanceDescriptor { backends: wgpu::Backends::all(), dx12_shader_compiler: wgpu::Dx12Compiler::default(), ..Default::default() });
     let adapter     = instance.request_adapter(&wgpu::RequestAdapterOptions {
         power_preference:       wgpu::PowerPreference::default(),
         force_fallback_adapter: false,
         compatible_surface:     None,
-    }).await.unwrap();
+    }).await.ok_or(RenderInitError::CannotOpenGraphicsDevice)?;
 
-    // Fetch the device and the queue
+    // Fetch the device and the queue (enabling whichever texture compression formats the adapter supports)
+    let compression_features    = wgpu::Features::TEXTURE_COMPRESSION_BC | wgpu::Features::TEXTURE_COMPRESSION_ETC2 | wgpu::Features::TEXTURE_COMPRESSION_ASTC;
+    let features                = adapter.features() & compression_features;
     let (device, queue) = adapter.request_device(&wgpu::DeviceDescriptor {
             label:      None,
-            features:   wgpu::Features::empty(),
+            features:   features,
             limits:     wgpu::Limits::downlevel_webgl2_defaults().using_resolution(adapter.limits())
-        }, None).await.unwrap();
+        }, None).await.map_err(|_| RenderInitError::CannotCreateGraphicsDevice)?;
 
     // Result is a WGPU offscreen render context
     Ok(WgpuOffscreenRenderContext {
@@ -83,6 +92,19 @@ impl OffscreenRenderContext for WgpuOffscreenRenderContext {
     /// Creates a new render target for this context
     ///
     fn create_render_target(&mut self, width: usize, height: usize) -> Self::RenderTarget {
+        self.create_render_target_with_format(width, height, wgpu::TextureFormat::Rgba8Unorm)
+    }
+}
+
+impl WgpuOffscreenRenderContext {
+    ///
+    /// Creates a new render target that renders to a texture of the specified format
+    ///
+    /// `realize()` returns the raw pixel data for the format, so a target with a floating-point format such as `Rgba16Float` (eg,
+    /// for rendering colours outside of the 0-1 range for HDR output) will return 8 bytes per pixel. Floating-point targets
+    /// store linear colour values.
+    ///
+    fn create_render_target_with_format(&mut self, width: usize, height: usize, format: wgpu::TextureFormat) -> WgpuOffscreenRenderTarget {
         // Create a texture to render on
         let target_texture = self.device.create_texture(&wgpu::TextureDescriptor {
             label:              Some("WgpuOffscreenRenderTarget"),
@@ -90,15 +112,15 @@ impl OffscreenRenderContext for WgpuOffscreenRenderContext {
             mip_level_count:    1,
             sample_count:       1,
             dimension:          wgpu::TextureDimension::D2,
-            format:             wgpu::TextureFormat::Rgba8Unorm,
+            format:             format,
             usage:              wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::RENDER_ATTACHMENT,
-            view_formats:       &[wgpu::TextureFormat::Rgba8Unorm],
+            view_formats:       &[format],
         });
 
         let target_texture = Arc::new(target_texture);
 
         // Create a renderer that will write to this texture
-        let renderer = WgpuRenderer::from_texture(Arc::clone(&self.device), Arc::clone(&self.queue), Arc::clone(&target_texture), Arc::clone(&self.adapter), wgpu::TextureFormat::Rgba8Unorm, (width as _, height as _));
+        let renderer = WgpuRenderer::from_texture(Arc::clone(&self.device), Arc::clone(&self.queue), Arc::clone(&target_texture), Arc::clone(&self.adapter), format, (width as _, height as _));
 
         // Build the render target
         WgpuOffscreenRenderTarget {
@@ -120,12 +142,21 @@ impl OffscreenRenderTarget for WgpuOffscreenRenderTarget {
         self.renderer.render_to_surface(actions);
     }
 
+    ///
+    /// Blocks until all of the rendering sent to this render target has been completed by the GPU
+    ///
+    #[inline]
+    fn finish(&mut self) {
+        self.renderer.finish();
+    }
+
     ///
     /// Consumes this render target and returns the realized pixels as a byte array
     ///
     fn realize(self) -> Vec<u8> {
         // Create a buffer to store the result
-        let bytes_per_row   = (((self.size.0 * 4 - 1) / 256) + 1) * 256;
+        let bytes_per_pixel = self.texture.format().block_size(None).unwrap_or(4);
+        let bytes_per_row   = (((self.size.0 * bytes_per_pixel - 1) / 256) + 1) * 256;
         let buffer          = self.device.create_buffer(&wgpu::BufferDescriptor {
             label:              Some("WgpuOffscreenRenderTarget::realize"),
             size:               (bytes_per_row as u64) * (self.size.1 as u64),
@@ -155,16 +186,16 @@ impl OffscreenRenderTarget for WgpuOffscreenRenderTarget {
         }
 
         // Prepare to write the buffer
-        let mut result      = vec![0; (self.size.0 * self.size.1 * 4) as usize];
+        let mut result      = vec![0; (self.size.0 * self.size.1 * bytes_per_pixel) as usize];
 
         // Poll for the result
         let mapped_buffer   = buffer_slice.get_mapped_range();
 
         // Copy to a Vec<u8>
-        let row_len = (self.size.0 * 4) as usize;
+        let row_len = (self.size.0 * bytes_per_pixel) as usize;
         for row in 0..self.size.1 {
             let buffer_row_start    = (row * bytes_per_row) as usize;
-            let row_start           = ((self.size.1 - 1 - row) * self.size.0 * 4) as usize;
+            let row_start           = ((self.size.1 - 1 - row) * self.size.0 * bytes_per_pixel) as usize;
 
             result[row_start..(row_start+row_len)].copy_from_slice(&mapped_buffer[buffer_row_start..(buffer_row_start+row_len)]);
         }
@@ -172,3 +203,404 @@ impl OffscreenRenderTarget for WgpuOffscreenRenderTarget {
         result
     }
 }
+
+impl RenderCapabilities for WgpuOffscreenRenderTarget {
+    #[inline]
+    fn supports_action(&self, action: &RenderAction) -> bool {
+        self.renderer.supports_action(action)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::buffer::*;
+
+    use futures::executor;
+
+    #[test]
+    fn srgb_target_matches_linear_target() {
+        let context         = executor::block_on(create_offscreen_context());
+        let mut context     = match context {
+            Ok(context)     => context,
+            Err(_)          => { println!("Test not run: graphics device unavailable"); return; }
+        };
+
+        // Clear the target and then draw a triangle over half of it
+        use self::RenderAction::*;
+
+        let color           = [200, 100, 50, 255];
+        let actions         = vec![
+            Clear(Rgba8([128, 64, 32, 255])),
+            UseShader(ShaderType::Simple { clip_texture: None }),
+            CreateVertex2DBuffer(VertexBufferId(0), vec![
+                Vertex2D { pos: [-1.0, -1.0],   tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [1.0, 1.0],     tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [1.0, -1.0],    tex_coord: [0.0, 0.0], color: color },
+            ]),
+            DrawTriangles(VertexBufferId(0), 0..3)
+        ];
+
+        let mut linear      = context.create_render_target_with_format(100, 100, wgpu::TextureFormat::Rgba8Unorm);
+        let mut srgb        = context.create_render_target_with_format(100, 100, wgpu::TextureFormat::Rgba8UnormSrgb);
+        linear.render(actions.clone());
+        srgb.render(actions);
+
+        let linear_image    = linear.realize();
+        let srgb_image      = srgb.realize();
+
+        // The colours should not be encoded twice for the sRGB target (allow for rounding during the conversion)
+        assert!(linear_image.len() == srgb_image.len());
+        for (linear_byte, srgb_byte) in linear_image.iter().zip(srgb_image.iter()) {
+            assert!((*linear_byte as i32 - *srgb_byte as i32).abs() <= 1, "{} {}", linear_byte, srgb_byte);
+        }
+    }
+
+    #[test]
+    fn srgb_target_keeps_translucent_colors() {
+        let context         = executor::block_on(create_offscreen_context());
+        let mut context     = match context {
+            Ok(context)     => context,
+            Err(_)          => { println!("Test not run: graphics device unavailable"); return; }
+        };
+
+        // Fill a transparent target from a premultiplied gradient texture containing a grey of 200 at 50% alpha
+        use self::RenderAction::*;
+
+        let color           = [255, 255, 255, 255];
+        let actions         = vec![
+            Clear(Rgba8([0, 0, 0, 0])),
+            Create1DTextureBgra(TextureId(0), Size1D(1)),
+            SetTexturePremultiplied(TextureId(0), true),
+            WriteTexture1D(TextureId(0), Position1D(0), Position1D(1), Arc::new(vec![100, 100, 100, 128])),
+            UseShader(ShaderType::LinearGradient { texture: TextureId(0), texture_transform: Matrix::identity(), repeat: false, alpha: 1.0, clip_texture: None }),
+            CreateVertex2DBuffer(VertexBufferId(0), vec![
+                Vertex2D { pos: [-1.0, -1.0],   tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [1.0, 1.0],     tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [1.0, -1.0],    tex_coord: [0.0, 0.0], color: color },
+
+                Vertex2D { pos: [-1.0, -1.0],   tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [1.0, 1.0],     tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [-1.0, 1.0],    tex_coord: [0.0, 0.0], color: color },
+            ]),
+            DrawTriangles(VertexBufferId(0), 0..6)
+        ];
+
+        let mut srgb        = context.create_render_target_with_format(16, 16, wgpu::TextureFormat::Rgba8UnormSrgb);
+        srgb.render(actions);
+
+        let image           = srgb.realize();
+        let center          = (8*16 + 8) * 4;
+        let pixel           = &image[center..(center+4)];
+
+        // The target stores linear colours premultiplied by alpha: dividing by the alpha should give back the original grey
+        let alpha           = (pixel[3] as f64) / 255.0;
+        let encoded         = (pixel[0] as f64) / 255.0;
+        let linear          = if encoded <= 0.04045 { encoded / 12.92 } else { ((encoded + 0.055) / 1.055).powf(2.4) };
+        let linear          = linear / alpha;
+        let straight        = if linear <= 0.0031308 { linear * 12.92 } else { 1.055 * linear.powf(1.0/2.4) - 0.055 };
+        let straight        = straight * 255.0;
+
+        assert!((alpha - 0.5).abs() < 0.01, "{:?}", pixel);
+        assert!((straight - 200.0).abs() < 4.0, "{} {:?}", straight, pixel);
+    }
+
+    ///
+    /// Converts a little-endian 16-bit float to a f32
+    ///
+    fn f16_to_f32(bytes: [u8; 2]) -> f32 {
+        let bits        = u16::from_le_bytes(bytes) as u32;
+        let sign        = if bits & 0x8000 != 0 { -1.0 } else { 1.0 };
+        let exponent    = ((bits >> 10) & 0x1f) as i32;
+        let mantissa    = (bits & 0x3ff) as f32;
+
+        if exponent == 0 {
+            sign * mantissa * f32::powi(2.0, -24)
+        } else {
+            sign * (1.0 + mantissa / 1024.0) * f32::powi(2.0, exponent - 15)
+        }
+    }
+
+    #[test]
+    fn render_to_f16_target() {
+        let context         = executor::block_on(create_offscreen_context());
+        let mut context     = match context {
+            Ok(context)     => context,
+            Err(_)          => { println!("Test not run: graphics device unavailable"); return; }
+        };
+
+        // Fill the target with a flat colour
+        use self::RenderAction::*;
+
+        let color           = [255, 128, 0, 255];
+        let actions         = vec![
+            Clear(Rgba8([0, 0, 0, 0])),
+            UseShader(ShaderType::Simple { clip_texture: None }),
+            CreateVertex2DBuffer(VertexBufferId(0), vec![
+                Vertex2D { pos: [-1.0, -1.0],   tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [1.0, 1.0],     tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [1.0, -1.0],    tex_coord: [0.0, 0.0], color: color },
+
+                Vertex2D { pos: [-1.0, -1.0],   tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [1.0, 1.0],     tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [-1.0, 1.0],    tex_coord: [0.0, 0.0], color: color },
+            ]),
+            DrawTriangles(VertexBufferId(0), 0..6)
+        ];
+
+        let mut hdr         = context.create_render_target_with_format(16, 16, wgpu::TextureFormat::Rgba16Float);
+        hdr.render(actions);
+
+        let image           = hdr.realize();
+        assert!(image.len() == 16*16*8);
+
+        // Each channel is read back as a 16-bit float, converted from sRGB to linear values (128/255 in sRGB is about 0.216)
+        let center          = (8*16 + 8) * 8;
+        let red             = f16_to_f32([image[center], image[center+1]]);
+        let green           = f16_to_f32([image[center+2], image[center+3]]);
+        let blue            = f16_to_f32([image[center+4], image[center+5]]);
+        let alpha           = f16_to_f32([image[center+6], image[center+7]]);
+
+        assert!((red - 1.0).abs() < 0.01 && (green - 0.216).abs() < 0.01 && blue.abs() < 0.01 && (alpha - 1.0).abs() < 0.01, "{} {} {} {}", red, green, blue, alpha);
+    }
+
+    #[test]
+    fn render_over_range_color_to_f16_target() {
+        let context         = executor::block_on(create_offscreen_context());
+        let mut context     = match context {
+            Ok(context)     => context,
+            Err(_)          => { println!("Test not run: graphics device unavailable"); return; }
+        };
+
+        // Fill the target from a floating-point texture where every pixel is (2.0, 2.0, 2.0, 1.0)
+        use self::RenderAction::*;
+
+        let over_range      = [0x00, 0x40, 0x00, 0x40, 0x00, 0x40, 0x00, 0x3c];
+        let texture_data    = over_range.iter().cycle().take(4*4*8).copied().collect::<Vec<u8>>();
+        let color           = [255, 255, 255, 255];
+        let actions         = vec![
+            Clear(Rgba8([0, 0, 0, 0])),
+            CreateTextureRgbaF16(TextureId(0), Size2D(4, 4)),
+            WriteTextureData(TextureId(0), Position2D(0, 0), Position2D(4, 4), Arc::new(texture_data)),
+            UseShader(ShaderType::Texture { texture: TextureId(0), texture_transform: Matrix::identity(), repeat: true, alpha: 1.0, clip_texture: None }),
+            CreateVertex2DBuffer(VertexBufferId(0), vec![
+                Vertex2D { pos: [-1.0, -1.0],   tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [1.0, 1.0],     tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [1.0, -1.0],    tex_coord: [0.0, 0.0], color: color },
+
+                Vertex2D { pos: [-1.0, -1.0],   tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [1.0, 1.0],     tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [-1.0, 1.0],    tex_coord: [0.0, 0.0], color: color },
+            ]),
+            DrawTriangles(VertexBufferId(0), 0..6)
+        ];
+
+        let mut hdr         = context.create_render_target_with_format(16, 16, wgpu::TextureFormat::Rgba16Float);
+        assert!(hdr.supports_action(&CreateTextureRgbaF16(TextureId(0), Size2D(4, 4))));
+        hdr.render(actions);
+
+        let image           = hdr.realize();
+        assert!(image.len() == 16*16*8);
+
+        // The colour channels should not have been clamped to 1.0 (2.0 in extended sRGB is about 4.95 as a linear value)
+        let center          = (8*16 + 8) * 8;
+        let red             = f16_to_f32([image[center], image[center+1]]);
+        let green           = f16_to_f32([image[center+2], image[center+3]]);
+        let blue            = f16_to_f32([image[center+4], image[center+5]]);
+        let alpha           = f16_to_f32([image[center+6], image[center+7]]);
+
+        assert!(red > 1.5 && green > 1.5 && blue > 1.5, "{} {} {}", red, green, blue);
+        assert!((alpha - 1.0).abs() < 0.01, "{}", alpha);
+    }
+
+    #[test]
+    fn sample_bc1_compressed_texture() {
+        let context         = executor::block_on(create_offscreen_context());
+        let mut context     = match context {
+            Ok(context)     => context,
+            Err(_)          => { println!("Test not run: graphics device unavailable"); return; }
+        };
+
+        let mut target      = context.create_render_target_with_format(16, 16, wgpu::TextureFormat::Rgba8Unorm);
+        if !target.supports_action(&RenderAction::CreateTextureCompressed(TextureId(0), Size2D(4, 4), TextureCompression::Bc1)) {
+            println!("Test not run: BC texture compression unavailable");
+            return;
+        }
+
+        // A single BC1 block where every pixel uses the first colour (pure red, in RGB565 format)
+        use self::RenderAction::*;
+
+        let red_block       = vec![0x00, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let color           = [255, 255, 255, 255];
+        let actions         = vec![
+            Clear(Rgba8([0, 0, 0, 255])),
+            CreateTextureCompressed(TextureId(0), Size2D(4, 4), TextureCompression::Bc1),
+            WriteTextureData(TextureId(0), Position2D(0, 0), Position2D(4, 4), Arc::new(red_block)),
+            UseShader(ShaderType::Texture { texture: TextureId(0), texture_transform: Matrix::identity(), repeat: true, alpha: 1.0, clip_texture: None }),
+            CreateVertex2DBuffer(VertexBufferId(0), vec![
+                Vertex2D { pos: [-1.0, -1.0],   tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [1.0, 1.0],     tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [1.0, -1.0],    tex_coord: [0.0, 0.0], color: color },
+
+                Vertex2D { pos: [-1.0, -1.0],   tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [1.0, 1.0],     tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [-1.0, 1.0],    tex_coord: [0.0, 0.0], color: color },
+            ]),
+            DrawTriangles(VertexBufferId(0), 0..6)
+        ];
+
+        target.render(actions);
+
+        let image           = target.realize();
+        assert!(image.len() == 16*16*4);
+
+        // The texture should decompress to red
+        let center          = (8*16 + 8) * 4;
+        let pixel           = &image[center..(center+4)];
+
+        assert!(pixel[0] > 250 && pixel[1] < 5 && pixel[2] < 5 && pixel[3] > 250, "{:?}", pixel);
+    }
+
+    #[test]
+    fn mipmap_bc1_compressed_texture() {
+        let context         = executor::block_on(create_offscreen_context());
+        let mut context     = match context {
+            Ok(context)     => context,
+            Err(_)          => { println!("Test not run: graphics device unavailable"); return; }
+        };
+
+        let mut target      = context.create_render_target_with_format(16, 16, wgpu::TextureFormat::Rgba8Unorm);
+        if !target.supports_action(&RenderAction::CreateTextureCompressed(TextureId(0), Size2D(8, 8), TextureCompression::Bc1)) {
+            println!("Test not run: BC texture compression unavailable");
+            return;
+        }
+
+        // Compressed textures can't be rendered to, so generating mipmaps or filtering should leave the texture as it is
+        use self::RenderAction::*;
+
+        let red_block       = vec![0x00, 0xf8, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        let red_blocks      = red_block.iter().cycle().take(4*8).copied().collect::<Vec<u8>>();
+        let color           = [255, 255, 255, 255];
+        let actions         = vec![
+            Clear(Rgba8([0, 0, 0, 255])),
+            CreateTextureCompressed(TextureId(0), Size2D(8, 8), TextureCompression::Bc1),
+            WriteTextureData(TextureId(0), Position2D(0, 0), Position2D(8, 8), Arc::new(red_blocks)),
+            CreateMipMaps(TextureId(0)),
+            FilterTexture(TextureId(0), vec![TextureFilter::AlphaBlend(0.5)]),
+            UseShader(ShaderType::Texture { texture: TextureId(0), texture_transform: Matrix::identity(), repeat: true, alpha: 1.0, clip_texture: None }),
+            CreateVertex2DBuffer(VertexBufferId(0), vec![
+                Vertex2D { pos: [-1.0, -1.0],   tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [1.0, 1.0],     tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [1.0, -1.0],    tex_coord: [0.0, 0.0], color: color },
+
+                Vertex2D { pos: [-1.0, -1.0],   tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [1.0, 1.0],     tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [-1.0, 1.0],    tex_coord: [0.0, 0.0], color: color },
+            ]),
+            DrawTriangles(VertexBufferId(0), 0..6)
+        ];
+
+        target.render(actions);
+
+        let image           = target.realize();
+        let center          = (8*16 + 8) * 4;
+        let pixel           = &image[center..(center+4)];
+
+        assert!(pixel[0] > 250 && pixel[1] < 5 && pixel[2] < 5 && pixel[3] > 250, "{:?}", pixel);
+    }
+
+    #[test]
+    fn reject_unaligned_compressed_texture() {
+        let context         = executor::block_on(create_offscreen_context());
+        let mut context     = match context {
+            Ok(context)     => context,
+            Err(_)          => { println!("Test not run: graphics device unavailable"); return; }
+        };
+
+        let mut target      = context.create_render_target_with_format(16, 16, wgpu::TextureFormat::Rgba8Unorm);
+        if !target.supports_action(&RenderAction::CreateTextureCompressed(TextureId(0), Size2D(4, 4), TextureCompression::Bc1)) {
+            println!("Test not run: BC texture compression unavailable");
+            return;
+        }
+
+        // Sizes that aren't a whole number of blocks are not supported, and creating a texture with one should not panic
+        use self::RenderAction::*;
+
+        assert!(!target.supports_action(&CreateTextureCompressed(TextureId(0), Size2D(6, 4), TextureCompression::Bc1)));
+        assert!(!target.supports_action(&CreateTextureCompressed(TextureId(0), Size2D(4, 5), TextureCompression::Bc1)));
+
+        target.render(vec![
+            Clear(Rgba8([0, 0, 0, 255])),
+            CreateTextureCompressed(TextureId(0), Size2D(6, 5), TextureCompression::Bc1),
+            CreateMipMaps(TextureId(0)),
+        ]);
+
+        let image           = target.realize();
+        assert!(image.len() == 16*16*4);
+    }
+
+    #[test]
+    fn copy_texture_quadrant() {
+        let context         = executor::block_on(create_offscreen_context());
+        let mut context     = match context {
+            Ok(context)     => context,
+            Err(_)          => { println!("Test not run: graphics device unavailable"); return; }
+        };
+
+        // Source texture is white in the top-left quadrant and grey elsewhere, target texture is transparent
+        let source          = (0..8).flat_map(|y| (0..8).map(move |x| if x < 4 && y < 4 { [255, 255, 255, 255] } else { [128, 128, 128, 255] }))
+            .flatten()
+            .collect::<Vec<u8>>();
+        let target          = vec![0; 8*8*4];
+
+        // Map -1..1 to 0..1 so the target texture covers the render target exactly
+        let transform       = Matrix([
+            [0.5, 0.0, 0.0, 0.5],
+            [0.0, 0.5, 0.0, 0.5],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0]
+        ]);
+
+        // Copy the white quadrant into the bottom-right quadrant of the target texture, then draw the target texture
+        use self::RenderAction::*;
+
+        let color           = [255, 255, 255, 255];
+        let actions         = vec![
+            CreateTextureBgra(TextureId(0), Size2D(8, 8)),
+            WriteTextureData(TextureId(0), Position2D(0, 0), Position2D(8, 8), Arc::new(source)),
+            CreateTextureBgra(TextureId(1), Size2D(8, 8)),
+            WriteTextureData(TextureId(1), Position2D(0, 0), Position2D(8, 8), Arc::new(target)),
+            CopyTextureRegion(TextureId(0), Position2D(0, 0), Position2D(4, 4), TextureId(1), Position2D(4, 4)),
+
+            Clear(Rgba8([0, 0, 0, 255])),
+            UseShader(ShaderType::Texture { texture: TextureId(1), texture_transform: transform, repeat: false, alpha: 1.0, clip_texture: None }),
+            CreateVertex2DBuffer(VertexBufferId(0), vec![
+                Vertex2D { pos: [-1.0, -1.0],   tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [1.0, 1.0],     tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [1.0, -1.0],    tex_coord: [0.0, 0.0], color: color },
+
+                Vertex2D { pos: [-1.0, -1.0],   tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [1.0, 1.0],     tex_coord: [0.0, 0.0], color: color },
+                Vertex2D { pos: [-1.0, 1.0],    tex_coord: [0.0, 0.0], color: color },
+            ]),
+            DrawTriangles(VertexBufferId(0), 0..6)
+        ];
+
+        let mut target      = context.create_render_target_with_format(8, 8, wgpu::TextureFormat::Rgba8Unorm);
+        target.render(actions);
+
+        let image           = target.realize();
+        assert!(image.len() == 8*8*4);
+
+        // Only the copied quadrant should be white (the grey part of the source should not have been copied)
+        let white_pixels    = image.chunks(4).enumerate()
+            .filter(|(_, pixel)| pixel.iter().all(|component| *component >= 250))
+            .map(|(idx, _)| (idx % 8, idx / 8))
+            .collect::<Vec<_>>();
+        let black_pixels    = image.chunks(4).filter(|pixel| pixel[0] <= 5 && pixel[1] <= 5 && pixel[2] <= 5).count();
+
+        assert!(white_pixels.len() == 16, "{:?}", white_pixels);
+        assert!(white_pixels.iter().all(|(x, _)| *x >= 4), "{:?}", white_pixels);
+        assert!(black_pixels == 48, "{:?}", image);
+    }
+}
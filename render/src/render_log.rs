@@ -0,0 +1,47 @@
+use crate::action::*;
+
+use std::time::{Duration};
+
+///
+/// Structured events that the renderers log (via the `log` crate) when the `logging` feature is enabled
+///
+/// Events are logged at the debug level with the target `flo_render`. The message starts with the event name, followed
+/// by the debug representation of the event.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub enum RenderLogEvent {
+    /// A shader or shader pipeline was compiled
+    ShaderCompiled { description: String },
+
+    /// A vertex or index buffer was allocated on the GPU
+    BufferAllocated { kind: &'static str, buffer_id: usize, bytes: usize },
+
+    /// The renderer switched to a new render target (None for the main frame buffer)
+    RenderTargetSelected { render_target: Option<RenderTargetId> },
+
+    /// A set of render actions finished rendering
+    FrameCompleted { duration: Duration },
+}
+
+impl RenderLogEvent {
+    ///
+    /// The name of this event, as it appears at the start of the log message
+    ///
+    pub fn name(&self) -> &'static str {
+        use RenderLogEvent::*;
+
+        match self {
+            ShaderCompiled { .. }       => "shader_compiled",
+            BufferAllocated { .. }      => "buffer_allocated",
+            RenderTargetSelected { .. } => "render_target_selected",
+            FrameCompleted { .. }       => "frame_completed",
+        }
+    }
+}
+
+///
+/// Writes a render event to the log
+///
+pub (crate) fn log_render_event(event: RenderLogEvent) {
+    log::debug!(target: "flo_render", "{} {:?}", event.name(), event);
+}
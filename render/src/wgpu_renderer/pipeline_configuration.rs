@@ -46,6 +46,26 @@ impl Default for PipelineConfiguration {
     }
 }
 
+///
+/// Converts an sRGB colour component to a linear one
+///
+pub (crate) fn srgb_to_linear(val: f64) -> f64 {
+    if val <= 0.04045 {
+        val / 12.92
+    } else {
+        ((val + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+///
+/// Returns true if the specified texture format expects linear colour values instead of sRGB values
+///
+/// sRGB formats encode colours as they're written and floating-point formats are used as linear (scRGB) surfaces
+///
+pub (crate) fn is_linear_format(format: wgpu::TextureFormat) -> bool {
+    format.is_srgb() || format == wgpu::TextureFormat::Rgba16Float
+}
+
 #[inline]
 fn create_add_blend_state(rgb_src_factor: wgpu::BlendFactor, rgb_dst_factor: wgpu::BlendFactor, alpha_src_factor: wgpu::BlendFactor, alpha_dst_factor: wgpu::BlendFactor) -> wgpu::BlendState {
     wgpu::BlendState {
@@ -98,6 +118,24 @@ impl Default for PipelineDescriptorTempStorage {
 }
 
 impl PipelineConfiguration {
+    ///
+    /// Returns the configuration to use for rendering against the texture format in this configuration
+    ///
+    /// Colours are supplied as sRGB values, so shaders that render against an sRGB texture (which encodes the colours
+    /// again as they're written) or a floating-point texture (which stores linear values) need to convert their output
+    /// to linear values
+    ///
+    pub (crate) fn for_target_format(&self) -> PipelineConfiguration {
+        if is_linear_format(self.texture_format) {
+            PipelineConfiguration {
+                shader_module: self.shader_module.with_linear_output(self.source_is_premultiplied),
+                ..self.clone()
+            }
+        } else {
+            self.clone()
+        }
+    }
+
     ///
     /// Creates a pipeline configuration targeting the specified texture
     ///
@@ -1,3 +1,5 @@
+use crate::action::*;
+
 use wgpu;
 
 use std::sync::*;
@@ -16,3 +18,15 @@ pub (crate) struct WgpuTexture {
     /// True if this texture has premultiplied alpha
     pub is_premultiplied: bool,
 }
+
+///
+/// Returns the texture format used for a compressed texture, along with the device feature needed to use it
+///
+pub (crate) fn compressed_texture_format(compression: TextureCompression) -> (wgpu::TextureFormat, wgpu::Features) {
+    match compression {
+        TextureCompression::Bc1         => (wgpu::TextureFormat::Bc1RgbaUnorm, wgpu::Features::TEXTURE_COMPRESSION_BC),
+        TextureCompression::Bc3         => (wgpu::TextureFormat::Bc3RgbaUnorm, wgpu::Features::TEXTURE_COMPRESSION_BC),
+        TextureCompression::Etc2Rgba8   => (wgpu::TextureFormat::Etc2Rgba8Unorm, wgpu::Features::TEXTURE_COMPRESSION_ETC2),
+        TextureCompression::Astc4x4     => (wgpu::TextureFormat::Astc { block: wgpu::AstcBlock::B4x4, channel: wgpu::AstcChannel::Unorm }, wgpu::Features::TEXTURE_COMPRESSION_ASTC),
+    }
+}
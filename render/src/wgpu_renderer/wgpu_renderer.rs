@@ -17,6 +17,8 @@ use super::displacement_map_filter::*;
 
 #[cfg(feature="profile")]
 use crate::profiler::*;
+#[cfg(feature="logging")]
+use crate::render_log::*;
 
 use crate::action::*;
 use crate::buffer::*;
@@ -34,6 +36,9 @@ use std::sync::*;
 use std::collections::HashMap;
 use std::ffi::c_void;
 
+#[cfg(feature="logging")]
+use std::time::{Instant};
+
 #[cfg(feature="profile")]
 use std::cell::*;
 #[cfg(feature="profile")]
@@ -44,6 +49,9 @@ use wgpu_profiler::{GpuProfiler, GpuProfilerSettings};
 #[cfg(feature="wgpu-profiler")]
 use std::path::Path;
 
+/// The surface formats to try when an HDR surface is requested, in order of preference
+const HDR_SURFACE_FORMATS: [wgpu::TextureFormat; 2] = [wgpu::TextureFormat::Rgba16Float, wgpu::TextureFormat::Rgb10a2Unorm];
+
 ///
 /// Renderer that uses the `wgpu` abstract library as a render target
 ///
@@ -75,6 +83,12 @@ pub struct WgpuRenderer {
     /// The height of the target surface
     height: u32,
 
+    /// True if the target surface should be composited with the windows behind it using its alpha channel
+    transparent: bool,
+
+    /// True if the target surface should use a high dynamic range format, if one is available
+    hdr: bool,
+
     /// The vertex buffers for this renderer
     vertex_buffers: Vec<Option<Arc<wgpu::Buffer>>>,
 
@@ -138,6 +152,8 @@ impl WgpuRenderer {
             shader_cache:           ShaderCache::empty(device.clone()),
             width:                  0,
             height:                 0,
+            transparent:            false,
+            hdr:                    false,
             active_render_target:   None,
             active_shader:          Some(ShaderType::Simple { clip_texture: None }),
             active_blend_mode:      Some(BlendMode::SourceOver),
@@ -173,6 +189,8 @@ impl WgpuRenderer {
             shader_cache:           ShaderCache::empty(device.clone()),
             width:                  texture_size.0,
             height:                 texture_size.1,
+            transparent:            false,
+            hdr:                    false,
             active_render_target:   None,
             active_shader:          Some(ShaderType::Simple { clip_texture: None }),
             active_blend_mode:      Some(BlendMode::SourceOver),
@@ -186,6 +204,79 @@ impl WgpuRenderer {
         }
     }
 
+    ///
+    /// Sets whether or not the surface this renderer targets should be transparent
+    ///
+    /// A transparent surface is composited with whatever is behind it (eg, the desktop) using the alpha channel of the rendering.
+    /// Not every platform supports this: if the surface can't be made transparent, it will remain opaque.
+    ///
+    pub fn set_transparent(&mut self, transparent: bool) {
+        if self.transparent != transparent {
+            self.transparent = transparent;
+
+            // Reconfigure the surface the next time it's prepared
+            if self.target_surface.is_some() {
+                self.target_format = None;
+            }
+        }
+    }
+
+    ///
+    /// Sets whether or not the surface this renderer targets should use a high dynamic range format
+    ///
+    /// HDR surfaces use a floating point (or 10-bit) format, so colours brighter than 1.0 are not clamped when they are displayed.
+    /// If the surface doesn't support any HDR formats, a standard format is used instead.
+    ///
+    /// Vertex colours are limited to the 0-1 range, so colours brighter than 1.0 are drawn using textures created with
+    /// `CreateTextureRgbaF16`.
+    ///
+    pub fn set_hdr(&mut self, hdr: bool) {
+        if self.hdr != hdr {
+            self.hdr = hdr;
+
+            // Reconfigure the surface the next time it's prepared
+            if self.target_surface.is_some() {
+                self.target_format = None;
+            }
+        }
+    }
+
+    ///
+    /// Blocks until the GPU has finished executing all of the commands submitted by this renderer
+    ///
+    /// Rendering is submitted to the device queue at the end of `render_to_surface()`, so this can be used after that call as a sync
+    /// point (eg, before reading back a texture or when timing how long the GPU takes to render a frame)
+    ///
+    pub fn finish(&mut self) {
+        self.device.poll(wgpu::Maintain::Wait);
+    }
+
+    ///
+    /// Changes the texture that this renderer draws to
+    ///
+    /// This is for renderers created with `from_texture()`, where the texture is owned by the application (eg, so the rendering
+    /// can be sampled by the application's own pipelines). The texture needs the `RENDER_ATTACHMENT` usage, and usually
+    /// `TEXTURE_BINDING` as well so it can be sampled. This can be used to replace the texture with a new one when it is resized.
+    ///
+    pub fn set_target_texture(&mut self, target_texture: Arc<wgpu::Texture>, texture_format: wgpu::TextureFormat, texture_size: (u32, u32)) {
+        self.target_surface         = None;
+        self.target_surface_texture = None;
+        self.target_texture         = Some(target_texture);
+        self.target_format          = Some(texture_format);
+        self.width                  = texture_size.0;
+        self.height                 = texture_size.1;
+    }
+
+    ///
+    /// Calls a function once the GPU has finished all of the rendering that has been submitted by this renderer so far
+    ///
+    /// When rendering to a texture, this indicates when the texture is ready to be used. The callback is made from whichever
+    /// thread next polls the device (which happens when further work is submitted, or when `finish()` is called).
+    ///
+    pub fn on_rendering_complete(&self, callback: impl 'static + Send + FnOnce()) {
+        self.queue.on_submitted_work_done(callback);
+    }
+
     ///
     /// Sets up the surface to render at a new size
     ///
@@ -200,17 +291,29 @@ impl WgpuRenderer {
 
         if let Some(target_surface) = &self.target_surface {
             // Fetch the format
-            let possible_formats    = target_surface.get_capabilities(&*self.adapter).formats;
-            let actual_format       = possible_formats.iter().filter(|format| !format.is_srgb()).next().copied();
+            let capabilities        = target_surface.get_capabilities(&*self.adapter);
+            let possible_formats    = capabilities.formats;
+            let hdr_format          = if self.hdr { HDR_SURFACE_FORMATS.iter().filter(|format| possible_formats.contains(format)).next().copied() } else { None };
+            let actual_format       = hdr_format.or_else(|| possible_formats.iter().filter(|format| !format.is_srgb()).next().copied());
             let actual_format       = actual_format.unwrap_or(possible_formats[0]);
 
+            // Transparent surfaces need an alpha mode that composites with the desktop (falling back to an opaque surface if none is available)
+            let alpha_mode          = if self.transparent {
+                [wgpu::CompositeAlphaMode::PreMultiplied, wgpu::CompositeAlphaMode::PostMultiplied].iter().copied()
+                    .filter(|mode| capabilities.alpha_modes.contains(mode))
+                    .next()
+                    .unwrap_or(wgpu::CompositeAlphaMode::Auto)
+            } else {
+                wgpu::CompositeAlphaMode::Auto
+            };
+
             let surface_config      = wgpu::SurfaceConfiguration {
                 usage:          wgpu::TextureUsages::RENDER_ATTACHMENT,
                 format:         actual_format,
                 width:          width,
                 height:         height,
                 present_mode:   wgpu::PresentMode::AutoVsync,
-                alpha_mode:     wgpu::CompositeAlphaMode::Auto,
+                alpha_mode:     alpha_mode,
                 view_formats:   vec![actual_format]
             };
 
@@ -231,6 +334,8 @@ impl WgpuRenderer {
     pub fn render_to_surface<Actions: IntoIterator<Item=RenderAction>>(&mut self, actions: Actions) -> Option<wgpu::SurfaceTexture> {
         #[cfg(feature="profile")]
         self.profiler.borrow_mut().start_frame();
+        #[cfg(feature="logging")]
+        let frame_start = Instant::now();
 
         // Create the render state
         let mut render_state    = RendererState::new(Arc::clone(&self.queue), Arc::clone(&self.device));
@@ -278,18 +383,23 @@ impl WgpuRenderer {
                 ShowFrameBuffer                                                                 => { self.show_frame_buffer(&mut render_state); }
                 CreateTextureBgra(texture_id, Size2D(width, height))                            => { self.create_bgra_texture(texture_id, width, height); }
                 CreateTextureMono(texture_id, Size2D(width, height))                            => { self.create_mono_texture(texture_id, width, height); }
+                CreateTextureRgbaF16(texture_id, Size2D(width, height))                         => { self.create_f16_texture(texture_id, width, height); }
+                CreateTextureCompressed(texture_id, Size2D(width, height), compression)         => { self.create_compressed_texture(texture_id, width, height, compression); }
                 Create1DTextureBgra(texture_id, Size1D(width))                                  => { self.create_bgra_1d_texture(texture_id, width); }
                 Create1DTextureMono(texture_id, Size1D(width))                                  => { self.create_mono_1d_texture(texture_id, width); }
                 WriteTextureData(texture_id, Position2D(x1, y1), Position2D(x2, y2), data)      => { self.write_texture_data_2d(texture_id, x1, y1, x2, y2, data, &mut render_state); }
                 WriteTexture1D(texture_id, Position1D(x1), Position1D(x2), data)                => { self.write_texture_data_1d(texture_id, x1, x2, data, &mut render_state); }
                 CreateMipMaps(texture_id)                                                       => { self.create_mipmaps(texture_id, &mut render_state); }
                 CopyTexture(src_texture, tgt_texture)                                           => { self.copy_texture(src_texture, tgt_texture, &mut render_state); }
+                CopyTextureRegion(src, Position2D(x1, y1), Position2D(x2, y2), tgt, Position2D(x, y)) => { self.copy_texture_region(src, (x1, y1), (x2, y2), tgt, (x, y), &mut render_state); }
+                SetTexturePremultiplied(texture_id, premultiplied)                              => { self.set_texture_premultiplied(texture_id, premultiplied); }
                 FilterTexture(texture, filter)                                                  => { self.filter_texture(texture, filter, &mut render_state); }
                 FreeTexture(texture_id)                                                         => { self.free_texture(texture_id); }
                 Clear(color)                                                                    => { self.clear(color, &mut render_state); }
                 UseShader(shader_type)                                                          => { self.use_shader(shader_type, &mut render_state); }
                 DrawTriangles(buffer_id, buffer_range)                                          => { self.draw_triangles(buffer_id, buffer_range, &mut render_state); }
                 DrawIndexedTriangles(vertex_buffer, index_buffer, num_vertices)                 => { self.draw_indexed_triangles(vertex_buffer, index_buffer, num_vertices, &mut render_state); }
+                PushStencilClip | EndStencilClip | PopStencilClip                               => { /* Stencil clipping is not supported by this renderer */ }
             }
 
             #[cfg(feature="profile")]
@@ -323,6 +433,9 @@ impl WgpuRenderer {
             wgpu_profiler::chrometrace::write_chrometrace(Path::new("flo_draw_profile.json"), &profiling_data);
         }
         
+        #[cfg(feature="logging")]
+        log_render_event(RenderLogEvent::FrameCompleted { duration: frame_start.elapsed() });
+
         // Result is the surface texture that was last presented
         render_state.present.take()
     }
@@ -337,7 +450,13 @@ impl WgpuRenderer {
 
         let pipeline        = pipeline_states.entry(config.clone())
             .or_insert_with(|| {
+                // sRGB targets encode colours as they're written, so the shaders need to produce linear colours for them
+                let config = config.for_target_format();
+
                 // Create the pipeline if we don't have one matching the configuration already
+                #[cfg(feature="logging")]
+                log_render_event(RenderLogEvent::ShaderCompiled { description: format!("{:?}", config.shader_module) });
+
                 Arc::new(Pipeline::from_configuration(&config, device, shader_cache))
             });
 
@@ -421,6 +540,9 @@ impl WgpuRenderer {
         // Create the buffer
         let vertex_buffer = vertices.to_buffer(&*self.device, wgpu::BufferUsages::VERTEX);
 
+        #[cfg(feature="logging")]
+        log_render_event(RenderLogEvent::BufferAllocated { kind: "vertex", buffer_id: vertex_id, bytes: vertices.len() * mem::size_of::<Vertex2D>() });
+
         // Store associated with the vertex ID
         if vertex_id >= self.vertex_buffers.len() {
             self.vertex_buffers.extend((self.vertex_buffers.len()..(vertex_id+1))
@@ -444,6 +566,9 @@ impl WgpuRenderer {
         // Create the buffer
         let index_buffer = indices.to_buffer(&*self.device, wgpu::BufferUsages::INDEX);
 
+        #[cfg(feature="logging")]
+        log_render_event(RenderLogEvent::BufferAllocated { kind: "index", buffer_id: index_id, bytes: indices.len() * mem::size_of::<u16>() });
+
         // Store associated with the index ID
         if index_id >= self.index_buffers.len() {
             self.index_buffers.extend((self.index_buffers.len()..(index_id+1))
@@ -545,6 +670,9 @@ impl WgpuRenderer {
 
             self.active_render_target = Some(RenderTargetId(render_id));
 
+            #[cfg(feature="logging")]
+            log_render_event(RenderLogEvent::RenderTargetSelected { render_target: Some(RenderTargetId(render_id)) });
+
             // Render to the existing render target
             #[cfg(feature="profile")] self.profiler.borrow_mut().start_action(RenderActionType::RunRenderPass);
             state.run_render_pass();
@@ -567,6 +695,16 @@ impl WgpuRenderer {
             state.pipeline_bindings_changed                     = true;
 
             self.update_pipeline_if_needed(state);
+        } else {
+            // Finish rendering to the existing target, then discard rendering until a valid target is selected (so clears can't affect a different target)
+            self.active_render_target = Some(RenderTargetId(render_id));
+
+            #[cfg(feature="profile")] self.profiler.borrow_mut().start_action(RenderActionType::RunRenderPass);
+            state.run_render_pass();
+            #[cfg(feature="profile")] self.profiler.borrow_mut().finish_action(RenderActionType::RunRenderPass);
+
+            state.render_pass_resources.target_view             = None;
+            state.render_pass_resources.target_texture          = None;
         }
     }
     
@@ -576,6 +714,9 @@ impl WgpuRenderer {
     fn select_main_frame_buffer(&mut self, state: &mut RendererState) {
         self.active_render_target = None;
 
+        #[cfg(feature="logging")]
+        log_render_event(RenderLogEvent::RenderTargetSelected { render_target: None });
+
         if let Some(target_surface) = &self.target_surface {
             // Ensure that there's a main frame buffer to render to
             if self.target_surface_texture.is_none() {
@@ -860,6 +1001,49 @@ impl WgpuRenderer {
         self.textures[texture_id] = Some(new_texture);
     }
     
+    ///
+    /// Creates a 2D RGBA texture with 16-bit floating point channels
+    ///
+    fn create_f16_texture(&mut self, TextureId(texture_id): TextureId, width: usize, height: usize) {
+        // Free the old texture if there is one
+        if let Some(old_texture) = self.textures.get_mut(texture_id) {
+            *old_texture = None;
+        }
+
+        // Texture is COPY_DST so we can write to it
+        let descriptor = wgpu::TextureDescriptor {
+            label:  Some("f16_texture"),
+            size:   wgpu::Extent3d {
+                width:                  width as _,
+                height:                 height as _,
+                depth_or_array_layers:  1,
+            },
+            mip_level_count:    1,
+            sample_count:       1,
+            dimension:          wgpu::TextureDimension::D2,
+            format:             wgpu::TextureFormat::Rgba16Float,
+            usage:              wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats:       &[],
+        };
+
+        // Create the texture
+        let new_texture = self.device.create_texture(&descriptor);
+        let new_texture = WgpuTexture {
+            descriptor:         descriptor,
+            texture:            Arc::new(new_texture),
+            is_premultiplied:   false,
+        };
+
+        // Store the texture
+        if texture_id >= self.textures.len() {
+            self.textures.extend((self.textures.len()..(texture_id+1))
+                .into_iter()
+                .map(|_| None));
+        }
+
+        self.textures[texture_id] = Some(new_texture);
+    }
+
     ///
     /// Creates a 1D BGRA texture
     ///
@@ -946,6 +1130,63 @@ impl WgpuRenderer {
         self.textures[texture_id] = Some(new_texture);
     }
     
+    ///
+    /// Creates a 2D texture using a block-compressed format
+    ///
+    /// The texture is not created if the device does not have the feature needed for the format, or if the size is not a
+    /// whole number of blocks (any existing texture with the same ID is still freed).
+    ///
+    fn create_compressed_texture(&mut self, TextureId(texture_id): TextureId, width: usize, height: usize, compression: TextureCompression) {
+        // Free the old texture if there is one
+        if let Some(old_texture) = self.textures.get_mut(texture_id) {
+            *old_texture = None;
+        }
+
+        // Can only create the texture if the device supports this format
+        let (format, feature) = compressed_texture_format(compression);
+        if !self.device.features().contains(feature) {
+            return;
+        }
+
+        // wgpu rejects compressed textures that aren't made up of whole blocks
+        if !compression.is_valid_size(width, height) {
+            return;
+        }
+
+        // Compressed textures can only be sampled or copied to
+        let descriptor = wgpu::TextureDescriptor {
+            label:  Some("compressed_texture"),
+            size:   wgpu::Extent3d {
+                width:                  width as _,
+                height:                 height as _,
+                depth_or_array_layers:  1,
+            },
+            mip_level_count:    1,
+            sample_count:       1,
+            dimension:          wgpu::TextureDimension::D2,
+            format:             format,
+            usage:              wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::COPY_DST | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats:       &[],
+        };
+
+        // Create the texture
+        let new_texture = self.device.create_texture(&descriptor);
+        let new_texture = WgpuTexture {
+            descriptor:         descriptor,
+            texture:            Arc::new(new_texture),
+            is_premultiplied:   false,
+        };
+
+        // Store the texture
+        if texture_id >= self.textures.len() {
+            self.textures.extend((self.textures.len()..(texture_id+1))
+                .into_iter()
+                .map(|_| None));
+        }
+
+        self.textures[texture_id] = Some(new_texture);
+    }
+
     ///
     /// Writes byte data to a region of a 2D texture
     ///
@@ -958,11 +1199,15 @@ impl WgpuRenderer {
             let (x1, x2)        = if x1 > x2 { (x2, x1) } else { (x1, x2) };
             let (y1, y2)        = if y1 > y2 { (y2, y1) } else { (y1, y2) };
 
-            let bytes_per_pixel = texture.descriptor.format.block_size(None).unwrap() as u64;
+            // Compressed textures are laid out in blocks of pixels (uncompressed textures have 1x1 blocks)
+            let (block_width, block_height) = texture.descriptor.format.block_dimensions();
+            let (block_width, block_height) = (block_width as u64, block_height as u64);
+            let bytes_per_block             = texture.descriptor.format.block_size(None).unwrap() as u64;
+            let blocks_per_row              = ((texture.descriptor.size.width as u64) + block_width - 1) / block_width;
 
-            let line_offset     = (y1 as u64) * (texture.descriptor.size.width as u64) * bytes_per_pixel;
-            let pixel_offset    = (x1 as u64) * bytes_per_pixel;
-            let bytes_per_row   = (texture.descriptor.size.width as u64) * bytes_per_pixel;
+            let line_offset     = ((y1 as u64) / block_height) * blocks_per_row * bytes_per_block;
+            let pixel_offset    = ((x1 as u64) / block_width) * bytes_per_block;
+            let bytes_per_row   = blocks_per_row * bytes_per_block;
 
             let layout          = wgpu::ImageDataLayout {
                 offset:         line_offset + pixel_offset,
@@ -980,14 +1225,23 @@ impl WgpuRenderer {
     ///
     fn write_texture_data_1d(&mut self, TextureId(texture_id): TextureId, x1: usize, x2: usize, data: Arc<Vec<u8>>, state: &mut RendererState) {
         if let Some(Some(texture)) = self.textures.get(texture_id) {
+            let (x1, x2)        = if x1 > x2 { (x2, x1) } else { (x1, x2) };
+
+            // The data contains just the pixels in the range, which are written starting at x1 in the texture
             let bytes_per_pixel = texture.descriptor.format.block_size(None).unwrap() as u64;
             let layout          = wgpu::ImageDataLayout {
-                offset:         (x1 as u64) * bytes_per_pixel,
-                bytes_per_row:  Some(((texture.descriptor.size.width as u64) * bytes_per_pixel) as u32),
+                offset:         0,
+                bytes_per_row:  Some((((x2-x1) as u64) * bytes_per_pixel) as u32),
                 rows_per_image: None,
             };
+            let target          = wgpu::ImageCopyTexture {
+                texture:    &texture.texture,
+                mip_level:  0,
+                origin:     wgpu::Origin3d { x: x1 as u32, y: 0, z: 0 },
+                aspect:     wgpu::TextureAspect::All
+            };
 
-            self.queue.write_texture(texture.texture.as_image_copy(), &*data, layout, wgpu::Extent3d { width: (x2-x1) as u32, height: 1, depth_or_array_layers: 1 });
+            self.queue.write_texture(target, &*data, layout, wgpu::Extent3d { width: (x2-x1) as u32, height: 1, depth_or_array_layers: 1 });
             state.render_pass_resources.textures.push(Arc::clone(&texture.texture));
         }
     }
@@ -999,7 +1253,8 @@ impl WgpuRenderer {
         if let Some(Some(src_texture)) = self.textures.get(texture_id) {
             let src_texture                 = src_texture.clone();
 
-            if src_texture.descriptor.dimension == wgpu::TextureDimension::D2 {
+            // Compressed textures can't be rendered to, so their mipmaps can't be generated
+            if src_texture.descriptor.dimension == wgpu::TextureDimension::D2 && !src_texture.descriptor.format.is_compressed() {
                 // We use the reduce filter to generate the mipmaps
                 let mut reduce_pipeline         = PipelineConfiguration::for_texture(&src_texture);
                 reduce_pipeline.blending_mode   = None;
@@ -1052,11 +1307,82 @@ impl WgpuRenderer {
         }
     }
     
+    ///
+    /// Copies a region of one texture to a position in another texture
+    ///
+    fn copy_texture_region(&mut self, TextureId(src_texture_id): TextureId, (x1, y1): (usize, usize), (x2, y2): (usize, usize), TextureId(tgt_texture_id): TextureId, (x, y): (usize, usize), state: &mut RendererState) {
+        // WGPU can't copy between regions of the same texture
+        if src_texture_id == tgt_texture_id {
+            return;
+        }
+
+        if let (Some(Some(src_texture)), Some(Some(tgt_texture))) = (self.textures.get(src_texture_id), self.textures.get(tgt_texture_id)) {
+            // The textures must have the same format, and need to be usable as the source and target of a copy
+            if src_texture.descriptor.format != tgt_texture.descriptor.format
+                || !src_texture.descriptor.usage.contains(wgpu::TextureUsages::COPY_SRC)
+                || !tgt_texture.descriptor.usage.contains(wgpu::TextureUsages::COPY_DST) {
+                return;
+            }
+
+            // Clip the region to fit in both textures
+            let (x1, x2)    = if x1 > x2 { (x2, x1) } else { (x1, x2) };
+            let (y1, y2)    = if y1 > y2 { (y2, y1) } else { (y1, y2) };
+            let src_size    = src_texture.descriptor.size;
+            let tgt_size    = tgt_texture.descriptor.size;
+
+            let x2          = x2.min(src_size.width as usize);
+            let y2          = y2.min(src_size.height as usize);
+            let width       = x2.saturating_sub(x1).min((tgt_size.width as usize).saturating_sub(x));
+            let height      = y2.saturating_sub(y1).min((tgt_size.height as usize).saturating_sub(y));
+
+            if width == 0 || height == 0 {
+                return;
+            }
+
+            // Finish the render pass (in case either texture is involved in it)
+            #[cfg(feature="profile")] self.profiler.borrow_mut().start_action(RenderActionType::RunRenderPass);
+            state.run_render_pass();
+            #[cfg(feature="profile")] self.profiler.borrow_mut().finish_action(RenderActionType::RunRenderPass);
+
+            // Copy the region
+            let source = wgpu::ImageCopyTexture {
+                texture:    &src_texture.texture,
+                mip_level:  0,
+                origin:     wgpu::Origin3d { x: x1 as u32, y: y1 as u32, z: 0 },
+                aspect:     wgpu::TextureAspect::All,
+            };
+            let target = wgpu::ImageCopyTexture {
+                texture:    &tgt_texture.texture,
+                mip_level:  0,
+                origin:     wgpu::Origin3d { x: x as u32, y: y as u32, z: 0 },
+                aspect:     wgpu::TextureAspect::All,
+            };
+
+            state.encoder.copy_texture_to_texture(source, target, wgpu::Extent3d { width: width as u32, height: height as u32, depth_or_array_layers: 1 });
+            state.render_pass_resources.textures.push(Arc::clone(&src_texture.texture));
+            state.render_pass_resources.textures.push(Arc::clone(&tgt_texture.texture));
+        }
+    }
+
+    ///
+    /// Sets whether or not a texture contains premultiplied colours
+    ///
+    fn set_texture_premultiplied(&mut self, TextureId(texture_id): TextureId, premultiplied: bool) {
+        if let Some(Some(texture)) = self.textures.get_mut(texture_id) {
+            texture.is_premultiplied = premultiplied;
+        }
+    }
+
     ///
     /// Applies a filter effect to the content of a texture
     ///
     fn filter_texture(&mut self, TextureId(texture_id): TextureId, texture_filters: Vec<TextureFilter>, state: &mut RendererState) {
         if let Some(Some(texture)) = self.textures.get(texture_id) {
+            // The filters render to a texture in the same format as the source, which isn't possible for compressed textures
+            if texture.descriptor.format.is_compressed() {
+                return;
+            }
+
             let mut final_texture = texture.clone();
 
             // Finish the current render pass (in case it's updating the current texture)
@@ -1190,6 +1516,14 @@ impl WgpuRenderer {
         let g       = (g as f64) / 255.0;
         let b       = (b as f64) / 255.0;
         let a       = (a as f64) / 255.0;
+
+        // Clear colours also need to be linear for sRGB and floating-point targets
+        let (r, g, b) = if is_linear_format(state.pipeline_configuration.texture_format) {
+            (srgb_to_linear(r), srgb_to_linear(g), srgb_to_linear(b))
+        } else {
+            (r, g, b)
+        };
+
         let color   = wgpu::Color { r, g, b, a };
         
         state.render_pass_resources.clear = Some(color);
@@ -1431,6 +1765,22 @@ impl WgpuRenderer {
     }
 }
 
+impl RenderCapabilities for WgpuRenderer {
+    ///
+    /// Returns true if this renderer implements the specified action
+    ///
+    fn supports_action(&self, action: &RenderAction) -> bool {
+        match action {
+            RenderAction::UseShader(ShaderType::DashedLine { .. })          => false,
+            RenderAction::CreateTextureCompressed(_, Size2D(w, h), format)  => self.device.features().contains(compressed_texture_format(*format).1) && format.is_valid_size(*w, *h),
+            RenderAction::PushStencilClip                                   |
+            RenderAction::EndStencilClip                                    |
+            RenderAction::PopStencilClip                                    => false,
+            _                                                               => true,
+        }
+    }
+}
+
 ///
 /// Converts a canvas transform to a rendering matrix
 ///
@@ -31,6 +31,18 @@ pub enum ColorPostProcessingStep {
 
     /// The colour is blended so that at alpha (0), the RGB values are (1,1,1) - the inverse of pre-multiplications
     InvertColorAlpha,
+
+    /// As for NoPostProcessing, but the output is converted to linear values (for sRGB targets, which encode the colour when it's written)
+    NoPostProcessingLinearOutput,
+
+    /// As for NoPostProcessingLinearOutput, for shaders that produce colours with premultiplied alpha
+    NoPostProcessingPremultipliedLinearOutput,
+
+    /// As for MultiplyAlpha, but the output is converted to linear values
+    MultiplyAlphaLinearOutput,
+
+    /// As for InvertColorAlpha, but the output is converted to linear values
+    InvertColorAlphaLinearOutput,
 }
 
 ///
@@ -143,6 +155,22 @@ pub enum WgpuShader {
     Filter(FilterShader),
 }
 
+impl WgpuShader {
+    ///
+    /// Returns the version of this shader that produces linear colour values, for rendering to sRGB targets
+    ///
+    /// `source_is_premultiplied` should be true if the shader's colour output has premultiplied alpha
+    ///
+    pub (crate) fn with_linear_output(&self, source_is_premultiplied: bool) -> WgpuShader {
+        match self {
+            WgpuShader::Simple(variant, post_processing)                                        => WgpuShader::Simple(*variant, post_processing.linear_output(source_is_premultiplied)),
+            WgpuShader::Texture(variant, input_type, position, alpha_blend, post_processing)    => WgpuShader::Texture(*variant, *input_type, *position, *alpha_blend, post_processing.linear_output(source_is_premultiplied)),
+            WgpuShader::LinearGradient(variant, position, alpha_blend, post_processing)         => WgpuShader::LinearGradient(*variant, *position, *alpha_blend, post_processing.linear_output(source_is_premultiplied)),
+            WgpuShader::Filter(filter)                                                          => WgpuShader::Filter(*filter),
+        }
+    }
+}
+
 impl Default for WgpuShader {
     fn default() -> WgpuShader {
         WgpuShader::Simple(StandardShaderVariant::NoClipping, ColorPostProcessingStep::NoPostProcessing)
@@ -153,11 +181,66 @@ impl ColorPostProcessingStep {
     ///
     /// Retrieves the `color_post_process` function for this post-processing step
     ///
-    fn shader_function(&self) -> &'static str {
+    fn shader_function(&self) -> String {
+        match self {
+            ColorPostProcessingStep::NoPostProcessing               => include_str!("../../shaders/simple/color_no_post_processing.wgsl").to_string(),
+            ColorPostProcessingStep::MultiplyAlpha                  => include_str!("../../shaders/simple/color_multiply_alpha.wgsl").to_string(),
+            ColorPostProcessingStep::InvertColorAlpha               => include_str!("../../shaders/simple/color_invert_alpha.wgsl").to_string(),
+
+            ColorPostProcessingStep::NoPostProcessingLinearOutput               |
+            ColorPostProcessingStep::NoPostProcessingPremultipliedLinearOutput  |
+            ColorPostProcessingStep::MultiplyAlphaLinearOutput                  |
+            ColorPostProcessingStep::InvertColorAlphaLinearOutput               => {
+                // The display post-processing function is renamed so the linear output function can call it
+                let display_function = self.display_output().shader_function().replace("fn color_post_process(", "fn color_post_process_display(");
+
+                // The sRGB curve is not linear, so premultiplied colours need to be converted without their alpha
+                let linear_function = if self.output_is_premultiplied() {
+                    include_str!("../../shaders/simple/color_linear_output_premultiplied.wgsl")
+                } else {
+                    include_str!("../../shaders/simple/color_linear_output.wgsl")
+                };
+
+                format!("{}\n\n{}\n\n{}", display_function, include_str!("../../shaders/simple/srgb_to_linear.wgsl"), linear_function)
+            }
+        }
+    }
+
+    ///
+    /// Returns the version of this step that converts its output to linear values (for rendering to sRGB targets)
+    ///
+    /// `source_is_premultiplied` should be true if the shader produces colours with premultiplied alpha before this step
+    ///
+    pub fn linear_output(&self, source_is_premultiplied: bool) -> ColorPostProcessingStep {
+        match self {
+            ColorPostProcessingStep::NoPostProcessing   => if source_is_premultiplied { ColorPostProcessingStep::NoPostProcessingPremultipliedLinearOutput } else { ColorPostProcessingStep::NoPostProcessingLinearOutput },
+            ColorPostProcessingStep::MultiplyAlpha      => ColorPostProcessingStep::MultiplyAlphaLinearOutput,
+            ColorPostProcessingStep::InvertColorAlpha   => ColorPostProcessingStep::InvertColorAlphaLinearOutput,
+            linear_output                               => *linear_output,
+        }
+    }
+
+    ///
+    /// True if the output of this step has premultiplied alpha (only meaningful for the linear output steps)
+    ///
+    fn output_is_premultiplied(&self) -> bool {
+        match self {
+            ColorPostProcessingStep::NoPostProcessingPremultipliedLinearOutput  |
+            ColorPostProcessingStep::MultiplyAlphaLinearOutput                  => true,
+            _                                                                   => false,
+        }
+    }
+
+    ///
+    /// Returns the version of this step that leaves its output in display (sRGB) values
+    ///
+    pub fn display_output(&self) -> ColorPostProcessingStep {
         match self {
-            ColorPostProcessingStep::NoPostProcessing   => include_str!("../../shaders/simple/color_no_post_processing.wgsl"),
-            ColorPostProcessingStep::MultiplyAlpha      => include_str!("../../shaders/simple/color_multiply_alpha.wgsl"),
-            ColorPostProcessingStep::InvertColorAlpha   => include_str!("../../shaders/simple/color_invert_alpha.wgsl"),
+            ColorPostProcessingStep::NoPostProcessingLinearOutput               => ColorPostProcessingStep::NoPostProcessing,
+            ColorPostProcessingStep::NoPostProcessingPremultipliedLinearOutput  => ColorPostProcessingStep::NoPostProcessing,
+            ColorPostProcessingStep::MultiplyAlphaLinearOutput                  => ColorPostProcessingStep::MultiplyAlpha,
+            ColorPostProcessingStep::InvertColorAlphaLinearOutput               => ColorPostProcessingStep::InvertColorAlpha,
+            display_output                                                      => *display_output,
         }
     }
 }
@@ -0,0 +1,298 @@
+#[cfg(not(feature="render-wgpu"))]
+fn main() {
+    panic!("This example requires the render-wgpu feature to be set");
+}
+
+///
+/// Demonstrates rendering a canvas to a texture owned by the application, which is then sampled by the application's own
+/// wgpu pipeline.
+///
+/// The canvas is drawn once to a 512x512 texture using `WgpuRenderer::from_texture()`. `on_rendering_complete()` reports
+/// when the GPU has finished drawing it, after which the texture is mapped onto a quad that spins around the y axis. This is
+/// the approach to use when embedding `flo_canvas` drawings into a larger wgpu application, such as a 3D scene.
+///
+#[cfg(feature="render-wgpu")]
+fn main() {
+    use flo_canvas::*;
+    use flo_render::*;
+    use flo_render_canvas::*;
+
+    use winit::window;
+    use winit::event::{Event, WindowEvent};
+    use winit::event_loop::{ControlFlow, EventLoop};
+
+    use futures::prelude::*;
+    use futures::executor;
+    use std::sync::*;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::{Instant};
+
+    const CANVAS_SIZE: u32 = 512;
+
+    // Draw some shapes on a canvas
+    let mut drawing = vec![];
+    drawing.clear_canvas(Color::Rgba(0.9, 0.9, 0.8, 1.0));
+    drawing.canvas_height(1000.0);
+    drawing.center_region(0.0, 0.0, 1000.0, 1000.0);
+
+    drawing.new_path();
+    drawing.rect(100.0, 100.0, 900.0, 900.0);
+    drawing.fill_color(Color::Rgba(0.2, 0.4, 0.8, 1.0));
+    drawing.fill();
+
+    drawing.new_path();
+    drawing.circle(500.0, 500.0, 300.0);
+    drawing.fill_color(Color::Rgba(0.9, 0.6, 0.1, 1.0));
+    drawing.fill();
+    drawing.line_width(20.0);
+    drawing.stroke_color(Color::Rgba(0.0, 0.0, 0.0, 1.0));
+    drawing.stroke();
+
+    // Convert the drawing to render actions for a canvas-sized target
+    let mut canvas_renderer = CanvasRenderer::new();
+    canvas_renderer.set_viewport(0.0..(CANVAS_SIZE as f32), 0.0..(CANVAS_SIZE as f32), CANVAS_SIZE as f32, CANVAS_SIZE as f32, 1.0);
+
+    let rendering = executor::block_on(async {
+        canvas_renderer.draw(drawing.into_iter()).collect::<Vec<_>>().await
+    });
+
+    // Set up an event loop and a window that reports to it
+    let event_loop  = EventLoop::new();
+    let window      = window::Window::new(&event_loop).unwrap();
+
+    executor::block_on(async move {
+        // Create a new WGPU instance, surface and adapter
+        let instance    = wgpu::Instance::new(Default::default());
+        let surface     = unsafe { instance.create_surface(&window).expect("Failed to create surface") };
+        let adapter     = instance.request_adapter(&wgpu::RequestAdapterOptions {
+            power_preference:       wgpu::PowerPreference::default(),
+            force_fallback_adapter: false,
+            compatible_surface:     Some(&surface),
+        }).await.unwrap();
+
+        // Fetch the device and the queue
+        let (device, queue) = adapter.request_device(&wgpu::DeviceDescriptor {
+            label:      None,
+            features:   wgpu::Features::empty(),
+            limits:     wgpu::Limits::downlevel_webgl2_defaults().using_resolution(adapter.limits())
+        }, None).await.unwrap();
+
+        let device          = Arc::new(device);
+        let queue           = Arc::new(queue);
+        let adapter         = Arc::new(adapter);
+
+        // Create the texture that the canvas is rendered to: this belongs to us, so we can sample from it as well as render to it
+        let canvas_format   = wgpu::TextureFormat::Rgba8Unorm;
+        let canvas_texture  = Arc::new(device.create_texture(&wgpu::TextureDescriptor {
+            label:              Some("canvas_texture"),
+            size:               wgpu::Extent3d { width: CANVAS_SIZE, height: CANVAS_SIZE, depth_or_array_layers: 1 },
+            mip_level_count:    1,
+            sample_count:       1,
+            dimension:          wgpu::TextureDimension::D2,
+            format:             canvas_format,
+            usage:              wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats:       &[],
+        }));
+
+        // Render the canvas to the texture, and wait for the GPU to finish with it
+        let mut renderer    = WgpuRenderer::from_texture(Arc::clone(&device), Arc::clone(&queue), Arc::clone(&canvas_texture), Arc::clone(&adapter), canvas_format, (CANVAS_SIZE, CANVAS_SIZE));
+        let canvas_ready    = Arc::new(AtomicBool::new(false));
+
+        renderer.render_to_surface(rendering);
+
+        let ready = Arc::clone(&canvas_ready);
+        renderer.on_rendering_complete(move || ready.store(true, Ordering::Release));
+        renderer.finish();
+
+        println!("Canvas texture ready: {}", canvas_ready.load(Ordering::Acquire));
+
+        // Configure the window surface
+        let size            = window.inner_size();
+        let surface_format  = surface.get_capabilities(&*adapter).formats[0];
+        let mut config      = wgpu::SurfaceConfiguration {
+            usage:          wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format:         surface_format,
+            width:          size.width.max(1),
+            height:         size.height.max(1),
+            present_mode:   wgpu::PresentMode::AutoVsync,
+            alpha_mode:     wgpu::CompositeAlphaMode::Auto,
+            view_formats:   vec![],
+        };
+        surface.configure(&*device, &config);
+
+        // Create a pipeline that draws a spinning quad with the canvas texture on it
+        let shader          = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label:  Some("spinning_quad"),
+            source: wgpu::ShaderSource::Wgsl(SPINNING_QUAD_SHADER.into()),
+        });
+
+        let uniforms        = device.create_buffer(&wgpu::BufferDescriptor {
+            label:              Some("spinning_quad_uniforms"),
+            size:               16,
+            usage:              wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let sampler         = device.create_sampler(&wgpu::SamplerDescriptor {
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            ..Default::default()
+        });
+        let canvas_view     = canvas_texture.create_view(&wgpu::TextureViewDescriptor::default());
+
+        let bind_layout     = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label:      Some("spinning_quad_bindings"),
+            entries:    &[
+                wgpu::BindGroupLayoutEntry {
+                    binding:    0,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty:         wgpu::BindingType::Buffer { ty: wgpu::BufferBindingType::Uniform, has_dynamic_offset: false, min_binding_size: None },
+                    count:      None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding:    1,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty:         wgpu::BindingType::Texture { sample_type: wgpu::TextureSampleType::Float { filterable: true }, view_dimension: wgpu::TextureViewDimension::D2, multisampled: false },
+                    count:      None,
+                },
+                wgpu::BindGroupLayoutEntry {
+                    binding:    2,
+                    visibility: wgpu::ShaderStages::FRAGMENT,
+                    ty:         wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
+                    count:      None,
+                },
+            ],
+        });
+        let bind_group      = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label:      Some("spinning_quad_bindings"),
+            layout:     &bind_layout,
+            entries:    &[
+                wgpu::BindGroupEntry { binding: 0, resource: uniforms.as_entire_binding() },
+                wgpu::BindGroupEntry { binding: 1, resource: wgpu::BindingResource::TextureView(&canvas_view) },
+                wgpu::BindGroupEntry { binding: 2, resource: wgpu::BindingResource::Sampler(&sampler) },
+            ],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label:                  Some("spinning_quad"),
+            bind_group_layouts:     &[&bind_layout],
+            push_constant_ranges:   &[],
+        });
+        let pipeline        = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label:          Some("spinning_quad"),
+            layout:         Some(&pipeline_layout),
+            vertex:         wgpu::VertexState { module: &shader, entry_point: "vs_main", buffers: &[] },
+            primitive:      wgpu::PrimitiveState::default(),
+            depth_stencil:  None,
+            multisample:    wgpu::MultisampleState::default(),
+            fragment:       Some(wgpu::FragmentState {
+                module:         &shader,
+                entry_point:    "fs_main",
+                targets:        &[Some(wgpu::ColorTargetState { format: surface_format, blend: Some(wgpu::BlendState::REPLACE), write_mask: wgpu::ColorWrites::ALL })],
+            }),
+            multiview:      None,
+        });
+
+        // Run the main event loop, redrawing the quad continuously
+        let start_time = Instant::now();
+
+        event_loop.run(move |event, _, control_flow| {
+            *control_flow = ControlFlow::Poll;
+
+            match event {
+                Event::WindowEvent { event: WindowEvent::CloseRequested, .. } => {
+                    *control_flow = ControlFlow::Exit;
+                }
+
+                Event::WindowEvent { event: WindowEvent::Resized(size), .. } => {
+                    config.width    = size.width.max(1);
+                    config.height   = size.height.max(1);
+                    surface.configure(&*device, &config);
+                }
+
+                Event::MainEventsCleared => {
+                    window.request_redraw();
+                }
+
+                Event::RedrawRequested(_) => {
+                    // Update the angle of the quad
+                    let angle       = start_time.elapsed().as_secs_f32();
+                    let aspect      = (config.width as f32) / (config.height as f32);
+                    let uniform_data = [angle, aspect, 0.0, 0.0].iter().flat_map(|value| value.to_ne_bytes()).collect::<Vec<u8>>();
+                    queue.write_buffer(&uniforms, 0, &uniform_data);
+
+                    // Draw the quad to the window
+                    let frame       = match surface.get_current_texture() { Ok(frame) => frame, Err(_) => { return; } };
+                    let frame_view  = frame.texture.create_view(&wgpu::TextureViewDescriptor::default());
+                    let mut encoder = device.create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some("spinning_quad") });
+
+                    {
+                        let mut render_pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+                            label:                      Some("spinning_quad"),
+                            color_attachments:          &[Some(wgpu::RenderPassColorAttachment {
+                                view:           &frame_view,
+                                resolve_target: None,
+                                ops:            wgpu::Operations { load: wgpu::LoadOp::Clear(wgpu::Color { r: 0.1, g: 0.1, b: 0.15, a: 1.0 }), store: wgpu::StoreOp::Store },
+                            })],
+                            depth_stencil_attachment:   None,
+                            timestamp_writes:           None,
+                            occlusion_query_set:        None,
+                        });
+
+                        render_pass.set_pipeline(&pipeline);
+                        render_pass.set_bind_group(0, &bind_group, &[]);
+                        render_pass.draw(0..6, 0..1);
+                    }
+
+                    queue.submit(Some(encoder.finish()));
+                    frame.present();
+                }
+
+                _ => {}
+            }
+        });
+    });
+}
+
+/// Shader that draws a quad spinning around the y axis, textured with the canvas
+#[cfg(feature="render-wgpu")]
+const SPINNING_QUAD_SHADER: &'static str = "
+struct Uniforms {
+    angle:  f32,
+    aspect: f32,
+    unused: vec2<f32>,
+}
+
+@group(0) @binding(0) var<uniform> uniforms: Uniforms;
+@group(0) @binding(1) var canvas_texture: texture_2d<f32>;
+@group(0) @binding(2) var canvas_sampler: sampler;
+
+struct VertexOutput {
+    @builtin(position)  position:   vec4<f32>,
+    @location(0)        tex_coord:  vec2<f32>,
+}
+
+@vertex
+fn vs_main(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(-1.0, -1.0), vec2<f32>(1.0, -1.0), vec2<f32>(1.0, 1.0),
+        vec2<f32>(-1.0, -1.0), vec2<f32>(1.0, 1.0), vec2<f32>(-1.0, 1.0),
+    );
+    let corner = corners[vertex_index];
+
+    // Rotate around the y axis and move away from the viewer, then apply a simple perspective projection
+    let x = corner.x * cos(uniforms.angle) * 0.6;
+    let y = corner.y * 0.6;
+    let z = corner.x * sin(uniforms.angle) * 0.6 + 2.0;
+
+    var result: VertexOutput;
+    result.position     = vec4<f32>(x * 2.0 / uniforms.aspect, y * 2.0, 0.5, z);
+    result.tex_coord    = vec2<f32>((corner.x + 1.0) * 0.5, (1.0 - corner.y) * 0.5);
+
+    return result;
+}
+
+@fragment
+fn fs_main(vertex: VertexOutput) -> @location(0) vec4<f32> {
+    return textureSample(canvas_texture, canvas_sampler, vertex.tex_coord);
+}
+";
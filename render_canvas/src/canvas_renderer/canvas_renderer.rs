@@ -4,6 +4,7 @@ use crate::renderer_worker::*;
 use crate::renderer_stream::*;
 use crate::resource_ids::*;
 use crate::layer_handle::*;
+use crate::render_entity::*;
 
 use super::tessellate_build_path::*;
 
@@ -15,15 +16,26 @@ use flo_stream::*;
 use ::desync::*;
 
 use futures::prelude::*;
+use futures::executor;
+use futures::channel::mpsc;
 use num_cpus;
 
 use std::collections::{HashMap};
 use std::ops::{Range};
 use std::sync::*;
 
+/// The default tolerance in pixels to use when flattening curves during tessellation
+const DEFAULT_TESSELLATION_TOLERANCE: f32 = 0.2;
+
+/// The smallest tolerance in pixels that can be used when flattening curves (smaller values generate huge numbers of triangles)
+const MIN_TESSELLATION_TOLERANCE: f32 = 0.001;
+
 ///
 /// Changes commands for `flo_canvas` into commands for `flo_render`
 ///
+/// Tessellation is performed by a set of worker threads. Dropping the renderer part way through a drawing is safe: the
+/// workers finish any tessellation they've already started before they're freed.
+///
 pub struct CanvasRenderer {
     /// The worker threads
     workers: Vec<Arc<Desync<CanvasWorker>>>,
@@ -58,6 +70,12 @@ pub struct CanvasRenderer {
     /// The transforms pushed to the stack when PushState was called
     pub (super) transform_stack: Vec<canvas::Transform2D>,
 
+    /// The opacity applied to everything that's filled or stroked
+    pub (super) global_alpha: f32,
+
+    /// The global alpha values pushed to the stack when PushState was called
+    pub (super) global_alpha_stack: Vec<f32>,
+
     /// The next ID to assign to an entity for tessellation
     pub (super) next_entity_id: usize,
 
@@ -71,7 +89,10 @@ pub struct CanvasRenderer {
     viewport_origin: (f32, f32),
 
     /// The width and size of the viewport we're rendering to
-    pub (super) viewport_size: (f32, f32)
+    pub (super) viewport_size: (f32, f32),
+
+    /// The maximum distance in pixels between a curve and the lines used to approximate it during tessellation
+    pub (super) tessellation_tolerance: f32,
 }
 
 impl CanvasRenderer {
@@ -82,6 +103,8 @@ impl CanvasRenderer {
         // Create the shared core
         let core = RenderCore {
             frame_starts:               0,
+            frames_shown:               0,
+            frame_complete:             None,
             setup_actions:              vec![],
             layers:                     vec![],
             free_layers:                vec![],
@@ -103,6 +126,7 @@ impl CanvasRenderer {
             free_textures:              vec![],
             unused_render_target_id:    16,
             free_render_targets:        vec![],
+            debug_layer_bounds:         false,
         };
         let core = Arc::new(Desync::new(core));
 
@@ -134,12 +158,15 @@ impl CanvasRenderer {
             inverse_viewport_transform: canvas::Transform2D::identity(),
             active_transform:           canvas::Transform2D::identity(),
             transform_stack:            vec![],
+            global_alpha:               1.0,
+            global_alpha_stack:         vec![],
             namespace_stack:            vec![],
             next_entity_id:             0,
             window_size:                (1.0, 1.0),
             window_scale:               1.0,
             viewport_origin:            (0.0, 0.0),
             viewport_size:              (1.0, 1.0),
+            tessellation_tolerance:     DEFAULT_TESSELLATION_TOLERANCE,
         }
     }
 
@@ -202,6 +229,109 @@ impl CanvasRenderer {
         self.viewport_size              = (viewport_width, viewport_height);
     }
 
+    ///
+    /// Sets the tolerance, in pixels, to use when flattening curves during tessellation
+    ///
+    /// Smaller values produce smoother curves at the cost of generating more triangles. This applies to any drawing instructions
+    /// that are processed after it is set: existing tessellations are not regenerated.
+    ///
+    /// The tolerance is clamped to a minimum of 0.001 pixels, and the default tolerance is used if the value is infinite or NaN.
+    ///
+    pub fn set_tessellation_tolerance(&mut self, tolerance_pixels: f32) {
+        self.tessellation_tolerance = if tolerance_pixels.is_finite() {
+            tolerance_pixels.max(MIN_TESSELLATION_TOLERANCE)
+        } else {
+            DEFAULT_TESSELLATION_TOLERANCE
+        };
+    }
+
+    ///
+    /// Returns the tolerance, in pixels, used when flattening curves during tessellation
+    ///
+    pub fn tessellation_tolerance(&self) -> f32 {
+        self.tessellation_tolerance
+    }
+
+    ///
+    /// Frees all of the textures, gradients and sprites that were defined in a particular namespace
+    ///
+    /// If a sprite from the namespace is currently selected for drawing, layer 0 is selected instead.
+    ///
+    pub fn free_namespace(&mut self, namespace: canvas::NamespaceId) {
+        let namespace_id = namespace.local_id();
+
+        // Stop drawing to any sprite that's about to be freed
+        if self.current_sprite.is_some() && self.current_namespace == namespace_id {
+            self.tes_layer(canvas::LayerId(0));
+        }
+
+        self.core.sync(|core| core.free_namespace(namespace_id));
+    }
+
+    ///
+    /// Sets whether or not the bounding box of each layer is drawn over the top of the rendering
+    ///
+    /// This is intended for debugging: it makes it possible to see where the renderer thinks the contents of each layer are.
+    ///
+    pub fn set_debug_layer_bounds(&mut self, enabled: bool) {
+        self.core.sync(|core| core.debug_layer_bounds = enabled);
+    }
+
+    ///
+    /// Sets a channel that is sent a message every time a frame that was displayed by `ShowFrame` finishes rendering
+    ///
+    /// A message is sent once the render stream for the frame has generated all of its actions, so this can be used to keep
+    /// external state (eg, a simulation) in step with what is being drawn. Frames that are suspended by `StartFrame` are only
+    /// acknowledged once their `ShowFrame` has been processed.
+    ///
+    pub fn notify_frame_complete(&mut self, frame_complete: mpsc::UnboundedSender<()>) {
+        self.core.sync(|core| core.frame_complete = Some(frame_complete));
+    }
+
+    ///
+    /// Returns the bounding box of the contents of a sprite, in the sprite's own coordinate space
+    ///
+    /// The bounds are calculated from the drawing instructions that have been processed so far, and will be `None` if the sprite
+    /// is not defined or does not contain anything.
+    ///
+    pub fn sprite_bounds(&self, namespace: canvas::NamespaceId, sprite_id: canvas::SpriteId) -> Option<canvas::SpriteBounds> {
+        let namespace_id = namespace.local_id();
+
+        self.core.sync(|core| {
+            let sprite_layer    = *core.sprites.get(&(namespace_id, sprite_id))?;
+            let bounds          = core.layer_readonly(sprite_layer).bounds;
+
+            if bounds.is_undefined() {
+                None
+            } else {
+                Some(canvas::SpriteBounds(canvas::SpritePosition(bounds.min_x, bounds.min_y), canvas::SpriteSize(bounds.width(), bounds.height())))
+            }
+        })
+    }
+
+    ///
+    /// Returns the number of triangles that have been tessellated for the layers and sprites in the current drawing
+    ///
+    /// This can be used to estimate how expensive a drawing will be to render (eg, to warn when content is too complex). It
+    /// counts the triangles generated for fills and strokes processed so far, but not the triangles used for clipping masks.
+    ///
+    pub fn triangle_count(&self) -> usize {
+        self.core.sync(|core| {
+            let core: &RenderCore   = core;
+            let all_layers          = core.layers.iter().cloned()
+                .chain(core.sprites.values().cloned());
+
+            all_layers
+                .flat_map(|layer_id| core.layer_readonly(layer_id).render_order.iter())
+                .map(|entity| match entity {
+                    RenderEntity::VertexBuffer(buffers, _)      => buffers.indices.len() / 3,
+                    RenderEntity::DrawIndexed(_, _, num_items)  => *num_items / 3,
+                    _                                           => 0,
+                })
+                .sum()
+        })
+    }
+
     ///
     /// Returns the coordinates of the viewport, as x and y ranges
     ///
@@ -289,6 +419,7 @@ impl CanvasRenderer {
 
                     Fill                                        => self.tes_fill(&mut path_state, &mut job_publisher, &mut pending_jobs).await,
                     Stroke                                      => self.tes_stroke(&mut path_state, &mut job_publisher, &mut pending_jobs).await,
+                    DrawMesh(vertices, indices)                 => self.tes_draw_mesh(&mut path_state, vertices, indices),
 
                     LineWidth(width)                            => self.tes_line_width(width),
                     LineWidthPixels(pixel_width)                => self.tes_line_width_pixels(pixel_width),
@@ -302,6 +433,7 @@ impl CanvasRenderer {
                     FillTexture(texture_id, min, max)           => self.tes_fill_texture(self.current_namespace, texture_id, min, max),
                     FillGradient(gradient_id, min, max)         => self.tes_fill_gradient(self.current_namespace, gradient_id, min, max),
                     FillTransform(transform)                    => self.tes_fill_transform(transform),
+                    GlobalAlpha(alpha)                          => self.tes_global_alpha(alpha),
                     StrokeColor(color)                          => self.tes_stroke_color(color),
                     BlendMode(blend_mode)                       => self.tes_blend_mode(blend_mode),
 
@@ -456,6 +588,32 @@ impl CanvasRenderer {
         // Return a stream of results from processing the drawing
         RenderStream::new(core, processing, viewport_transform, viewport_size, background_vertex_buffer, initialise, finalize)
     }
+
+    ///
+    /// Processes a set of drawing instructions and returns the render actions that would be generated for them, once the drawing
+    /// has been fully tessellated
+    ///
+    /// This generates the same actions as the stream returned by `draw()`, collected into a list, which is useful for testing or
+    /// for passing the actions on to somewhere other than a renderer.
+    ///
+    pub async fn render_actions_for_async<'a, DrawIter: IntoIterator<Item=&'a canvas::Draw>>(&mut self, drawing: DrawIter) -> Vec<render::RenderAction> {
+        let drawing = drawing.into_iter().cloned().collect::<Vec<_>>();
+
+        self.draw(drawing.into_iter()).collect().await
+    }
+
+    ///
+    /// Processes a set of drawing instructions and returns the render actions that would be generated for them, blocking until
+    /// the drawing has been fully tessellated
+    ///
+    /// This generates the same actions as the stream returned by `draw()`, but synchronously, which is useful for testing or for
+    /// passing the actions on to somewhere other than a renderer. As this blocks the calling thread, it must not be called from
+    /// async code (where it would stall the executor, and can deadlock a single-threaded one): use `render_actions_for_async()`
+    /// there instead.
+    ///
+    pub fn render_actions_for<'a, DrawIter: IntoIterator<Item=&'a canvas::Draw>>(&mut self, drawing: DrawIter) -> Vec<render::RenderAction> {
+        executor::block_on(self.render_actions_for_async(drawing))
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,6 @@
 mod canvas_renderer;
 mod tessellate_path;
+mod tessellate_mesh;
 mod tessellate_frame;
 mod tessellate_build_path;
 mod tessellate_properties;
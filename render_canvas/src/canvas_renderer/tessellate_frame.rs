@@ -22,6 +22,10 @@ impl CanvasRenderer {
             if core.frame_starts > 0 { 
                 core.frame_starts -= 1;
             }
+
+            if core.frame_starts == 0 {
+                core.frames_shown += 1;
+            }
         });
     }
 
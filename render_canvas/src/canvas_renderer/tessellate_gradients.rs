@@ -23,7 +23,16 @@ impl CanvasRenderer {
     ///
     pub (super) fn tes_gradient_create(&mut self, namespace_id: usize, gradient_id: canvas::GradientId, initial_colour: canvas::Color) {
         self.core.sync(move |core| {
-            core.canvas_gradients.insert((namespace_id, gradient_id), RenderGradient::Defined(vec![canvas::GradientOp::Create(initial_colour)]));
+            let new_definition  = vec![canvas::GradientOp::Create(initial_colour)];
+
+            let new_gradient    = match core.canvas_gradients.remove(&(namespace_id, gradient_id)) {
+                // Gradients that already have a texture remember it so it can be updated in place when it's next rendered
+                Some(RenderGradient::Ready(texture_id, old_defn))           => RenderGradient::Redefined(texture_id, old_defn, new_definition),
+                Some(RenderGradient::Redefined(texture_id, old_defn, _))    => RenderGradient::Redefined(texture_id, old_defn, new_definition),
+                Some(RenderGradient::Defined(_)) | None                     => RenderGradient::Defined(new_definition),
+            };
+
+            core.canvas_gradients.insert((namespace_id, gradient_id), new_gradient);
         });
     }
 
@@ -40,11 +49,18 @@ impl CanvasRenderer {
                     defn.push(AddStop(pos, stop_colour))
                 }
 
-                Some(RenderGradient::Ready(_, defn)) => {
-                    // Gradient has been mapped to a texture (continue defining it as a new texture)
-                    let mut defn = defn.clone();
+                Some(RenderGradient::Redefined(_, _, defn)) => {
+                    // Gradient is being redefined after being mapped to a texture
+                    defn.push(AddStop(pos, stop_colour))
+                }
+
+                Some(RenderGradient::Ready(texture_id, defn)) => {
+                    // Gradient has been mapped to a texture (continue defining it, remembering the texture so it can be updated if it's no longer in use)
+                    let texture_id  = *texture_id;
+                    let old_defn    = defn.clone();
+                    let mut defn    = defn.clone();
                     defn.push(AddStop(pos, stop_colour));
-                    core.canvas_gradients.insert((namespace_id, gradient_id), RenderGradient::Defined(defn));
+                    core.canvas_gradients.insert((namespace_id, gradient_id), RenderGradient::Redefined(texture_id, old_defn, defn));
                 }
 
                 None => { }
@@ -95,6 +95,8 @@ impl CanvasRenderer {
         });
 
         self.active_transform   = canvas::Transform2D::identity();
+        self.global_alpha       = 1.0;
+        self.global_alpha_stack = vec![];
     }
 
     ///
@@ -201,6 +203,9 @@ impl CanvasRenderer {
     ///
     /// Clears all of the layers (leaving sprites, textures, etc intact)
     ///
+    /// Unlike clearing the canvas, the list of layers and their ordering is left unchanged: each layer is just emptied as if
+    /// `ClearLayer` had been used on it.
+    ///
     pub (super) fn tes_clear_all_layers(&mut self, path_state: &mut PathState) {
         *path_state = PathState::default();
 
@@ -209,16 +214,25 @@ impl CanvasRenderer {
 
             for handle in handles.into_iter() {
                 // Sprite layers are left alone
-                if core.layer(self.current_layer).state.is_sprite {
+                if core.layer(handle).state.is_sprite {
                     continue;
                 }
 
                 // Create a new layer
                 let mut layer   = Self::create_default_layer();
 
+                // Retain the modification count from the old layer (so any cached rendering of the layer is invalidated)
+                let old_layer   = core.layer(handle);
+                layer.state.modification_count  = old_layer.state.modification_count + 1;
+                layer.state.base_scale_factor   = old_layer.state.base_scale_factor;
+                layer.state.scale_factor        = old_layer.state.scale_factor;
+
                 // Swap into the layer list to replace the old one
                 mem::swap(core.layer(handle), &mut layer);
 
+                // Ensure the layer transform is up to date
+                core.layer(handle).update_transform(&self.active_transform);
+
                 // Free the data for the current layer
                 core.free_layer_entities(layer);
             }
@@ -0,0 +1,86 @@
+use crate::fill_state::*;
+use crate::render_entity::*;
+use crate::render_entity_details::*;
+
+use super::canvas_renderer::*;
+use super::tessellate_build_path::*;
+
+use flo_canvas as canvas;
+use flo_render as render;
+
+use lyon::tessellation::{VertexBuffers};
+
+impl CanvasRenderer {
+    ///
+    /// Draws a triangle mesh (which is sent straight to the renderer without needing to be tessellated)
+    ///
+    pub (super) fn tes_draw_mesh(&mut self, path_state: &mut PathState, vertices: Vec<canvas::MeshVertex>, indices: Vec<u16>) {
+        let layer_id            = self.current_layer;
+        let active_transform    = &self.active_transform;
+        let global_alpha        = self.global_alpha;
+
+        // Indices that are outside of the vertex list (or don't make up a whole triangle) are discarded
+        let num_vertices        = vertices.len();
+        let indices             = indices.chunks_exact(3)
+            .filter(|triangle| triangle.iter().all(|index| (*index as usize) < num_vertices))
+            .flatten()
+            .copied()
+            .collect::<Vec<_>>();
+
+        // A mesh with no triangles draws nothing (and has no bounds), so it's not added to the layer
+        if indices.is_empty() {
+            return;
+        }
+
+        self.core.sync(move |core| {
+            let layer = core.layer(layer_id);
+
+            // Rendering in a blend mode other than source over sets the 'commit before rendering' flag for this layer
+            if layer.state.blend_mode != canvas::BlendMode::SourceOver {
+                layer.commit_before_rendering = true;
+            }
+
+            // Update the transformation matrix
+            layer.update_transform(active_transform);
+
+            // Meshes are always drawn using the flat colour shader, with the colours taken from the vertices
+            match path_state.fill_state {
+                FillState::None     |
+                FillState::Color(_) => {
+                    if !path_state.dash_pattern.is_empty() {
+                        layer.render_order.push(RenderEntity::SetFlatColor);
+                    }
+                }
+
+                _                   => { layer.render_order.push(RenderEntity::SetFlatColor); }
+            }
+
+            path_state.fill_state   = FillState::None;
+            path_state.dash_pattern = vec![];
+
+            // When drawing to the erase layer (DesintationOut blend mode), all colour components are alpha components
+            let is_erasing          = layer.state.blend_mode == canvas::BlendMode::DestinationOut;
+            let vertices            = vertices.into_iter()
+                .map(|vertex| {
+                    let render::Rgba8(color)    = Self::render_color(vertex.color);
+                    let color                   = if global_alpha < 1.0 { [color[0], color[1], color[2], ((color[3] as f32) * global_alpha).round() as u8] } else { color };
+                    let color                   = if is_erasing { [color[3], color[3], color[3], color[3]] } else { color };
+
+                    render::Vertex2D {
+                        pos:        [vertex.pos.0, vertex.pos.1],
+                        tex_coord:  [0.0, 0.0],
+                        color:      color
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            // Add the vertex buffer directly to the layer: it'll be sent to the renderer along with the tessellated buffers
+            let details             = RenderEntityDetails::from_vertices(&vertices, &layer.state.current_matrix);
+            let geometry            = VertexBuffers { vertices, indices };
+
+            layer.render_order.push(RenderEntity::VertexBuffer(geometry, VertexBufferIntent::Draw));
+            layer.state.modification_count += 1;
+            layer.bounds.add_entity_with_details(details);
+        });
+    }
+}
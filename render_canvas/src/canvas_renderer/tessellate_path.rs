@@ -27,12 +27,15 @@ impl CanvasRenderer {
             let layer_id            = self.current_layer;
             let entity_id           = self.next_entity_id;
             let viewport_height     = self.viewport_size.1;
+            let tolerance           = self.tessellation_tolerance;
             let active_transform    = &self.active_transform;
+            let global_alpha        = self.global_alpha;
 
             self.next_entity_id += 1;
 
             let job = self.core.sync(move |core| {
-                let layer = core.layer(layer_id);
+                let layer       = core.layer(layer_id);
+                let fill_color  = layer.state.fill_color.with_global_alpha(global_alpha);
 
                 // Update the transformation matrix
                 layer.update_transform(active_transform);
@@ -43,9 +46,9 @@ impl CanvasRenderer {
                 }
 
                 // If the shader state has changed, generate the operations needed to use that shader state
-                if path_state.fill_state != layer.state.fill_color {
+                if path_state.fill_state != fill_color {
                     // Update the active fill state to match that of the layer
-                    match layer.state.fill_color {
+                    match fill_color {
                         FillState::None | FillState::Color(_) => { 
                             layer.render_order.push(RenderEntity::SetFlatColor);
                         }
@@ -70,18 +73,18 @@ impl CanvasRenderer {
                     }
 
                     path_state.dash_pattern = vec![];
-                    path_state.fill_state   = core.layer(layer_id).state.fill_color.clone();
+                    path_state.fill_state   = fill_color.clone();
                 } else if !path_state.dash_pattern.is_empty() {
                     // Ensure there's no dash pattern
                     layer.render_order.push(RenderEntity::SetFlatColor);
                     path_state.dash_pattern = vec![];
-                    path_state.fill_state   = layer.state.fill_color.clone();
+                    path_state.fill_state   = fill_color.clone();
                 }
 
                 // Create the render entity in the tessellating state
                 let layer               = core.layer(layer_id);
-                let scale_factor        = layer.state.tolerance_scale_factor(viewport_height);
-                let color               = layer.state.fill_color.clone();
+                let scale_factor        = layer.state.tolerance_scale_factor(viewport_height, tolerance);
+                let color               = fill_color;
                 let fill_rule           = layer.state.winding_rule;
                 let entity_index        = layer.render_order.len();
                 let transform           = layer.state.current_matrix;
@@ -118,7 +121,9 @@ impl CanvasRenderer {
             let layer_id            = self.current_layer;
             let entity_id           = self.next_entity_id;
             let viewport_height     = self.viewport_size.1;
+            let tolerance           = self.tessellation_tolerance;
             let active_transform    = &self.active_transform;
+            let global_alpha        = self.global_alpha;
             let dash_pattern        = &mut path_state.dash_pattern;
             let fill_state          = &mut path_state.fill_state;
 
@@ -151,13 +156,13 @@ impl CanvasRenderer {
                 }
 
                 // Create the render entity in the tessellating state
-                let scale_factor        = layer.state.tolerance_scale_factor(viewport_height);
+                let scale_factor        = layer.state.tolerance_scale_factor(viewport_height, tolerance);
                 let mut stroke_options  = layer.state.stroke_settings.clone();
                 let entity_index        = layer.render_order.len();
                 let transform           = layer.state.current_matrix;
 
                 // When drawing to the erase layer (DesintationOut blend mode), all colour components are alpha components
-                let color                   = stroke_options.stroke_color;
+                let color                   = FillState::Color(stroke_options.stroke_color).with_global_alpha(global_alpha).flat_color();
                 stroke_options.stroke_color = if layer.state.blend_mode == canvas::BlendMode::DestinationOut { render::Rgba8([color.0[3], color.0[3], color.0[3], color.0[3]]) } else { color };
 
                 layer.render_order.push(RenderEntity::Tessellating(entity_id));
@@ -192,6 +197,7 @@ impl CanvasRenderer {
             let layer_id            = self.current_layer;
             let entity_id           = self.next_entity_id;
             let viewport_height     = self.viewport_size.1;
+            let tolerance           = self.tessellation_tolerance;
             let active_transform    = &self.active_transform;
 
             self.next_entity_id += 1;
@@ -203,7 +209,7 @@ impl CanvasRenderer {
                 layer.update_transform(active_transform);
 
                 // Create the render entity in the tessellating state
-                let scale_factor        = layer.state.tolerance_scale_factor(viewport_height);
+                let scale_factor        = layer.state.tolerance_scale_factor(viewport_height, tolerance);
                 let color               = render::Rgba8([255, 255, 255, 255]);
                 let fill_rule           = layer.state.winding_rule;
                 let entity_index        = layer.render_order.len();
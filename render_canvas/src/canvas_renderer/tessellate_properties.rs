@@ -141,6 +141,12 @@ impl CanvasRenderer {
         });
     }
 
+    /// Sets the opacity for everything that's filled or stroked after this point
+    #[inline]
+    pub (super) fn tes_global_alpha(&mut self, alpha: f32) {
+        self.global_alpha = f32::max(0.0, f32::min(1.0, alpha));
+    }
+
     // Set the line color
     #[inline]
     pub (super) fn tes_stroke_color(&mut self, color: canvas::Color) {
@@ -76,6 +76,12 @@ impl CanvasRenderer {
     /// Renders a sprite with a set of transformations
     ///
     pub (super) fn tes_draw_sprite(&mut self, namespace_id: usize, sprite_id: canvas::SpriteId) { 
+        // Sprites drawn with a global alpha are faded using the alpha blend filter
+        if self.global_alpha < 1.0 {
+            self.tes_draw_sprite_with_filters(namespace_id, sprite_id, vec![]);
+            return;
+        }
+
         self.core.sync(|core| {
             let layer           = core.layer(self.current_layer);
             let sprite_matrix   = layer.state.sprite_matrix;
@@ -101,7 +107,7 @@ impl CanvasRenderer {
             layer.update_transform(&self.active_transform);
 
             // Turn the TextureFilters into filter requests
-            let filters = filters.into_iter().filter_map(|filter| {
+            let mut filters = filters.into_iter().filter_map(|filter| {
                 use canvas::TextureFilter::*;
 
                 match filter {
@@ -112,6 +118,11 @@ impl CanvasRenderer {
                 }
             }).collect::<Vec<_>>();
 
+            // The global alpha is applied after any other filters
+            if self.global_alpha < 1.0 {
+                filters.push(TextureFilterRequest::AlphaBlend(self.global_alpha));
+            }
+
             // Increase the usage count of any referenced textures
             for texture_id in filters.iter().flat_map(|filter| filter.used_textures()) {
                 core.add_texture_usage(texture_id);
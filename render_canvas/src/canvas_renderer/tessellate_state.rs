@@ -54,6 +54,7 @@ impl CanvasRenderer {
     ///
     pub (super) fn tes_push_state(&mut self) {
         self.transform_stack.push(self.active_transform);
+        self.global_alpha_stack.push(self.global_alpha);
         self.namespace_stack.push(self.current_namespace);
 
         self.core.sync(|core| {
@@ -74,6 +75,7 @@ impl CanvasRenderer {
         // The current transform is applied globally
         self.transform_stack.pop()
             .map(|transform| self.active_transform = transform);
+        if let Some(global_alpha) = self.global_alpha_stack.pop() { self.global_alpha = global_alpha; }
         if let Some(namespace) = self.namespace_stack.pop() { self.current_namespace = namespace;  };
 
         self.core.sync(|core| {
@@ -0,0 +1,53 @@
+use flo_canvas as canvas;
+use flo_render::*;
+
+///
+/// Returns the drawing features that a renderer supports, for use with `flo_canvas::drawing_for_capabilities()`
+///
+/// Gradients are supported if the renderer can create the 1D textures that gradients are stored in and draw with the linear
+/// gradient shader. Filters are supported if the renderer can apply all of the filters that the canvas uses to a texture.
+///
+pub fn drawing_capabilities<TRenderer: RenderCapabilities>(renderer: &TRenderer) -> canvas::DrawingCapabilities {
+    let gradient_actions = vec![
+        RenderAction::Create1DTextureBgra(TextureId(0), Size1D(64)),
+        RenderAction::UseShader(ShaderType::LinearGradient { texture: TextureId(0), texture_transform: Matrix::identity(), repeat: false, alpha: 1.0, clip_texture: None }),
+    ];
+
+    let filter_actions = vec![
+        RenderAction::FilterTexture(TextureId(0), vec![
+            TextureFilter::GaussianBlurHorizontal(1.0, 1.0, 9),
+            TextureFilter::GaussianBlurVertical(1.0, 1.0, 9),
+            TextureFilter::AlphaBlend(1.0),
+            TextureFilter::Mask(TextureId(1)),
+            TextureFilter::DisplacementMap(TextureId(1), 1.0, 1.0),
+        ]),
+    ];
+
+    canvas::DrawingCapabilities {
+        gradients:  gradient_actions.iter().all(|action| renderer.supports_action(action)),
+        filters:    filter_actions.iter().all(|action| renderer.supports_action(action)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    struct NoFilters;
+
+    impl RenderCapabilities for NoFilters {
+        fn supports_action(&self, action: &RenderAction) -> bool {
+            match action {
+                RenderAction::FilterTexture(_, _)   => false,
+                _                                   => true,
+            }
+        }
+    }
+
+    #[test]
+    fn unsupported_filters_are_reported() {
+        let capabilities = drawing_capabilities(&NoFilters);
+
+        assert!(capabilities == canvas::DrawingCapabilities { gradients: true, filters: false }, "{:?}", capabilities);
+    }
+}
@@ -131,6 +131,22 @@ impl FillState {
         }
     }
 
+    ///
+    /// Returns this fill state with its opacity multiplied by a global alpha value
+    ///
+    pub fn with_global_alpha(&self, global_alpha: f32) -> Self {
+        if global_alpha >= 1.0 {
+            return self.clone();
+        }
+
+        match self {
+            FillState::None                                                                     => self.clone(),
+            FillState::Color(render::Rgba8([r, g, b, a]))                                       => FillState::Color(render::Rgba8([*r, *g, *b, ((*a as f32) * global_alpha).round() as u8])),
+            FillState::Texture(render_texture, canvas_texture, matrix, repeat, alpha)           => FillState::Texture(*render_texture, *canvas_texture, *matrix, *repeat, *alpha * global_alpha),
+            FillState::LinearGradient(render_texture, canvas_gradient, matrix, repeat, alpha)   => FillState::LinearGradient(*render_texture, *canvas_gradient, *matrix, *repeat, *alpha * global_alpha)
+        }
+    }
+
     ///
     /// Updates the fill state with a transformed matrix
     ///
@@ -0,0 +1,223 @@
+use super::offscreen::*;
+
+use flo_canvas::*;
+use flo_render::*;
+
+use futures::prelude::*;
+use futures::stream;
+
+use std::collections::{HashSet};
+use std::sync::*;
+
+///
+/// A user-supplied identifier for a shape drawn to an id buffer
+///
+/// Shape IDs must be less than `ShapeId::MAX`, as they're encoded in the 24 bits of colour of each pixel. Shapes with larger
+/// IDs are drawn as if they had no ID.
+///
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct ShapeId(pub u32);
+
+impl ShapeId {
+    /// The largest value that can be stored in a shape ID, plus one
+    pub const MAX: u32 = 0xffffff;
+
+    ///
+    /// Returns this shape ID if it can be stored in an id buffer, or None if it's too large
+    ///
+    fn valid(self) -> Option<ShapeId> {
+        if self.0 < Self::MAX { Some(self) } else { None }
+    }
+
+    ///
+    /// The colour used to draw a shape with this ID (or no ID) into an id buffer
+    ///
+    fn id_color(shape_id: Option<ShapeId>) -> Color {
+        // 0 is used for shapes with no ID, so the IDs are offset by 1 (IDs that are too large are treated as no ID)
+        let value = match shape_id.and_then(ShapeId::valid) {
+            Some(ShapeId(id))   => id + 1,
+            None                => 0,
+        };
+
+        // The colour components are in the middle of the range for each byte, so they convert back to the same bytes
+        let component = |byte: u32| ((byte & 0xff) as f32 + 0.5) / 255.0;
+
+        Color::Rgba(component(value >> 16), component(value >> 8), component(value), 1.0)
+    }
+}
+
+///
+/// A buffer that stores the ID of the topmost shape at every pixel of a rendered canvas
+///
+/// This is a picking buffer: once it's been rendered, mapping a point to the shape that was drawn there is a single lookup.
+///
+#[derive(Clone, PartialEq, Debug)]
+pub struct IdBuffer {
+    /// The width of the buffer in pixels
+    width: usize,
+
+    /// The height of the buffer in pixels
+    height: usize,
+
+    /// The shape ID for each pixel
+    ids: Vec<Option<ShapeId>>,
+}
+
+impl IdBuffer {
+    ///
+    /// Decodes an id buffer from the pixels rendered by `render_id_buffer()`
+    ///
+    fn from_pixels(width: usize, height: usize, pixels: &[u8], shape_ids: &HashSet<ShapeId>) -> IdBuffer {
+        let ids = pixels.chunks_exact(4)
+            .map(|pixel| {
+                // Pixels that are not opaque are either empty or on the anti-aliased edge of a shape
+                if pixel[3] != 255 { return None; }
+
+                let value = ((pixel[0] as u32) << 16) | ((pixel[1] as u32) << 8) | (pixel[2] as u32);
+                if value == 0 { return None; }
+
+                // The edges of overlapping shapes can blend to a colour that doesn't represent a shape that was drawn
+                Some(ShapeId(value - 1)).filter(|shape_id| shape_ids.contains(shape_id))
+            })
+            .collect();
+
+        IdBuffer {
+            width:  width,
+            height: height,
+            ids:    ids,
+        }
+    }
+
+    ///
+    /// The width of this buffer in pixels
+    ///
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    ///
+    /// The height of this buffer in pixels
+    ///
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    ///
+    /// Returns the ID of the topmost shape at the specified pixel, if there is one
+    ///
+    /// Pixels are in the same order as the bitmap returned by `render_canvas_offscreen()`
+    ///
+    pub fn id_at(&self, x: usize, y: usize) -> Option<ShapeId> {
+        if x < self.width && y < self.height {
+            self.ids[x + y*self.width]
+        } else {
+            None
+        }
+    }
+}
+
+///
+/// Changes a drawing instruction so that it draws the colour representing a shape ID
+///
+fn id_drawing(shape_id: Option<ShapeId>, draw: Draw) -> Vec<Draw> {
+    match draw {
+        // Colours, alpha and blending are all replaced by the colour that represents the shape
+        Draw::FillColor(_)              |
+        Draw::FillTexture(_, _, _)      |
+        Draw::FillGradient(_, _, _)     |
+        Draw::StrokeColor(_)            |
+        Draw::GlobalAlpha(_)            |
+        Draw::BlendMode(_)              |
+        Draw::LayerAlpha(_, _)          |
+        Draw::LayerBlend(_, _)          => vec![],
+
+        // The background never has an ID
+        Draw::ClearCanvas(_)            => vec![Draw::ClearCanvas(Color::Rgba(0.0, 0.0, 0.0, 0.0))],
+
+        // Shapes are drawn using the colour of their ID
+        Draw::Fill                      |
+        Draw::DrawText(_, _, _, _)      |
+        Draw::DrawLaidOutText           => vec![Draw::FillColor(ShapeId::id_color(shape_id)), draw],
+        Draw::Stroke                    => vec![Draw::StrokeColor(ShapeId::id_color(shape_id)), draw],
+
+        // Meshes have their own colours, so every vertex is changed to the colour of the ID
+        Draw::DrawMesh(vertices, indices) => {
+            let id_color = ShapeId::id_color(shape_id);
+            let vertices = vertices.into_iter().map(|vertex| MeshVertex { color: id_color, ..vertex }).collect();
+
+            vec![Draw::DrawMesh(vertices, indices)]
+        }
+
+        other                           => vec![other],
+    }
+}
+
+///
+/// Renders an id buffer for a canvas in an offscreen context
+///
+/// The drawing instructions are paired with the ID of the shape that they're drawing: each fill or stroke is tagged with the
+/// ID it's paired with, and the resulting buffer contains the ID of the topmost shape at each pixel. Shapes with no ID still
+/// hide any shapes that are underneath them. Colours, gradients, textures and blend modes are ignored, so shapes are always
+/// treated as opaque.
+///
+/// The canvas is always rendered with anti-aliasing, so the edges of shapes are blended with whatever is underneath them. Edge
+/// pixels that are blended with the background report no ID, but where two tagged shapes meet, the blended colour can decode
+/// to the ID of a third shape that was drawn elsewhere on the canvas. IDs found on the edges of shapes should be treated as
+/// approximate: checking the pixels around a point will give a more reliable result.
+///
+pub fn render_id_buffer<'a, DrawStream, RenderContext>(context: &'a mut RenderContext, width: usize, height: usize, scale: f32, actions: DrawStream) -> impl 'a+Future<Output=IdBuffer>
+where
+    DrawStream:    'a+Stream<Item=(Option<ShapeId>, Draw)>,
+    RenderContext: 'a+OffscreenRenderContext
+{
+    async move {
+        // Track the IDs that are used so that blended edges can't produce IDs for shapes that were never drawn
+        let shape_ids       = Arc::new(Mutex::new(HashSet::new()));
+
+        let used_ids        = Arc::clone(&shape_ids);
+        let actions         = actions.flat_map(move |(shape_id, draw)| {
+            let shape_id = shape_id.and_then(ShapeId::valid);
+            if let Some(shape_id) = shape_id { used_ids.lock().unwrap().insert(shape_id); }
+
+            stream::iter(id_drawing(shape_id, draw))
+        });
+
+        // Render the IDs as colours
+        let pixels          = render_canvas_offscreen(context, width, height, scale, actions).await;
+
+        // Convert the colours back to IDs
+        let shape_ids       = shape_ids.lock().unwrap();
+        IdBuffer::from_pixels(width, height, &pixels, &*shape_ids)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn mesh_vertices_use_id_color() {
+        let vertices    = vec![MeshVertex::new(0.0, 0.0, Color::Rgba(1.0, 0.0, 0.0, 1.0)), MeshVertex::new(10.0, 0.0, Color::Rgba(0.0, 1.0, 0.0, 0.5)), MeshVertex::new(0.0, 10.0, Color::Rgba(0.0, 0.0, 1.0, 1.0))];
+        let id_drawing  = id_drawing(Some(ShapeId(42)), Draw::DrawMesh(vertices, vec![0, 1, 2]));
+
+        assert!(id_drawing.len() == 1);
+        match &id_drawing[0] {
+            Draw::DrawMesh(vertices, indices) => {
+                assert!(indices == &vec![0, 1, 2]);
+                assert!(vertices.iter().all(|vertex| vertex.color == ShapeId::id_color(Some(ShapeId(42)))));
+                assert!(vertices.iter().map(|vertex| vertex.pos).collect::<Vec<_>>() == vec![(0.0, 0.0), (10.0, 0.0), (0.0, 10.0)]);
+            }
+
+            other => { panic!("Expected a mesh, found {:?}", other); }
+        }
+    }
+
+    #[test]
+    fn large_ids_are_drawn_without_an_id() {
+        let id_drawing = id_drawing(Some(ShapeId(u32::MAX)), Draw::Fill);
+
+        assert!(id_drawing == vec![Draw::FillColor(ShapeId::id_color(None)), Draw::Fill], "{:?}", id_drawing);
+        assert!(ShapeId(ShapeId::MAX).valid() == None);
+        assert!(ShapeId(ShapeId::MAX-1).valid() == Some(ShapeId(ShapeId::MAX-1)));
+    }
+}
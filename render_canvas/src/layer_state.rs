@@ -3,7 +3,7 @@ use super::stroke_settings::*;
 
 use flo_canvas as canvas;
 
-use lyon::tessellation::{FillRule};
+use lyon::tessellation::{FillRule, FillOptions};
 
 ///
 /// The current state of a layer
@@ -56,9 +56,11 @@ impl LayerState {
     }
 
     ///
-    /// Returns the scale factor to use for fills and strokes given a particular viewport height
+    /// Returns the scale factor to use for fills and strokes given a particular viewport height and a tolerance in pixels
     ///
-    pub fn tolerance_scale_factor(&mut self, viewport_height: f32) -> f64 {
+    /// The tessellator multiplies its default tolerance by this value to find the tolerance to use in canvas coordinates
+    ///
+    pub fn tolerance_scale_factor(&mut self, viewport_height: f32, tolerance_pixels: f32) -> f64 {
         // Assume the viewport is at least a certain size (so if the rendering is initially to a very small viewport during initialisation we won't produce a wildly inaccurate rendering)
         let viewport_height = if viewport_height < 1000.0 {
             1000.0
@@ -69,7 +71,9 @@ impl LayerState {
         let scale_factor = self.scale_factor as f64;
         let scale_factor = if scale_factor.abs() < 0.000001 { 0.000001 } else { scale_factor };
 
-        // The window height is 2.0 - so 2.0/scale_factor = the height of the viewport with the current transformation. We use 4.0 instead of 2.0 to reduce the precision a bit for rendering.
-        (4.0/scale_factor) / viewport_height
+        // The window height is 2.0 - so 2.0/scale_factor = the height of the viewport with the current transformation, which gives us the size of a pixel
+        let pixel_size = (2.0/scale_factor) / viewport_height;
+
+        (pixel_size * (tolerance_pixels as f64)) / (FillOptions::DEFAULT_TOLERANCE as f64)
     }
 }
\ No newline at end of file
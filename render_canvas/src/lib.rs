@@ -16,11 +16,15 @@ mod renderer_layer;
 mod renderer_worker;
 mod renderer_stream;
 mod offscreen;
+mod id_buffer;
+mod drawing_capabilities;
 mod matrix;
 mod dynamic_texture_state;
 
 pub use self::canvas_renderer::*;
 pub use self::offscreen::*;
+pub use self::id_buffer::*;
+pub use self::drawing_capabilities::*;
 
 pub use flo_render::*;
 pub use flo_canvas as canvas;
@@ -4,11 +4,29 @@ use flo_canvas::*;
 use flo_render::*;
 
 use futures::prelude::*;
+use futures::stream;
 
 ///
 /// Renders a canvas in an offscreen context, returning the resulting bitmap
 ///
 pub fn render_canvas_offscreen<'a, DrawStream, RenderContext>(context: &'a mut RenderContext, width: usize, height: usize, scale: f32, actions: DrawStream) -> impl 'a+Future<Output=Vec<u8>>
+where
+    DrawStream:    'a+Stream<Item=Draw>,
+    RenderContext: 'a+OffscreenRenderContext 
+{
+    // No limit is applied to the size, so this can't fail
+    try_render_canvas_offscreen(context, width, height, scale, usize::MAX, actions)
+        .map(|result| result.unwrap())
+}
+
+///
+/// Renders a canvas in an offscreen context, returning the resulting bitmap, or an error if the width or height is larger
+/// than `max_dimension`
+///
+/// The size is checked before anything is allocated, so this is suitable for rendering sizes that come from an untrusted
+/// source. `DEFAULT_MAX_RENDER_DIMENSION` is a reasonable limit for most uses.
+///
+pub fn try_render_canvas_offscreen<'a, DrawStream, RenderContext>(context: &'a mut RenderContext, width: usize, height: usize, scale: f32, max_dimension: usize, actions: DrawStream) -> impl 'a+Future<Output=Result<Vec<u8>, RenderTargetError>>
 where
     DrawStream:    'a+Stream<Item=Draw>,
     RenderContext: 'a+OffscreenRenderContext 
@@ -19,7 +37,10 @@ where
         let mut actions         = actions.ready_chunks(10000);
 
         // Create the offscreen render target
-        let mut render_target   = context.create_render_target(width, height);
+        let mut render_target   = match context.try_create_render_target(width, height, max_dimension) {
+            Ok(render_target)   => render_target,
+            Err(err)            => { return Err(err); }
+        };
 
         // Create the canvas renderer
         let mut renderer        = CanvasRenderer::new();
@@ -38,6 +59,88 @@ where
         }
 
         // Result is the realized rendering
-        render_target.realize()
+        Ok(render_target.realize())
+    }
+}
+
+///
+/// Renders a canvas in an offscreen context at a scale factor (eg, 2.0 for a HiDPI display), returning the resulting bitmap
+///
+/// The bitmap is `logical_width * scale` by `logical_height * scale` pixels (rounded up), and the canvas is set up so that
+/// the coordinates (0, 0) to (logical_width, logical_height) cover the whole bitmap. A drawing authored in logical units
+/// will therefore render at the higher resolution without needing to be changed, and line widths specified in pixels are
+/// scaled to match.
+///
+pub fn render_canvas_offscreen_at_scale<'a, DrawStream, RenderContext>(context: &'a mut RenderContext, logical_width: usize, logical_height: usize, scale: f32, actions: DrawStream) -> impl 'a+Future<Output=Vec<u8>>
+where
+    DrawStream:    'a+Stream<Item=Draw>,
+    RenderContext: 'a+OffscreenRenderContext 
+{
+    // Work out the size in pixels
+    let width           = ((logical_width as f32) * scale).ceil() as usize;
+    let height          = ((logical_height as f32) * scale).ceil() as usize;
+
+    // Map the logical coordinates onto the pixels before the drawing starts (the size is rounded to whole pixels, so use the rounded size here too)
+    let logical_width   = (width as f32) / scale;
+    let logical_height  = (height as f32) / scale;
+    let setup           = vec![
+        Draw::CanvasHeight(logical_height),
+        Draw::CenterRegion((0.0, 0.0), (logical_width, logical_height)),
+    ];
+
+    // Clearing the canvas resets the transform, so the logical coordinates need to be set up again afterwards
+    let actions         = stream::iter(setup.clone()).chain(actions.flat_map(move |draw| {
+        let resets_transform    = matches!(draw, Draw::ClearCanvas(_));
+        let mut drawing         = vec![draw];
+
+        if resets_transform { drawing.extend(setup.iter().cloned()); }
+
+        stream::iter(drawing)
+    }));
+
+    render_canvas_offscreen(context, width, height, scale, actions)
+}
+
+///
+/// Renders canvas drawings to bitmaps, keeping the same offscreen rendering context for every drawing
+///
+/// Initialising the graphics device is the slowest part of rendering offscreen, so this is more efficient than setting up a new
+/// context for each drawing when rendering a batch of images (thumbnails, for example). Each drawing is rendered on its own, so
+/// nothing drawn for one image will appear in the next.
+///
+/// Only the context is reused: every drawing gets a new render target and canvas renderer, as reading the pixels back from
+/// an offscreen render target consumes it along with its GPU buffers.
+///
+pub struct OffscreenRenderer<RenderContext: OffscreenRenderContext> {
+    /// The context that is used for every render
+    context: RenderContext,
+}
+
+impl<RenderContext: OffscreenRenderContext> OffscreenRenderer<RenderContext> {
+    ///
+    /// Creates an offscreen renderer that will render using the specified context (eg, the result of `initialize_offscreen_rendering()`)
+    ///
+    pub fn new(context: RenderContext) -> OffscreenRenderer<RenderContext> {
+        OffscreenRenderer {
+            context: context
+        }
+    }
+
+    ///
+    /// Renders a drawing to a bitmap of the specified size
+    ///
+    pub fn render<'a, DrawIter>(&'a mut self, drawing: DrawIter, (width, height): (usize, usize)) -> impl 'a+Future<Output=Vec<u8>>
+    where
+        DrawIter:           IntoIterator<Item=Draw>,
+        DrawIter::IntoIter: 'a,
+    {
+        render_canvas_offscreen(&mut self.context, width, height, 1.0, stream::iter(drawing))
+    }
+
+    ///
+    /// Returns the context used by this renderer
+    ///
+    pub fn context(&mut self) -> &mut RenderContext {
+        &mut self.context
     }
-}
\ No newline at end of file
+}
@@ -32,6 +32,9 @@ pub enum RenderEntity {
     /// Render a vertex buffer
     DrawIndexed(render::VertexBufferId, render::IndexBufferId, usize),
 
+    /// The vertices for this operation were combined into the vertex buffer for an earlier `DrawIndexed` entity, so there is nothing to render here
+    Batched,
+
     /// Render the sprite layer with the specified ID
     RenderSprite(usize, canvas::SpriteId, canvas::Transform2D),
 
@@ -7,5 +7,22 @@ use flo_canvas as canvas;
 #[derive(Clone)]
 pub enum RenderGradient {
     Defined(Vec<canvas::GradientOp>),
-    Ready(render::TextureId, Vec<canvas::GradientOp>)
+    Ready(render::TextureId, Vec<canvas::GradientOp>),
+
+    /// A gradient that was loaded into a texture and has since been redefined (texture, previous definition, new definition)
+    ///
+    /// If the texture is no longer in use when the gradient is next rendered, only the texels that differ from the previous
+    /// definition are written to it, which makes animating a gradient's stops cheap
+    Redefined(render::TextureId, Vec<canvas::GradientOp>, Vec<canvas::GradientOp>),
+}
+
+///
+/// Given the texels for an old and a new gradient, returns the range of texels (start, end) that differ, or None if they're the same
+///
+pub fn changed_gradient_texels(old_texels: &[[u8; 4]], new_texels: &[[u8; 4]]) -> Option<(usize, usize)> {
+    let len     = usize::min(old_texels.len(), new_texels.len());
+    let start   = (0..len).find(|idx| old_texels[*idx] != new_texels[*idx])?;
+    let end     = (0..len).rev().find(|idx| old_texels[*idx] != new_texels[*idx])?;
+
+    Some((start, end+1))
 }
@@ -15,12 +15,16 @@ use super::texture_render_request::*;
 use flo_canvas as canvas;
 use flo_render as render;
 
-use lyon::tessellation::{FillRule};
+use lyon::tessellation::{FillRule, VertexBuffers};
+use futures::channel::mpsc;
 
 use std::mem;
 use std::sync::*;
 use std::collections::{HashMap, HashSet};
 
+/// The maximum number of vertices that can be combined into a single batch (indexes are 16-bit)
+const MAX_BATCH_VERTICES: usize = u16::MAX as usize;
+
 ///
 /// Parts of the renderer that are shared with the workers
 ///
@@ -28,6 +32,12 @@ pub struct RenderCore {
     /// Number of times that StartFrame has been specified without a corresponding ShowFrame
     pub frame_starts: usize,
 
+    /// Number of frames that have been shown by a ShowFrame instruction but have not finished rendering yet
+    pub frames_shown: usize,
+
+    /// Where to send a notification when a frame that was shown has finished rendering
+    pub frame_complete: Option<mpsc::UnboundedSender<()>>,
+
     /// One-time setup actions that are waiting to be rendered
     pub setup_actions: Vec<render::RenderAction>,
 
@@ -90,6 +100,9 @@ pub struct RenderCore {
 
     /// Render targets that were previously used by are now free
     pub free_render_targets: Vec<render::RenderTargetId>,
+
+    /// True if the bounds of each layer should be drawn on top of the rendering (for debugging)
+    pub debug_layer_bounds: bool,
 }
 
 impl RenderCore {
@@ -110,6 +123,7 @@ impl RenderCore {
 
         match render_entity {
             Missing                                 => { }
+            Batched                                 => { }
             Tessellating(_entity_id)                => { }
             VertexBuffer(_buffers, _)               => { }
             SetTransform(_)                         => { }
@@ -180,6 +194,22 @@ impl RenderCore {
             }
         }
 
+        // Gradients using a freed texture will need to be loaded again if they're re-used
+        if !unused_textures.is_empty() {
+            for gradient in self.canvas_gradients.values_mut() {
+                match gradient {
+                    RenderGradient::Ready(texture_id, definition)           |
+                    RenderGradient::Redefined(texture_id, _, definition)    => {
+                        if unused_textures.contains(texture_id) {
+                            *gradient = RenderGradient::Defined(definition.clone());
+                        }
+                    }
+
+                    RenderGradient::Defined(_) => { }
+                }
+            }
+        }
+
         // Free them
         let mut render_actions = vec![];
 
@@ -337,7 +367,12 @@ impl RenderCore {
 
         // The action we just removed should be a vertex buffer action
         match vertex_action {
-            RenderEntity::VertexBuffer(vertices, intent) => {
+            RenderEntity::VertexBuffer(mut vertices, intent) => {
+                // Vertex buffers that are drawn one after the other with no state change in between can be drawn with a single call
+                if let VertexBufferIntent::Draw = intent {
+                    self.batch_following_vertex_buffers(layer_idx, render_index, &mut vertices);
+                }
+
                 // Allocate a buffer
                 let buffer_id = self.allocate_vertex_buffer();
 
@@ -363,6 +398,49 @@ impl RenderCore {
         }
     }
 
+    ///
+    /// Appends the vertex buffers that immediately follow the specified entity in a layer to a set of vertices, replacing them
+    /// with `Batched` entities
+    ///
+    /// Only buffers that are drawn using the same state can be combined, so this stops at the first entity that isn't a vertex
+    /// buffer to draw (such as a transform or a shader change), at the layer's restore point, or if the combined buffer would
+    /// have too many vertices.
+    ///
+    fn batch_following_vertex_buffers(&mut self, layer_idx: usize, render_index: usize, vertices: &mut VertexBuffers<render::Vertex2D, u16>) {
+        let layer           = &mut self.layer_definitions[layer_idx];
+        let restore_point   = layer.state.restore_point;
+        let render_order    = &mut layer.render_order;
+
+        for next_index in (render_index+1)..render_order.len() {
+            // Entities after the restore point can be removed by a 'Restore' operation, so they can't be combined with the entities before it
+            if Some(next_index) == restore_point {
+                break;
+            }
+
+            // Stop at the first entity that can't be added to the batch
+            match &render_order[next_index] {
+                RenderEntity::VertexBuffer(next_vertices, VertexBufferIntent::Draw) => {
+                    if vertices.vertices.len() + next_vertices.vertices.len() > MAX_BATCH_VERTICES {
+                        break;
+                    }
+                }
+
+                _ => { break; }
+            }
+
+            // Append the vertices, offsetting the indices to point at them
+            let mut next_action = RenderEntity::Batched;
+            mem::swap(&mut render_order[next_index], &mut next_action);
+
+            if let RenderEntity::VertexBuffer(next_vertices, _) = next_action {
+                let index_offset = vertices.vertices.len() as u16;
+
+                vertices.vertices.extend(next_vertices.vertices);
+                vertices.indices.extend(next_vertices.indices.into_iter().map(|index| index + index_offset));
+            }
+        }
+    }
+
     ///
     /// Returns the render actions needed to prepare the render buffers for the specified layer (and updates the layer
     /// so that the buffers are not sent again)
@@ -460,7 +538,31 @@ impl RenderCore {
     pub fn gradient_for_rendering(&mut self, namespace_id: usize, gradient_id: canvas::GradientId) -> Option<render::TextureId> {
         match self.canvas_gradients.get(&(namespace_id, gradient_id))? {
             RenderGradient::Ready(gradient_texture, _)  => Some(*gradient_texture),
-            RenderGradient::Defined(definition)         => {
+
+            RenderGradient::Redefined(gradient_texture, old_definition, definition) if self.used_textures.get(gradient_texture) == Some(&0) => {
+                // The texture is not being used for any rendering, so we can update it in place
+                let texture_id      = *gradient_texture;
+                let definition      = definition.clone();
+
+                // Only the texels that have changed since the last definition need to be uploaded
+                let old_texels      = canvas::gradient_scale_premultiplied::<_, 256>(old_definition.clone());
+                let new_texels      = canvas::gradient_scale_premultiplied::<_, 256>(definition.clone());
+
+                if let Some((start, end)) = changed_gradient_texels(&old_texels, &new_texels) {
+                    let bytes = new_texels[start..end].iter().flatten().cloned().collect::<Vec<_>>();
+
+                    self.setup_actions.extend(vec![
+                        render::RenderAction::WriteTexture1D(texture_id, render::Position1D(start), render::Position1D(end), Arc::new(bytes)),
+                        render::RenderAction::CreateMipMaps(texture_id)
+                    ]);
+                }
+
+                self.canvas_gradients.insert((namespace_id, gradient_id), RenderGradient::Ready(texture_id, definition));
+
+                Some(texture_id)
+            }
+
+            RenderGradient::Defined(definition) | RenderGradient::Redefined(_, _, definition) => {
                 // Define a new texture
                 let definition  = definition.clone();
                 let texture_id  = self.allocate_texture();
@@ -469,12 +571,13 @@ impl RenderCore {
                 self.used_textures.insert(texture_id, 0);
 
                 // Get the bytes for this gradient
-                let bytes       = canvas::gradient_scale::<_, 256>(definition.clone());
+                let bytes       = canvas::gradient_scale_premultiplied::<_, 256>(definition.clone());
                 let bytes       = bytes.iter().flatten().cloned().collect::<Vec<_>>();
 
-                // Define as a 1D texture
+                // Define as a 1D texture (the gradient texels have premultiplied alpha)
                 self.setup_actions.extend(vec![
                     render::RenderAction::Create1DTextureBgra(texture_id, render::Size1D(256)),
+                    render::RenderAction::SetTexturePremultiplied(texture_id, true),
                     render::RenderAction::WriteTexture1D(texture_id, render::Position1D(0), render::Position1D(256), Arc::new(bytes)),
                     render::RenderAction::CreateMipMaps(texture_id)
                 ]);
@@ -488,6 +591,42 @@ impl RenderCore {
         }
     }
 
+    ///
+    /// Releases all of the textures, gradients and sprites that belong to a particular namespace
+    ///
+    pub fn free_namespace(&mut self, namespace_id: usize) {
+        // Release the textures
+        let namespace_textures = self.canvas_textures.keys()
+            .filter(|(texture_namespace, _)| *texture_namespace == namespace_id)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for canvas_texture_id in namespace_textures {
+            if let Some(render_texture) = self.canvas_textures.remove(&canvas_texture_id) {
+                let render_texture = (&render_texture).into();
+                self.used_textures.get_mut(&render_texture).map(|usage_count| *usage_count -= 1);
+            }
+        }
+
+        self.texture_alpha.retain(|(texture_namespace, _), _| *texture_namespace != namespace_id);
+
+        // Release the gradients (the textures for these are freed once they're no longer in use)
+        self.canvas_gradients.retain(|(gradient_namespace, _), _| *gradient_namespace != namespace_id);
+
+        // Release the sprites
+        let namespace_sprites = self.sprites.keys()
+            .filter(|(sprite_namespace, _)| *sprite_namespace == namespace_id)
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for sprite_id in namespace_sprites {
+            if let Some(layer_id) = self.sprites.remove(&sprite_id) {
+                let layer = self.release_layer_handle(layer_id);
+                self.free_layer_entities(layer);
+            }
+        }
+    }
+
     ///
     /// Allocates a new layer handle to a blank layer
     ///
@@ -53,12 +53,15 @@ impl Layer {
     /// Updates the scale factor for this layer from the currently set transform
     ///
     pub fn update_scale_factor(&mut self) {
-        // Work out the scale factor from the matrix (skewed matrices won't produce accurate values here)
-        let canvas::Transform2D([[_a, _b, _], [d, e, _], [_, _, _]]) = self.state.current_matrix;
-        // let scale_x              = a*a + b*b;
-        let scale_y                 = d*d + e*e;
-
-        self.state.scale_factor     = scale_y.sqrt() * self.state.base_scale_factor;
+        // Work out the scale factor from the matrix: this is the largest amount the matrix stretches any direction by (its largest singular value), so
+        // anisotropic or skewed transforms are tessellated finely enough for the axis that is magnified the most
+        let canvas::Transform2D([[a, b, _], [d, e, _], [_, _, _]]) = self.state.current_matrix;
+        let sum_squares             = a*a + b*b + d*d + e*e;
+        let determinant             = a*e - b*d;
+        let discriminant            = f32::max(0.0, sum_squares*sum_squares - 4.0*determinant*determinant);
+        let max_scale               = ((sum_squares + discriminant.sqrt()) / 2.0).sqrt();
+
+        self.state.scale_factor     = max_scale * self.state.base_scale_factor;
     }
 
     ///
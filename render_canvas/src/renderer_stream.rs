@@ -380,6 +380,70 @@ impl RenderCore {
         render
     }
 
+    ///
+    /// Draws an outline around the bounds of every layer (for debugging)
+    ///
+    fn render_debug_layer_bounds(&mut self, viewport_transform: canvas::Transform2D, viewport_size: render::Size2D, invalid_bounds: &mut LayerBounds) -> Vec<render::RenderAction> {
+        use render::RenderAction::*;
+        use render::{VertexBufferId, Vertex2D};
+
+        // Outlines are drawn in viewport coordinates, centered on the edges of the bounds
+        let render::Size2D(w, h)    = viewport_size;
+        let half_pixel_x            = 1.0 / (w as f32);
+        let half_pixel_y            = 1.0 / (h as f32);
+
+        let mut vertices            = vec![];
+
+        for layer_handle in self.layers.clone() {
+            let bounds = self.layer_readonly(layer_handle).bounds.transform(&viewport_transform);
+            if bounds.is_undefined() { continue; }
+
+            // One rectangle per edge
+            let edges = [
+                (bounds.min_x - half_pixel_x, bounds.min_y - half_pixel_y, bounds.max_x + half_pixel_x, bounds.min_y + half_pixel_y),
+                (bounds.min_x - half_pixel_x, bounds.max_y - half_pixel_y, bounds.max_x + half_pixel_x, bounds.max_y + half_pixel_y),
+                (bounds.min_x - half_pixel_x, bounds.min_y - half_pixel_y, bounds.min_x + half_pixel_x, bounds.max_y + half_pixel_y),
+                (bounds.max_x - half_pixel_x, bounds.min_y - half_pixel_y, bounds.max_x + half_pixel_x, bounds.max_y + half_pixel_y),
+            ];
+
+            for (min_x, min_y, max_x, max_y) in edges.iter().cloned() {
+                vertices.extend(vec![
+                    Vertex2D::with_pos(min_x, min_y).with_color(1.0, 0.0, 1.0, 1.0),
+                    Vertex2D::with_pos(min_x, max_y).with_color(1.0, 0.0, 1.0, 1.0),
+                    Vertex2D::with_pos(max_x, min_y).with_color(1.0, 0.0, 1.0, 1.0),
+
+                    Vertex2D::with_pos(max_x, max_y).with_color(1.0, 0.0, 1.0, 1.0),
+                    Vertex2D::with_pos(max_x, min_y).with_color(1.0, 0.0, 1.0, 1.0),
+                    Vertex2D::with_pos(min_x, max_y).with_color(1.0, 0.0, 1.0, 1.0),
+                ]);
+            }
+
+            // The outline needs to be included in the region that's drawn to the frame buffer
+            invalid_bounds.combine(&bounds.inflate(f32::max(half_pixel_x, half_pixel_y)));
+        }
+
+        if vertices.len() == 0 {
+            return vec![];
+        }
+
+        // Draw to a temporary vertex buffer on top of the main render target
+        let num_vertices        = vertices.len();
+        let debug_vertex_buffer = self.allocate_vertex_buffer();
+        let render              = vec![
+            SelectRenderTarget(MAIN_RENDER_TARGET),
+            BlendMode(render::BlendMode::SourceOver),
+            UseShader(render::ShaderType::Simple { clip_texture: None }),
+            SetTransform(render::Matrix::identity()),
+            CreateVertex2DBuffer(VertexBufferId(debug_vertex_buffer), vertices),
+            DrawTriangles(VertexBufferId(debug_vertex_buffer), 0..num_vertices),
+        ];
+
+        // Add back to the free list after rendering
+        self.free_vertex_buffer(debug_vertex_buffer);
+
+        render
+    }
+
     ///
     /// Generates the rendering actions for the layer with the specified handle
     ///
@@ -448,6 +512,10 @@ impl RenderCore {
                     render_order.push(render::RenderAction::DrawIndexedTriangles(*vertex_buffer, *index_buffer, *num_items));
                 },
 
+                Batched => {
+                    // Drawn along with an earlier DrawIndexed entity
+                },
+
                 RenderSprite(namespace_id, sprite_id, sprite_transform) => { 
                     let sprite_id           = *sprite_id;
                     let sprite_transform    = *sprite_transform;
@@ -1304,11 +1372,35 @@ impl<'a> Stream for RenderStream<'a> {
             self.pending = result.into();
             return Poll::Ready(self.pending.pop_front());
         } else if let Some(final_actions) = self.final_actions.take() {
+            // Draw the layer bounds over the top of everything else if debugging them
+            let viewport_size       = self.viewport_size;
+            let mut invalid_bounds  = self.invalid_bounds;
+            let debug_bounds        = self.core.sync(|core| {
+                if core.debug_layer_bounds {
+                    core.render_debug_layer_bounds(viewport_transform, viewport_size, &mut invalid_bounds)
+                } else {
+                    vec![]
+                }
+            });
+            self.invalid_bounds     = invalid_bounds;
+            self.pending.extend(debug_bounds);
+
             // There are no more drawing actions, but we have a set of final post-render instructions to execute
             let final_actions = self.clip_draw_framebuffer(final_actions);
             self.pending.extend(final_actions);
             return Poll::Ready(self.pending.pop_front());
         } else {
+            // Acknowledge any frames that were shown now that all of their actions have been generated
+            self.core.sync(|core| {
+                let frames_shown = mem::take(&mut core.frames_shown);
+
+                if let Some(frame_complete) = &core.frame_complete {
+                    for _ in 0..frames_shown {
+                        frame_complete.unbounded_send(()).ok();
+                    }
+                }
+            });
+
             // No further actions if the result was empty
             return Poll::Ready(None);
         }
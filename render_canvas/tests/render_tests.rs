@@ -4,6 +4,7 @@ use flo_render_canvas::*;
 use flo_canvas::*;
 
 use futures::prelude::*;
+use futures::stream;
 use futures::executor;
 
 ///
@@ -93,7 +94,7 @@ fn fill_two_circles() {
         // Get the upates for a drawing operation
         let mut draw_stream = renderer.draw(draw_circle.into_iter());
 
-        // Should be a 'clear', an 'upload vertex buffer', an 'upload index buffer' and two 'draw indexed' instructions
+        // Should be a 'clear', an 'upload vertex buffer', an 'upload index buffer' and a 'draw indexed' instruction (the two fills use the same state, so they're batched together)
         loop {
             let next = draw_stream.next().await;
             assert!(next.is_some());
@@ -116,14 +117,6 @@ fn fill_two_circles() {
         assert!(upload_indices.is_some());
         assert!(match upload_indices { Some(RenderAction::CreateIndexBuffer(_, _)) => true, _ => false });
 
-        let upload_vertices_2 = draw_stream.next().await;
-        assert!(upload_vertices_2.is_some());
-        assert!(match upload_vertices_2 { Some(RenderAction::CreateVertex2DBuffer(_, _)) => true, _ => false });
-
-        let upload_indices_2 = draw_stream.next().await;
-        assert!(upload_indices_2.is_some());
-        assert!(match upload_indices_2 { Some(RenderAction::CreateIndexBuffer(_, _)) => true, _ => false });
-
         // Layer preamble occurs after uploading the buffers
         check_layer_preamble(&mut draw_stream).await;
 
@@ -133,8 +126,7 @@ fn fill_two_circles() {
         assert!(match draw_vertices { Some(RenderAction::DrawIndexedTriangles(_, _, _)) => true, _ => false });
 
         let draw_vertices_2  = draw_stream.next().await;
-        assert!(draw_vertices_2.is_some());
-        assert!(match draw_vertices_2 { Some(RenderAction::DrawIndexedTriangles(_, _, _)) => true, _ => false });
+        assert!(!match draw_vertices_2 { Some(RenderAction::DrawIndexedTriangles(_, _, _)) => true, _ => false });
     })
 }
 
@@ -194,6 +186,182 @@ fn draw_twice() {
     })
 }
 
+#[test]
+fn draw_quad_mesh() {
+    // Draw a quad made up of two triangles
+    let mut draw_mesh = vec![];
+    draw_mesh.draw_mesh(vec![
+        MeshVertex::new(0.0, 0.0, Color::Rgba(1.0, 0.0, 0.0, 1.0)),
+        MeshVertex::new(100.0, 0.0, Color::Rgba(0.0, 1.0, 0.0, 1.0)),
+        MeshVertex::new(100.0, 100.0, Color::Rgba(0.0, 0.0, 1.0, 1.0)),
+        MeshVertex::new(0.0, 100.0, Color::Rgba(1.0, 1.0, 1.0, 1.0)),
+    ], vec![0, 1, 2, 0, 2, 3]);
+
+    executor::block_on(async {
+        // Create the renderer
+        let mut renderer    = CanvasRenderer::new();
+
+        // Get the upates for a drawing operation
+        let mut draw_stream = renderer.draw(draw_mesh.into_iter());
+
+        // The mesh is sent straight to the renderer as a vertex and index buffer
+        loop {
+            let next = draw_stream.next().await;
+            assert!(next.is_some());
+
+            if let Some(RenderAction::Clear(_)) = &next {
+                break;
+            }
+        }
+
+        let set_transform   = draw_stream.next().await;
+        assert!(match set_transform { Some(RenderAction::SetTransform(_)) => true, _ => false });
+
+        let upload_vertices = draw_stream.next().await;
+        println!("{:?}", upload_vertices);
+        assert!(match &upload_vertices { 
+            Some(RenderAction::CreateVertex2DBuffer(_, vertices)) => {
+                vertices.len() == 4 
+                    && vertices[0].color == [255, 0, 0, 255]
+                    && vertices[2].pos == [100.0, 100.0]
+            },
+            _ => false
+        });
+
+        let upload_indices  = draw_stream.next().await;
+        println!("{:?}", upload_indices);
+        assert!(match &upload_indices { Some(RenderAction::CreateIndexBuffer(_, indices)) => indices == &vec![0, 1, 2, 0, 2, 3], _ => false });
+
+        check_layer_preamble(&mut draw_stream).await;
+
+        let draw_vertices   = draw_stream.next().await;
+        println!("{:?}", draw_vertices);
+        assert!(match draw_vertices { Some(RenderAction::DrawIndexedTriangles(_, _, 6)) => true, _ => false });
+    })
+}
+
+#[test]
+fn skip_mesh_without_triangles() {
+    // Neither of these meshes has a whole triangle that's inside the vertex list
+    let mut draw_mesh = vec![];
+    draw_mesh.draw_mesh(vec![], vec![]);
+    draw_mesh.draw_mesh(vec![
+        MeshVertex::new(0.0, 0.0, Color::Rgba(1.0, 0.0, 0.0, 1.0)),
+        MeshVertex::new(100.0, 0.0, Color::Rgba(0.0, 1.0, 0.0, 1.0)),
+        MeshVertex::new(100.0, 100.0, Color::Rgba(0.0, 0.0, 1.0, 1.0)),
+    ], vec![0, 1, 3, 0, 1]);
+
+    // Nothing should be uploaded or drawn for them
+    let mut renderer    = CanvasRenderer::new();
+    let actions         = renderer.render_actions_for(&draw_mesh);
+
+    assert!(!actions.iter().any(|action| match action {
+        RenderAction::CreateVertex2DBuffer(_, _)        |
+        RenderAction::CreateIndexBuffer(_, _)           |
+        RenderAction::DrawIndexedTriangles(_, _, _)     => true,
+        _                                               => false
+    }), "{:?}", actions);
+}
+
+///
+/// Renders a drawing and returns the number of vertices in the first vertex buffer that it generates
+///
+fn first_vertex_count(renderer: &mut CanvasRenderer, drawing: Vec<Draw>) -> usize {
+    executor::block_on(async {
+        let mut draw_stream = renderer.draw(drawing.into_iter());
+
+        // Skip ahead to the first vertex buffer that's uploaded
+        loop {
+            let next = draw_stream.next().await;
+
+            match next {
+                Some(RenderAction::CreateVertex2DBuffer(_, vertices))   => { return vertices.len(); }
+                Some(_)                                                 => { }
+                None                                                    => panic!("Drawing did not generate a vertex buffer")
+            }
+        }
+    })
+}
+
+///
+/// Fills a circle using a particular tessellation tolerance and returns the number of vertices that were generated for it
+///
+fn circle_vertex_count(tolerance: f32) -> usize {
+    let mut draw_circle = vec![];
+    draw_circle.circle(0.0,0.0, 100.0);
+    draw_circle.fill();
+
+    let mut renderer = CanvasRenderer::new();
+    renderer.set_tessellation_tolerance(tolerance);
+
+    first_vertex_count(&mut renderer, draw_circle)
+}
+
+///
+/// Fills a circle after applying a scale transform and returns the number of vertices that were generated for it
+///
+fn scaled_circle_vertex_count(scale_x: f32, scale_y: f32) -> usize {
+    let mut draw_circle = vec![];
+    draw_circle.canvas_height(1000.0);
+    draw_circle.transform(Transform2D::scale(scale_x, scale_y));
+    draw_circle.circle(0.0,0.0, 100.0);
+    draw_circle.fill();
+
+    first_vertex_count(&mut CanvasRenderer::new(), draw_circle)
+}
+
+#[test]
+fn tighter_tolerance_generates_more_triangles() {
+    let coarse_vertices = circle_vertex_count(4.0);
+    let fine_vertices   = circle_vertex_count(0.05);
+
+    println!("{} {}", coarse_vertices, fine_vertices);
+    assert!(fine_vertices > coarse_vertices);
+}
+
+#[test]
+fn invalid_tolerances_are_clamped() {
+    let mut renderer = CanvasRenderer::new();
+    let default      = renderer.tessellation_tolerance();
+
+    renderer.set_tessellation_tolerance(0.0);
+    assert!(renderer.tessellation_tolerance() > 0.0);
+
+    renderer.set_tessellation_tolerance(-1.0);
+    assert!(renderer.tessellation_tolerance() > 0.0);
+
+    renderer.set_tessellation_tolerance(f32::NAN);
+    assert!(renderer.tessellation_tolerance() == default);
+
+    renderer.set_tessellation_tolerance(f32::INFINITY);
+    assert!(renderer.tessellation_tolerance() == default);
+
+    // A tolerance of 0 should still tessellate a circle into a finite number of triangles
+    let zero_vertices = circle_vertex_count(0.0);
+    assert!(zero_vertices > 0);
+    assert!(zero_vertices >= circle_vertex_count(0.05));
+}
+
+#[test]
+fn zooming_in_generates_more_triangles() {
+    let unscaled_vertices   = scaled_circle_vertex_count(1.0, 1.0);
+    let scaled_vertices     = scaled_circle_vertex_count(4.0, 4.0);
+
+    println!("{} {}", unscaled_vertices, scaled_vertices);
+    assert!(scaled_vertices > unscaled_vertices);
+}
+
+#[test]
+fn anisotropic_zoom_generates_more_triangles() {
+    let unscaled_vertices   = scaled_circle_vertex_count(1.0, 1.0);
+    let x_scaled_vertices   = scaled_circle_vertex_count(4.0, 1.0);
+    let y_scaled_vertices   = scaled_circle_vertex_count(1.0, 4.0);
+
+    println!("{} {} {}", unscaled_vertices, x_scaled_vertices, y_scaled_vertices);
+    assert!(x_scaled_vertices > unscaled_vertices);
+    assert!(y_scaled_vertices > unscaled_vertices);
+}
+
 #[test]
 fn clip_rect() {
     // Draw a simple rectabgle
@@ -258,3 +426,770 @@ fn clip_rect() {
         // Remaining instructions finish the render
     })
 }
+
+#[test]
+fn render_actions_for_simple_fill() {
+    // Draw a simple circle
+    let mut draw_circle = vec![];
+    draw_circle.circle(0.0,0.0, 100.0);
+    draw_circle.fill();
+
+    // Retrieve the render actions directly instead of via a stream
+    let mut renderer    = CanvasRenderer::new();
+    let actions         = renderer.render_actions_for(&draw_circle);
+
+    // Should upload a vertex buffer and then draw some triangles using it
+    let create_vertices = actions.iter().position(|action| match action { RenderAction::CreateVertex2DBuffer(_, _) => true, _ => false });
+    let draw_triangles  = actions.iter().position(|action| match action { RenderAction::DrawIndexedTriangles(_, _, _) => true, _ => false });
+
+    println!("{:?}", actions);
+    assert!(create_vertices.is_some());
+    assert!(draw_triangles.is_some());
+    assert!(create_vertices.unwrap() < draw_triangles.unwrap());
+}
+
+#[test]
+fn render_actions_for_async_matches_blocking() {
+    let mut draw_circle = vec![];
+    draw_circle.circle(0.0,0.0, 100.0);
+    draw_circle.fill();
+
+    // The async version can be awaited from inside another future, and should generate the same actions as the blocking one
+    let blocking        = CanvasRenderer::new().render_actions_for(&draw_circle);
+    let async_actions   = executor::block_on(async {
+        let mut renderer = CanvasRenderer::new();
+        renderer.render_actions_for_async(&draw_circle).await
+    });
+
+    assert!(blocking == async_actions, "{:?} != {:?}", blocking, async_actions);
+}
+
+#[test]
+fn free_namespace_leaves_other_namespaces() {
+    let namespace_a = NamespaceId::new();
+    let namespace_b = NamespaceId::new();
+
+    // Define the same sprite ID in two different namespaces
+    let mut define_sprites = vec![];
+    for namespace in [namespace_a, namespace_b].iter() {
+        define_sprites.draw(Draw::Namespace(*namespace));
+        define_sprites.sprite(SpriteId(0));
+        define_sprites.clear_sprite();
+        define_sprites.new_path();
+        define_sprites.rect(0.0, 0.0, 100.0, 100.0);
+        define_sprites.fill();
+    }
+    define_sprites.layer(LayerId(0));
+
+    let mut renderer = CanvasRenderer::new();
+    renderer.render_actions_for(&define_sprites);
+
+    // Free the resources in the first namespace
+    renderer.free_namespace(namespace_a);
+
+    // The sprite in the first namespace should no longer draw anything
+    let draw_sprite_a   = renderer.render_actions_for(&vec![Draw::Namespace(namespace_a), Draw::DrawSprite(SpriteId(0))]);
+    let num_draws_a     = draw_sprite_a.iter().filter(|action| match action { RenderAction::DrawIndexedTriangles(_, _, _) => true, _ => false }).count();
+    assert!(num_draws_a == 0, "{:?}", draw_sprite_a);
+
+    // The sprite in the second namespace should be unaffected
+    let draw_sprite_b   = renderer.render_actions_for(&vec![Draw::Namespace(namespace_b), Draw::DrawSprite(SpriteId(0))]);
+    let num_draws_b     = draw_sprite_b.iter().filter(|action| match action { RenderAction::DrawIndexedTriangles(_, _, _) => true, _ => false }).count();
+    assert!(num_draws_b > 0, "{:?}", draw_sprite_b);
+}
+
+#[test]
+fn sprite_bounds_for_rectangle() {
+    // Define a sprite containing a rectangle
+    let mut define_sprite = vec![];
+    define_sprite.sprite(SpriteId(0));
+    define_sprite.clear_sprite();
+    define_sprite.new_path();
+    define_sprite.rect(10.0, 20.0, 110.0, 70.0);
+    define_sprite.fill();
+    define_sprite.layer(LayerId(0));
+
+    let mut renderer = CanvasRenderer::new();
+    renderer.render_actions_for(&define_sprite);
+
+    // Bounds should match the rectangle
+    let bounds = renderer.sprite_bounds(NamespaceId::default(), SpriteId(0));
+    println!("{:?}", bounds);
+
+    let SpriteBounds(SpritePosition(x, y), SpriteSize(w, h)) = bounds.unwrap();
+    assert!((x-10.0).abs() < 0.01);
+    assert!((y-20.0).abs() < 0.01);
+    assert!((w-100.0).abs() < 0.01);
+    assert!((h-50.0).abs() < 0.01);
+
+    // Undefined sprites have no bounds
+    assert!(renderer.sprite_bounds(NamespaceId::default(), SpriteId(1)).is_none());
+}
+
+///
+/// Counts the number of 'draw indexed triangles' instructions in a set of render actions
+///
+fn count_draw_indexed(actions: &Vec<RenderAction>) -> usize {
+    actions.iter().filter(|action| match action { RenderAction::DrawIndexedTriangles(_, _, _) => true, _ => false }).count()
+}
+
+///
+/// Creates a drawing with a sprite and two layers
+///
+fn multi_layer_drawing() -> Vec<Draw> {
+    let mut drawing = vec![];
+
+    drawing.sprite(SpriteId(0));
+    drawing.clear_sprite();
+    drawing.new_path();
+    drawing.rect(0.0, 0.0, 10.0, 10.0);
+    drawing.fill();
+
+    drawing.layer(LayerId(0));
+    drawing.new_path();
+    drawing.rect(0.0, 0.0, 100.0, 100.0);
+    drawing.fill();
+
+    drawing.layer(LayerId(1));
+    drawing.new_path();
+    drawing.rect(50.0, 50.0, 150.0, 150.0);
+    drawing.fill();
+
+    drawing
+}
+
+#[test]
+fn clear_all_layers_keeps_sprites() {
+    let mut renderer    = CanvasRenderer::new();
+    let initial         = renderer.render_actions_for(&multi_layer_drawing());
+    assert!(count_draw_indexed(&initial) == 2, "{:?}", initial);
+
+    // Clearing all the layers should remove the contents of both layers
+    let cleared         = renderer.render_actions_for(&vec![Draw::ClearAllLayers]);
+    assert!(count_draw_indexed(&cleared) == 0, "{:?}", cleared);
+
+    // The sprite should still be defined
+    let draw_sprite     = renderer.render_actions_for(&vec![Draw::DrawSprite(SpriteId(0))]);
+    assert!(count_draw_indexed(&draw_sprite) == 1, "{:?}", draw_sprite);
+}
+
+#[test]
+fn clear_canvas_removes_sprites() {
+    let mut renderer    = CanvasRenderer::new();
+    let initial         = renderer.render_actions_for(&multi_layer_drawing());
+    assert!(count_draw_indexed(&initial) == 2, "{:?}", initial);
+
+    // Clearing the canvas should remove the contents of both layers
+    let cleared         = renderer.render_actions_for(&vec![Draw::ClearCanvas(Color::Rgba(0.0, 0.0, 0.0, 1.0))]);
+    assert!(count_draw_indexed(&cleared) == 0, "{:?}", cleared);
+
+    // The sprite is also removed by clearing the canvas
+    let draw_sprite     = renderer.render_actions_for(&vec![Draw::DrawSprite(SpriteId(0))]);
+    assert!(count_draw_indexed(&draw_sprite) == 0, "{:?}", draw_sprite);
+}
+
+#[test]
+fn debug_layer_bounds_overlay() {
+    // Draw a rectangle on a 1000x1000 canvas
+    let mut draw_rect = vec![];
+    draw_rect.canvas_height(1000.0);
+    draw_rect.new_path();
+    draw_rect.rect(-100.0, -100.0, 100.0, 100.0);
+    draw_rect.fill();
+
+    let mut renderer = CanvasRenderer::new();
+    renderer.set_viewport(0.0..1000.0, 0.0..1000.0, 1000.0, 1000.0, 1.0);
+    renderer.set_debug_layer_bounds(true);
+
+    let actions = renderer.render_actions_for(&draw_rect);
+
+    // Find the vertex buffer containing the overlay (the only one drawn in magenta)
+    let overlay = actions.iter()
+        .filter_map(|action| match action {
+            RenderAction::CreateVertex2DBuffer(_, vertices) => Some(vertices),
+            _                                               => None
+        })
+        .filter(|vertices| vertices.len() > 0 && vertices.iter().all(|vertex| vertex.color == [255, 0, 255, 255]))
+        .next();
+    assert!(overlay.is_some(), "{:?}", actions);
+
+    // Work out the bounds of the overlay in pixels
+    let overlay         = overlay.unwrap();
+    let to_pixels       = |x: f32, y: f32| ((x+1.0)/2.0 * 1000.0, (y+1.0)/2.0 * 1000.0);
+    let (min_x, min_y)  = overlay.iter().fold((f32::MAX, f32::MAX), |(x, y), vertex| (f32::min(x, vertex.pos[0]), f32::min(y, vertex.pos[1])));
+    let (max_x, max_y)  = overlay.iter().fold((f32::MIN, f32::MIN), |(x, y), vertex| (f32::max(x, vertex.pos[0]), f32::max(y, vertex.pos[1])));
+    let (min_x, min_y)  = to_pixels(min_x, min_y);
+    let (max_x, max_y)  = to_pixels(max_x, max_y);
+
+    // Should surround the rectangle
+    let transform               = renderer.get_viewport_transform();
+    let (rect_x1, rect_y1)      = transform.transform_point(-100.0, -100.0);
+    let (rect_x2, rect_y2)      = transform.transform_point(100.0, 100.0);
+
+    println!("{:?} {:?}", ((min_x, min_y), (max_x, max_y)), ((rect_x1, rect_y1), (rect_x2, rect_y2)));
+    assert!((min_x - rect_x1).abs() <= 1.0);
+    assert!((min_y - rect_y1).abs() <= 1.0);
+    assert!((max_x - rect_x2).abs() <= 1.0);
+    assert!((max_y - rect_y2).abs() <= 1.0);
+
+    // Overlay is drawn without a transform
+    assert!(actions.iter().any(|action| match action { RenderAction::DrawTriangles(_, range) => range.len() == overlay.len(), _ => false }));
+}
+
+fn gradient_rect(end_color: Color) -> Vec<Draw> {
+    let mut drawing = vec![];
+
+    drawing.layer(LayerId(0));
+    drawing.clear_layer();
+
+    drawing.create_gradient(GradientId(0), Color::Rgba(1.0, 0.0, 0.0, 1.0));
+    drawing.gradient_stop(GradientId(0), 0.5, Color::Rgba(0.0, 1.0, 0.0, 1.0));
+    drawing.gradient_stop(GradientId(0), 1.0, end_color);
+
+    drawing.new_path();
+    drawing.rect(-100.0, -100.0, 100.0, 100.0);
+    drawing.fill_gradient(GradientId(0), -100.0, 0.0, 100.0, 0.0);
+    drawing.fill();
+
+    drawing
+}
+
+#[test]
+fn changing_gradient_stop_uploads_affected_texels() {
+    let mut renderer    = CanvasRenderer::new();
+
+    // First render creates the whole gradient texture
+    let initial         = renderer.render_actions_for(&gradient_rect(Color::Rgba(0.0, 0.0, 1.0, 1.0)));
+    let texture_id      = initial.iter()
+        .filter_map(|action| match action { RenderAction::Create1DTextureBgra(texture_id, Size1D(256)) => Some(*texture_id), _ => None })
+        .next()
+        .expect("Gradient texture");
+
+    // Changing the final stop should only update the second half of the existing texture
+    let updated         = renderer.render_actions_for(&gradient_rect(Color::Rgba(1.0, 1.0, 1.0, 1.0)));
+    let writes          = updated.iter()
+        .filter_map(|action| match action { RenderAction::WriteTexture1D(texture_id, Position1D(start), Position1D(end), bytes) => Some((*texture_id, *start, *end, bytes.len())), _ => None })
+        .collect::<Vec<_>>();
+
+    assert!(!updated.iter().any(|action| match action { RenderAction::Create1DTextureBgra(_, _) => true, _ => false }), "{:?}", updated);
+    assert!(writes.len() == 1, "{:?}", writes);
+
+    let (write_texture, start, end, num_bytes) = writes[0];
+    assert!(write_texture == texture_id);
+    assert!(start >= 127 && end == 256, "{:?}", (start, end));
+    assert!(num_bytes == (end-start)*4);
+}
+
+#[test]
+#[cfg(any(feature="opengl", feature="osx-metal", feature="render-wgpu"))]
+fn rotate_gradient_with_fill_transform() {
+    let mut context = match initialize_offscreen_rendering() {
+        Ok(context)     => context,
+        Err(err)        => { println!("Test not run: {:?}", err); return; }
+    };
+
+    let mut drawing = vec![];
+    drawing.clear_canvas(Color::Rgba(0.0, 0.0, 0.0, 1.0));
+    drawing.canvas_height(100.0);
+    drawing.center_region(0.0, 0.0, 100.0, 100.0);
+
+    drawing.create_gradient(GradientId(0), Color::Rgba(1.0, 0.0, 0.0, 1.0));
+    drawing.gradient_stop(GradientId(0), 1.0, Color::Rgba(0.0, 0.0, 1.0, 1.0));
+
+    // Red-to-blue gradient running from x=-40 to x=40 around the origin, rotated by 180 degrees so that it runs the other way
+    // The drawing transform moves the origin to the middle of the image, which should move the gradient along with the shape
+    drawing.transform(Transform2D::translate(50.0, 50.0));
+    drawing.new_path();
+    drawing.rect(-50.0, -50.0, 50.0, 50.0);
+    drawing.fill_gradient(GradientId(0), -40.0, 0.0, 40.0, 0.0);
+    drawing.fill_transform(Transform2D::rotate_degrees(180.0));
+    drawing.fill();
+
+    let image       = executor::block_on(render_canvas_offscreen(&mut context, 100, 100, 1.0, stream::iter(drawing)));
+
+    // Sample along the middle row (so this doesn't depend on which way up the image is)
+    let pixel       = |x: usize| { let pos = (x + 50*100)*4; [image[pos], image[pos+1], image[pos+2], image[pos+3]] };
+    let is_near     = |pixel: [u8; 4], expected: [u8; 4]| pixel.iter().zip(expected.iter()).all(|(a, b)| (*a as i32 - *b as i32).abs() <= 4);
+
+    // The gradient starts at x=90 and ends at x=10 in canvas coordinates: pixel centres are half a unit in, so pixel 10 is
+    // 0.994 of the way along the gradient, pixel 50 is 0.494 of the way along and pixel 89 is 0.006 of the way along
+    assert!(is_near(pixel(10), [2, 0, 253, 255]), "{:?}", pixel(10));
+    assert!(is_near(pixel(50), [129, 0, 126, 255]), "{:?}", pixel(50));
+    assert!(is_near(pixel(89), [253, 0, 2, 255]), "{:?}", pixel(89));
+}
+
+#[test]
+fn triangle_count_for_filled_circle() {
+    let mut draw_circle = vec![];
+    draw_circle.canvas_height(1000.0);
+    draw_circle.new_path();
+    draw_circle.circle(0.0, 0.0, 100.0);
+    draw_circle.fill();
+
+    let mut renderer = CanvasRenderer::new();
+    assert!(renderer.triangle_count() == 0);
+
+    let actions     = renderer.render_actions_for(&draw_circle);
+    let triangles   = renderer.triangle_count();
+
+    // Every vertex in the circle should be used by at least one triangle, and there should be fewer triangles than indices
+    let num_vertices = actions.iter()
+        .map(|action| match action { RenderAction::CreateVertex2DBuffer(_, vertices) => vertices.len(), _ => 0 })
+        .sum::<usize>();
+    let num_indices = actions.iter()
+        .map(|action| match action { RenderAction::CreateIndexBuffer(_, indices) => indices.len(), _ => 0 })
+        .sum::<usize>();
+
+    assert!(triangles > 8, "{} triangles", triangles);
+    assert!(triangles*3 == num_indices, "{} triangles, {} indices", triangles, num_indices);
+    assert!(triangles >= num_vertices - 2, "{} triangles, {} vertices", triangles, num_vertices);
+}
+
+///
+/// True if the point (in canvas coordinates) is inside one of the triangles generated by a set of render actions
+///
+fn point_is_covered(actions: &Vec<RenderAction>, x: f32, y: f32) -> bool {
+    let vertex_buffers = actions.iter()
+        .filter_map(|action| match action { RenderAction::CreateVertex2DBuffer(VertexBufferId(id), vertices) => Some((*id, vertices)), _ => None })
+        .collect::<Vec<_>>();
+
+    actions.iter()
+        .filter_map(|action| match action { RenderAction::CreateIndexBuffer(IndexBufferId(id), indices) => Some((*id, indices)), _ => None })
+        .any(|(index_id, indices)| {
+            let vertices = vertex_buffers.iter().filter(|(vertex_id, _)| *vertex_id == index_id).map(|(_, vertices)| vertices).next().unwrap();
+
+            indices.chunks(3).any(|triangle| {
+                let [(x1, y1), (x2, y2), (x3, y3)] = [0, 1, 2].map(|idx| { let pos = vertices[triangle[idx] as usize].pos; (pos[0], pos[1]) });
+
+                let side1 = (x2-x1)*(y-y1) - (y2-y1)*(x-x1);
+                let side2 = (x3-x2)*(y-y2) - (y3-y2)*(x-x2);
+                let side3 = (x1-x3)*(y-y3) - (y1-y3)*(x-x3);
+
+                (side1 >= 0.0 && side2 >= 0.0 && side3 >= 0.0) || (side1 <= 0.0 && side2 <= 0.0 && side3 <= 0.0)
+            })
+        })
+}
+
+#[test]
+fn stroke_open_path_has_caps() {
+    // An 'L' shape that is not closed
+    let mut drawing = vec![];
+    drawing.new_path();
+    drawing.move_to(100.0, 100.0);
+    drawing.line_to(100.0, 300.0);
+    drawing.line_to(300.0, 300.0);
+    drawing.line_width(20.0);
+    drawing.line_cap(LineCap::Square);
+    drawing.stroke();
+
+    let mut renderer    = CanvasRenderer::new();
+    let actions         = renderer.render_actions_for(&drawing);
+
+    // The lines themselves are stroked
+    assert!(point_is_covered(&actions, 100.0, 200.0));
+    assert!(point_is_covered(&actions, 200.0, 300.0));
+
+    // Both free ends have square caps extending beyond the end points
+    assert!(point_is_covered(&actions, 100.0, 95.0));
+    assert!(point_is_covered(&actions, 305.0, 300.0));
+
+    // There's no edge joining the two ends
+    assert!(!point_is_covered(&actions, 200.0, 200.0));
+    assert!(!point_is_covered(&actions, 150.0, 150.0));
+    assert!(!point_is_covered(&actions, 250.0, 250.0));
+}
+
+///
+/// The colours of all of the vertices generated by a set of render actions
+///
+fn vertex_colors(actions: &Vec<RenderAction>) -> Vec<[u8; 4]> {
+    actions.iter()
+        .flat_map(|action| match action { RenderAction::CreateVertex2DBuffer(_, vertices) => vertices.iter().map(|vertex| vertex.color).collect(), _ => vec![] })
+        .collect()
+}
+
+#[test]
+fn global_alpha_dims_all_shapes() {
+    let mut shapes = vec![];
+    shapes.new_path();
+    shapes.rect(100.0, 100.0, 200.0, 200.0);
+    shapes.fill_color(Color::Rgba(1.0, 0.0, 0.0, 1.0));
+    shapes.fill();
+
+    shapes.new_path();
+    shapes.circle(500.0, 500.0, 100.0);
+    shapes.fill_color(Color::Rgba(0.0, 0.0, 1.0, 1.0));
+    shapes.fill();
+
+    shapes.new_path();
+    shapes.move_to(100.0, 600.0);
+    shapes.line_to(300.0, 700.0);
+    shapes.stroke_color(Color::Rgba(0.0, 1.0, 0.0, 1.0));
+    shapes.line_width(10.0);
+    shapes.stroke();
+
+    let mut faded = vec![];
+    faded.global_alpha(0.5);
+    faded.extend(shapes.iter().cloned());
+
+    let opaque_colors   = vertex_colors(&CanvasRenderer::new().render_actions_for(&shapes));
+    let faded_colors    = vertex_colors(&CanvasRenderer::new().render_actions_for(&faded));
+
+    // Every shape should be dimmed by the same amount without changing its colour
+    assert!(faded_colors.len() == opaque_colors.len() && !faded_colors.is_empty());
+    assert!(opaque_colors.iter().all(|color| color[3] == 255));
+    assert!(faded_colors.iter().all(|color| color[3] == 128), "{:?}", faded_colors);
+    assert!(faded_colors.iter().zip(opaque_colors.iter()).all(|(faded, opaque)| faded[0..3] == opaque[0..3]));
+}
+
+#[test]
+fn pop_state_restores_global_alpha() {
+    let mut drawing = vec![];
+    drawing.push_state();
+    drawing.global_alpha(0.5);
+    drawing.pop_state();
+    drawing.new_path();
+    drawing.rect(100.0, 100.0, 200.0, 200.0);
+    drawing.fill();
+
+    let colors = vertex_colors(&CanvasRenderer::new().render_actions_for(&drawing));
+
+    assert!(!colors.is_empty());
+    assert!(colors.iter().all(|color| color[3] == 255), "{:?}", colors);
+}
+
+#[test]
+fn global_alpha_dims_meshes() {
+    let mut drawing = vec![];
+    drawing.global_alpha(0.5);
+    drawing.draw_mesh(vec![
+        MeshVertex::new(100.0, 100.0, Color::Rgba(1.0, 0.0, 0.0, 1.0)),
+        MeshVertex::new(300.0, 100.0, Color::Rgba(0.0, 1.0, 0.0, 1.0)),
+        MeshVertex::new(100.0, 300.0, Color::Rgba(0.0, 0.0, 1.0, 1.0)),
+    ], vec![0, 1, 2]);
+
+    let colors = vertex_colors(&CanvasRenderer::new().render_actions_for(&drawing));
+
+    // The vertex colours keep their RGB values but have their alpha values reduced
+    assert!(colors.iter().all(|color| color[3] == 128), "{:?}", colors);
+    assert!(colors.contains(&[255, 0, 0, 128]) && colors.contains(&[0, 255, 0, 128]) && colors.contains(&[0, 0, 255, 128]), "{:?}", colors);
+}
+
+#[test]
+fn global_alpha_fades_sprites() {
+    let mut drawing = vec![];
+    drawing.sprite(SpriteId(0));
+    drawing.clear_sprite();
+    drawing.new_path();
+    drawing.rect(0.0, 0.0, 100.0, 100.0);
+    drawing.fill();
+
+    drawing.layer(LayerId(0));
+    drawing.global_alpha(0.5);
+    drawing.draw_sprite(SpriteId(0));
+
+    let actions = CanvasRenderer::new().render_actions_for(&drawing);
+
+    // The sprite is faded using the alpha blend filter
+    assert!(actions.iter().any(|action| match action {
+        RenderAction::FilterTexture(_, filters) => filters == &vec![render::TextureFilter::AlphaBlend(0.5)],
+        _                                       => false,
+    }), "{:?}", actions);
+}
+
+#[test]
+fn clear_canvas_resets_global_alpha_stack() {
+    let mut drawing = vec![];
+    drawing.global_alpha(0.5);
+    drawing.push_state();
+    drawing.clear_canvas(Color::Rgba(0.0, 0.0, 0.0, 0.0));
+    drawing.pop_state();
+    drawing.new_path();
+    drawing.rect(100.0, 100.0, 200.0, 200.0);
+    drawing.fill();
+
+    let colors = vertex_colors(&CanvasRenderer::new().render_actions_for(&drawing));
+
+    // The state pushed before the canvas was cleared should not be restored
+    assert!(!colors.is_empty());
+    assert!(colors.iter().all(|color| color[3] == 255), "{:?}", colors);
+}
+
+#[test]
+fn frame_complete_acknowledged_once_per_shown_frame() {
+    let (frame_complete, mut acknowledgements) = futures::channel::mpsc::unbounded();
+
+    let mut renderer = CanvasRenderer::new();
+    renderer.notify_frame_complete(frame_complete);
+
+    let count_acknowledgements = |acknowledgements: &mut futures::channel::mpsc::UnboundedReceiver<()>| {
+        let mut count = 0;
+        while let Ok(Some(())) = acknowledgements.try_next() { count += 1; }
+        count
+    };
+
+    // Drawing without showing a frame is not acknowledged
+    let mut drawing = vec![];
+    drawing.new_path();
+    drawing.rect(100.0, 100.0, 200.0, 200.0);
+    drawing.fill();
+
+    renderer.render_actions_for(&drawing);
+    assert!(count_acknowledgements(&mut acknowledgements) == 0);
+
+    // A complete frame is acknowledged once
+    let mut frame = vec![Draw::StartFrame];
+    frame.extend(drawing.iter().cloned());
+    frame.push(Draw::ShowFrame);
+
+    renderer.render_actions_for(&frame);
+    assert!(count_acknowledgements(&mut acknowledgements) == 1);
+
+    // A suspended frame is only acknowledged once it's been shown
+    renderer.render_actions_for(&vec![Draw::StartFrame, Draw::StartFrame, Draw::ShowFrame]);
+    assert!(count_acknowledgements(&mut acknowledgements) == 0);
+
+    renderer.render_actions_for(&vec![Draw::ShowFrame]);
+    assert!(count_acknowledgements(&mut acknowledgements) == 1);
+}
+
+///
+/// Offscreen context that fails the test if it is ever asked to allocate a render target
+///
+struct NoAllocationContext;
+
+struct NoAllocationTarget;
+
+impl OffscreenRenderTarget for NoAllocationTarget {
+    fn render<ActionIter: IntoIterator<Item=RenderAction>>(&mut self, _actions: ActionIter) { }
+    fn finish(&mut self) { }
+    fn realize(self) -> Vec<u8> { vec![] }
+}
+
+impl RenderCapabilities for NoAllocationTarget {
+    fn supports_action(&self, _action: &RenderAction) -> bool { true }
+}
+
+impl OffscreenRenderContext for NoAllocationContext {
+    type RenderTarget = NoAllocationTarget;
+
+    fn create_render_target(&mut self, width: usize, height: usize) -> NoAllocationTarget {
+        panic!("Tried to allocate a {}x{} render target", width, height);
+    }
+}
+
+#[test]
+fn oversized_offscreen_render_returns_error() {
+    let mut context = NoAllocationContext;
+    let result      = executor::block_on(try_render_canvas_offscreen(&mut context, 100000, 100000, 1.0, DEFAULT_MAX_RENDER_DIMENSION, stream::iter(vec![Draw::ClearCanvas(Color::Rgba(1.0, 1.0, 1.0, 1.0))])));
+
+    assert!(result == Err(RenderTargetError::TooLarge { width: 100000, height: 100000, max_dimension: DEFAULT_MAX_RENDER_DIMENSION }), "{:?}", result);
+}
+
+///
+/// Returns the lengths of the 'draw indexed' instructions in a set of render actions
+///
+fn draw_indexed_lengths(actions: &Vec<RenderAction>) -> Vec<usize> {
+    actions.iter().filter_map(|action| match action { RenderAction::DrawIndexedTriangles(_, _, length) => Some(*length), _ => None }).collect()
+}
+
+///
+/// Creates a drawing of a row of circles, optionally changing the transform halfway through
+///
+fn row_of_circles(num_circles: usize, change_transform: bool) -> Vec<Draw> {
+    let mut drawing = vec![];
+
+    for idx in 0..num_circles {
+        if change_transform && idx == num_circles/2 {
+            drawing.transform(Transform2D::translate(10.0, 0.0));
+        }
+
+        drawing.new_path();
+        drawing.circle((idx as f32) * 50.0, 100.0, 20.0);
+        drawing.fill_color(Color::Rgba(1.0, (idx as f32) / 10.0, 0.0, 1.0));
+        drawing.fill();
+    }
+
+    drawing
+}
+
+#[test]
+fn same_state_fills_are_batched() {
+    let single_circle   = CanvasRenderer::new().render_actions_for(&row_of_circles(1, false));
+    let ten_circles     = CanvasRenderer::new().render_actions_for(&row_of_circles(10, false));
+
+    // All of the fills use the flat colour shader with the same transform, so they should be drawn with a single call containing every circle
+    let single_lengths  = draw_indexed_lengths(&single_circle);
+    let batched_lengths = draw_indexed_lengths(&ten_circles);
+
+    assert!(single_lengths.len() == 1, "{:?}", single_circle);
+    assert!(batched_lengths == vec![single_lengths[0] * 10], "{:?}", ten_circles);
+
+    // Changing the transform between fills needs a separate draw call
+    let transformed     = CanvasRenderer::new().render_actions_for(&row_of_circles(10, true));
+    assert!(count_draw_indexed(&transformed) == 2, "{:?}", transformed);
+}
+
+#[test]
+fn restore_removes_shapes_drawn_after_store() {
+    let mut base = vec![];
+    base.new_path();
+    base.rect(0.0, 0.0, 100.0, 100.0);
+    base.fill();
+    base.store();
+
+    let mut overlay = vec![];
+    overlay.new_path();
+    overlay.rect(200.0, 200.0, 300.0, 300.0);
+    overlay.fill();
+
+    let base_only       = CanvasRenderer::new().render_actions_for(&base);
+
+    // Render the base and the overlay, then restore back to just the base
+    let mut renderer    = CanvasRenderer::new();
+    renderer.render_actions_for(&base.iter().cloned().chain(overlay.iter().cloned()).collect::<Vec<_>>());
+    renderer.render_actions_for(&vec![Draw::Restore]);
+
+    // Redrawing should only draw the base (the overlay should not have been batched into its vertex buffer)
+    let redrawn         = renderer.render_actions_for(&Vec::<Draw>::new());
+
+    assert!(!draw_indexed_lengths(&base_only).is_empty());
+    assert!(draw_indexed_lengths(&redrawn) == draw_indexed_lengths(&base_only), "{:?} {:?}", redrawn, base_only);
+}
+
+#[test]
+#[cfg(any(feature="opengl", feature="osx-metal", feature="render-wgpu"))]
+fn id_buffer_reports_shape_at_point() {
+    let mut context = match initialize_offscreen_rendering() {
+        Ok(context)     => context,
+        Err(err)        => { println!("Test not run: {:?}", err); return; }
+    };
+
+    let rect = |x1: f32, y1: f32, x2: f32, y2: f32| {
+        let mut drawing = vec![];
+        drawing.new_path();
+        drawing.rect(x1, y1, x2, y2);
+        drawing.fill_color(Color::Rgba(0.5, 0.5, 0.5, 0.5));
+        drawing.fill();
+        drawing
+    };
+
+    // Two tagged shapes, and an untagged shape covering part of the second one
+    let mut drawing = vec![];
+    drawing.push((None, Draw::ClearCanvas(Color::Rgba(1.0, 1.0, 1.0, 1.0))));
+    drawing.push((None, Draw::CanvasHeight(100.0)));
+    drawing.push((None, Draw::CenterRegion((0.0, 0.0), (100.0, 100.0))));
+    drawing.extend(rect(10.0, 10.0, 40.0, 90.0).into_iter().map(|draw| (Some(ShapeId(1)), draw)));
+    drawing.extend(rect(50.0, 10.0, 90.0, 90.0).into_iter().map(|draw| (Some(ShapeId(2)), draw)));
+    drawing.extend(rect(60.0, 40.0, 80.0, 60.0).into_iter().map(|draw| (None, draw)));
+
+    let id_buffer = executor::block_on(render_id_buffer(&mut context, 100, 100, 1.0, stream::iter(drawing)));
+
+    assert!(id_buffer.id_at(25, 50) == Some(ShapeId(1)), "{:?}", id_buffer.id_at(25, 50));
+    assert!(id_buffer.id_at(55, 50) == Some(ShapeId(2)), "{:?}", id_buffer.id_at(55, 50));
+    assert!(id_buffer.id_at(70, 50) == None, "{:?}", id_buffer.id_at(70, 50));
+    assert!(id_buffer.id_at(95, 50) == None, "{:?}", id_buffer.id_at(95, 50));
+    assert!(id_buffer.id_at(500, 50) == None);
+}
+
+#[test]
+#[cfg(any(feature="opengl", feature="osx-metal", feature="render-wgpu"))]
+fn offscreen_renderer_renders_several_drawings() {
+    let context = match initialize_offscreen_rendering() {
+        Ok(context)     => context,
+        Err(err)        => { println!("Test not run: {:?}", err); return; }
+    };
+
+    let mut renderer = OffscreenRenderer::new(context);
+
+    // Render three different drawings at different sizes through the same renderer
+    let red     = executor::block_on(renderer.render(vec![Draw::ClearCanvas(Color::Rgba(1.0, 0.0, 0.0, 1.0))], (32, 32)));
+    let green   = executor::block_on(renderer.render(vec![Draw::ClearCanvas(Color::Rgba(0.0, 1.0, 0.0, 1.0))], (64, 16)));
+
+    let mut square = vec![];
+    square.clear_canvas(Color::Rgba(0.0, 0.0, 1.0, 1.0));
+    square.canvas_height(100.0);
+    square.center_region(0.0, 0.0, 100.0, 100.0);
+    square.new_path();
+    square.rect(25.0, 25.0, 75.0, 75.0);
+    square.fill_color(Color::Rgba(1.0, 1.0, 1.0, 1.0));
+    square.fill();
+    let blue    = executor::block_on(renderer.render(square, (100, 100)));
+
+    assert!(red.len() == 32*32*4);
+    assert!(green.len() == 64*16*4);
+    assert!(blue.len() == 100*100*4);
+
+    assert!(red[0..4] == [255, 0, 0, 255], "{:?}", &red[0..4]);
+    assert!(green[0..4] == [0, 255, 0, 255], "{:?}", &green[0..4]);
+    assert!(blue[0..4] == [0, 0, 255, 255], "{:?}", &blue[0..4]);
+
+    // Nothing from the earlier drawings should appear in the last one
+    let center = (50 + 50*100) * 4;
+    assert!(blue[center..center+4] == [255, 255, 255, 255], "{:?}", &blue[center..center+4]);
+}
+
+#[test]
+#[cfg(any(feature="opengl", feature="osx-metal", feature="render-wgpu"))]
+fn render_offscreen_at_double_scale() {
+    let mut context = match initialize_offscreen_rendering() {
+        Ok(context)     => context,
+        Err(err)        => { println!("Test not run: {:?}", err); return; }
+    };
+
+    // A 10x10 square in a 50x40 drawing, in logical units
+    let mut drawing = vec![];
+    drawing.clear_canvas(Color::Rgba(0.0, 0.0, 0.0, 1.0));
+    drawing.new_path();
+    drawing.rect(10.0, 15.0, 20.0, 25.0);
+    drawing.fill_color(Color::Rgba(1.0, 1.0, 1.0, 1.0));
+    drawing.fill();
+
+    let image       = executor::block_on(render_canvas_offscreen_at_scale(&mut context, 50, 40, 2.0, stream::iter(drawing)));
+
+    // Image should be twice the logical size
+    assert!(image.len() == 100*80*4, "{}", image.len());
+
+    // Logical coordinates are doubled (the square is centered vertically, so this doesn't depend on which way up the image is)
+    let pixel       = |x: usize, y: usize| { let pos = (x + y*100)*4; [image[pos], image[pos+1], image[pos+2], image[pos+3]] };
+
+    assert!(pixel(30, 40) == [255, 255, 255, 255], "{:?}", pixel(30, 40));
+    assert!(pixel(21, 40) == [255, 255, 255, 255], "{:?}", pixel(21, 40));
+    assert!(pixel(15, 40) == [0, 0, 0, 255], "{:?}", pixel(15, 40));
+    assert!(pixel(45, 40) == [0, 0, 0, 255], "{:?}", pixel(45, 40));
+    assert!(pixel(30, 25) == [0, 0, 0, 255], "{:?}", pixel(30, 25));
+}
+
+#[test]
+fn drop_renderer_during_render() {
+    // Lots of shapes, so the workers are still tessellating after the first few actions have been generated
+    let mut drawing = vec![];
+    drawing.clear_canvas(Color::Rgba(0.0, 0.0, 0.0, 1.0));
+    drawing.canvas_height(1000.0);
+
+    for idx in 0..1000 {
+        drawing.new_path();
+        drawing.circle((idx % 40) as f32 * 25.0, (idx / 40) as f32 * 40.0, 12.0);
+        drawing.fill_color(Color::Rgba(1.0, 0.0, 0.0, 1.0));
+        drawing.fill();
+    }
+
+    for _ in 0..10 {
+        let mut renderer = CanvasRenderer::new();
+        renderer.set_viewport(0.0..1024.0, 0.0..768.0, 1024.0, 768.0, 1.0);
+
+        executor::block_on(async {
+            // Read the first action then stop rendering
+            let mut draw_stream = renderer.draw(drawing.clone().into_iter());
+            draw_stream.next().await;
+        });
+
+        // There's no explicit Drop for the renderer: teardown relies on each worker's Desync finishing its queued
+        // tessellation jobs when it's dropped, so this should neither panic nor leave the workers running
+        drop(renderer);
+    }
+
+    // A new renderer should still work normally
+    let actions = CanvasRenderer::new().render_actions_for(&drawing);
+    assert!(count_draw_indexed(&actions) > 0);
+}